@@ -0,0 +1,130 @@
+//! ESRI ASCII grids (`.asc`).
+//!
+//! The header gives `ncols`, `nrows`, the lower-left corner (`xllcorner`,
+//! `yllcorner`) or centre (`xllcenter`, `yllcenter`), the cell size
+//! (`cellsize`, or `dx` and `dy`) and an optional `nodata_value`; the values
+//! follow row by row from the top, in any line layout.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::io::asc::read_asc;
+//!
+//! let grid = "\
+//! ncols 3
+//! nrows 2
+//! xllcorner 500.0
+//! yllcorner 200.0
+//! cellsize 25
+//! NODATA_value -9999
+//! -4 -5 -6
+//! -7 -8 -9999
+//! ";
+//! let dem = read_asc::<f64, _>(grid.as_bytes()).unwrap();
+//! assert_eq!(dem.y_max, 250.0);
+//! assert_eq!(dem.get(2, 1), None);
+//! assert_eq!(dem.sample(Point2::new(525.0, 225.0)), Some(-6.0));
+//! ```
+
+use std::io::Read;
+
+use crate::Float;
+use crate::io::{read_text, Lines, Raster, Result};
+
+/// Read an ESRI ASCII grid.
+pub fn read_asc<T: Float, R: Read>(reader: R) -> Result<Raster<T>> {
+    let text = read_text(reader)?;
+    let mut lines = Lines::new(&text);
+    let (mut ncols, mut nrows, mut cellsize, mut dx, mut dy) = (None, None, None, None, None);
+    let (mut xll, mut yll, mut centre, mut nodata) = (None, None, false, None);
+    let mut first_data = None;
+    while let Some(l) = lines.next_line() {
+        let mut t = l.split_whitespace();
+        let Some(key) = t.next() else { continue };
+        let value = t.next();
+        match key.to_ascii_lowercase().as_str() {
+            "ncols" => ncols = Some(lines.parse::<usize>(value, "ncols")?),
+            "nrows" => nrows = Some(lines.parse::<usize>(value, "nrows")?),
+            "xllcorner" => xll = Some(lines.parse::<f64>(value, "xllcorner")?),
+            "yllcorner" => yll = Some(lines.parse::<f64>(value, "yllcorner")?),
+            "xllcenter" | "xllcentre" => {
+                xll = Some(lines.parse::<f64>(value, "xllcenter")?);
+                centre = true;
+            }
+            "yllcenter" | "yllcentre" => yll = Some(lines.parse::<f64>(value, "yllcenter")?),
+            "cellsize" => cellsize = Some(lines.parse::<f64>(value, "cellsize")?),
+            "dx" => dx = Some(lines.parse::<f64>(value, "dx")?),
+            "dy" => dy = Some(lines.parse::<f64>(value, "dy")?),
+            "nodata_value" => nodata = Some(lines.parse::<f64>(value, "nodata_value")?),
+            _ => {
+                first_data = Some(l);
+                break;
+            }
+        }
+    }
+    let (Some(ncols), Some(nrows), Some(xll), Some(yll)) = (ncols, nrows, xll, yll) else {
+        return lines.err("header needs ncols, nrows, xllcorner and yllcorner");
+    };
+    let (Some(dx), Some(dy)) = (dx.or(cellsize), dy.or(cellsize)) else {
+        return lines.err("header needs cellsize");
+    };
+    let (x_min, y_min) = if centre { (xll - dx / 2.0, yll - dy / 2.0) } else { (xll, yll) };
+
+    let Some(n) = ncols.checked_mul(nrows) else {
+        return lines.err(format!("{} x {} grid is too large", ncols, nrows));
+    };
+    // the header is not trusted to size the buffer; the values present do
+    let mut values = Vec::new();
+    let mut line = first_data;
+    while let Some(l) = line {
+        for tok in l.split_whitespace() {
+            if values.len() == n {
+                return lines.err(format!("more than {} values", n));
+            }
+            values.push(T::from(lines.parse::<f64>(Some(tok), "value")?).unwrap());
+        }
+        line = lines.next_line();
+    }
+    if values.len() != n {
+        return lines.err(format!("expected {} values, found {}", n, values.len()));
+    }
+    let t = |v: f64| T::from(v).unwrap();
+    Ok(Raster {
+        ncols,
+        nrows,
+        x_min: t(x_min),
+        y_max: t(y_min + dy * nrows as f64),
+        dx: t(dx),
+        dy: t(dy),
+        nodata: nodata.map(t),
+        values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::IoError;
+
+    #[test]
+    fn centre_registration_and_bad_values() {
+        let grid = "ncols 2\nnrows 2\nxllcenter 0.5\nyllcenter 0.5\ncellsize 1\n1 2 3\n4\n";
+        let r = read_asc::<f64, _>(grid.as_bytes()).unwrap();
+        assert_eq!((r.x_min, r.y_max), (0.0, 2.0));
+        assert_eq!(r.values, vec![1.0, 2.0, 3.0, 4.0]);
+
+        match read_asc::<f64, _>("ncols 1\nnrows 1\nxllcorner 0\nyllcorner 0\ncellsize 1\nabc\n".as_bytes()) {
+            Err(IoError::Parse { line, .. }) => assert_eq!(line, 6),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn oversized_headers_are_errors() {
+        for dims in ["ncols 4294967296\nnrows 4294967296", "ncols 1000000\nnrows 1000000"] {
+            let grid = format!("{}\nxllcorner 0\nyllcorner 0\ncellsize 1\n1 2\n", dims);
+            assert!(matches!(read_asc::<f64, _>(grid.as_bytes()), Err(IoError::Parse { .. })));
+        }
+    }
+}
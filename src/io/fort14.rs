@@ -0,0 +1,335 @@
+//! ADCIRC `fort.14` grid files.
+//!
+//! A `fort.14` holds a title, nodes with depths (positive down), triangle
+//! elements, and open and land boundary node strings. [`read_fort14`]
+//! returns them as a [`Fort14`], whose `mesh` is an ordinary [`Mesh2`] ready
+//! for `polygon_area`, `Mesh2::boundary_loops`, and `triangle_area` after
+//! `Mesh2::to_mesh3`. Node
+//! references in boundaries are 0-based node indices; the file's own node
+//! numbers are kept in `node_ids` and written back by [`write_fort14`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::fort14::{read_fort14, write_fort14};
+//!
+//! let grid = "\
+//! bay
+//! 2 4
+//! 1 0.0 0.0 5.0
+//! 2 1.0 0.0 6.0
+//! 3 1.0 1.0 7.0
+//! 4 0.0 1.0 8.0
+//! 1 3 1 2 3
+//! 2 3 1 3 4
+//! 1 ! NOPE
+//! 2 ! NETA
+//! 2 ! NVDLL
+//! 2
+//! 3
+//! 1 ! NBOU
+//! 3 ! NVEL
+//! 3 0 ! NVELL IBTYPE
+//! 3
+//! 4
+//! 1
+//! ";
+//! let f14 = read_fort14::<f64, _>(grid.as_bytes()).unwrap();
+//! assert_eq!(f14.mesh.num_cells(), 2);
+//! assert_eq!(f14.depths[2], 7.0);
+//! assert_eq!(f14.open_boundaries[0].nodes, vec![1, 2]);
+//! assert_eq!(f14.mesh.boundary_loops().len(), 1);
+//!
+//! let mut out = Vec::new();
+//! write_fort14(&mut out, &f14).unwrap();
+//! assert_eq!(read_fort14::<f64, _>(&out[..]).unwrap(), f14);
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2};
+use crate::io::{read_text, IoError, Lines, Result};
+use crate::mesh::{Cell, Mesh2};
+
+/// An open (elevation-specified) boundary segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenBoundary {
+    /// ADCIRC boundary type `IBTYPEE` (0 unless the file gives one)
+    pub ibtypee: i32,
+    /// Boundary nodes, as node indices
+    pub nodes: Vec<usize>,
+}
+
+/// A land boundary segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandBoundary {
+    /// ADCIRC boundary type `IBTYPE` (0 mainland, 1 island, 3/4/... barriers)
+    pub ibtype: i32,
+    /// Boundary nodes, as node indices
+    pub nodes: Vec<usize>,
+    /// Text after the node number on each line (barrier heights, paired
+    /// nodes, ...), kept verbatim; empty for simple boundaries
+    pub extra: Vec<String>,
+}
+
+/// Contents of a `fort.14` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fort14<T: Float> {
+    /// Grid title (first line)
+    pub title: String,
+    /// Horizontal mesh of triangles
+    pub mesh: Mesh2<T>,
+    /// Depth of each node, positive below the datum
+    pub depths: Vec<T>,
+    /// File node number of each node
+    pub node_ids: Vec<usize>,
+    /// Open (elevation-specified) boundary segments
+    pub open_boundaries: Vec<OpenBoundary>,
+    /// Land boundary segments
+    pub land_boundaries: Vec<LandBoundary>,
+}
+
+impl<T: Float> Fort14<T> {
+    /// Grid from a triangle mesh and depths, numbered from 1, without boundaries.
+    pub fn new(title: &str, mesh: Mesh2<T>, depths: Vec<T>) -> Self {
+        let node_ids = (1..=mesh.num_nodes()).collect();
+        Fort14 { title: title.into(), mesh, depths, node_ids, open_boundaries: Vec::new(), land_boundaries: Vec::new() }
+    }
+}
+
+/// Read an ADCIRC `fort.14` grid.
+pub fn read_fort14<T: Float, R: Read>(reader: R) -> Result<Fort14<T>> {
+    let text = read_text(reader)?;
+    let mut lines = Lines::new(&text);
+    let title = lines.expect_line()?.trim().into();
+
+    let mut t = lines.expect_line()?.split_whitespace();
+    let ne: usize = lines.parse(t.next(), "element count")?;
+    let np: usize = lines.parse(t.next(), "node count")?;
+
+    let mut mesh = Mesh2::new();
+    // counts come from the file, so vectors grow with the lines actually read
+    let mut depths = Vec::new();
+    let mut node_ids = Vec::new();
+    let mut index = BTreeMap::new();
+    for _ in 0..np {
+        let mut t = lines.expect_line()?.split_whitespace();
+        let id: usize = lines.parse(t.next(), "node number")?;
+        let x: f64 = lines.parse(t.next(), "x")?;
+        let y: f64 = lines.parse(t.next(), "y")?;
+        let d: f64 = lines.parse(t.next(), "depth")?;
+        if index.insert(id, node_ids.len()).is_some() {
+            return lines.err(format!("duplicate node number {}", id));
+        }
+        node_ids.push(id);
+        mesh.add_node(Point2::new(T::from(x).unwrap(), T::from(y).unwrap()));
+        depths.push(T::from(d).unwrap());
+    }
+    let node = |lines: &Lines, tok: Option<&str>| -> Result<usize> {
+        let id: usize = lines.parse(tok, "node number")?;
+        match index.get(&id) {
+            Some(&i) => Ok(i),
+            None => lines.err(format!("unknown node {}", id)),
+        }
+    };
+
+    for _ in 0..ne {
+        let mut t = lines.expect_line()?.split_whitespace();
+        let _id: usize = lines.parse(t.next(), "element number")?;
+        let nv: usize = lines.parse(t.next(), "element node count")?;
+        if nv != 3 {
+            return Err(IoError::Unsupported(format!("{}-node elements in fort.14", nv)));
+        }
+        let cell = [node(&lines, t.next())?, node(&lines, t.next())?, node(&lines, t.next())?];
+        mesh.add_cell(Cell::Triangle(cell));
+    }
+
+    let mut open_boundaries = Vec::new();
+    let mut land_boundaries = Vec::new();
+    // boundary information is optional; trailing blank lines mean none
+    let header = loop {
+        match lines.next_line() {
+            Some(l) if l.trim().is_empty() => continue,
+            other => break other,
+        }
+    };
+    if let Some(l) = header {
+        let nope: usize = lines.parse(l.split_whitespace().next(), "NOPE")?;
+        lines.expect_line()?; // NETA
+        for _ in 0..nope {
+            let mut t = lines.expect_line()?.split_whitespace();
+            let n: usize = lines.parse(t.next(), "NVDLL")?;
+            // older grids leave out IBTYPEE
+            let ibtypee: i32 = match t.next().filter(|tok| !tok.starts_with('!')) {
+                Some(tok) => lines.parse(Some(tok), "IBTYPEE")?,
+                None => 0,
+            };
+            let nodes = (0..n)
+                .map(|_| {
+                    let l = lines.expect_line()?;
+                    node(&lines, l.split_whitespace().next())
+                })
+                .collect::<Result<Vec<_>>>()?;
+            open_boundaries.push(OpenBoundary { ibtypee, nodes });
+        }
+        let l = lines.expect_line()?;
+        let nbou: usize = lines.parse(l.split_whitespace().next(), "NBOU")?;
+        lines.expect_line()?; // NVEL
+        for _ in 0..nbou {
+            let mut t = lines.expect_line()?.split_whitespace();
+            let n: usize = lines.parse(t.next(), "NVELL")?;
+            let ibtype: i32 = lines.parse(t.next(), "IBTYPE")?;
+            let mut seg = LandBoundary { ibtype, nodes: Vec::new(), extra: Vec::new() };
+            for _ in 0..n {
+                let l = lines.expect_line()?.trim();
+                let (first, rest) = l.split_once(char::is_whitespace).unwrap_or((l, ""));
+                seg.nodes.push(node(&lines, Some(first))?);
+                // drop Fortran-style trailing comments from simple boundaries
+                let rest = rest.trim();
+                seg.extra.push(if rest.starts_with('!') { String::new() } else { rest.into() });
+            }
+            land_boundaries.push(seg);
+        }
+    }
+    Ok(Fort14 { title, mesh, depths, node_ids, open_boundaries, land_boundaries })
+}
+
+/// Write an ADCIRC `fort.14` grid. The mesh must contain only triangles,
+/// with one node number and one depth per node.
+pub fn write_fort14<T: Float, W: Write>(mut w: W, grid: &Fort14<T>) -> Result<()> {
+    let mesh = &grid.mesh;
+    let np = mesh.num_nodes();
+    if grid.node_ids.len() != np || grid.depths.len() != np {
+        return Err(IoError::Unsupported(format!(
+            "{} nodes with {} node numbers and {} depths",
+            np,
+            grid.node_ids.len(),
+            grid.depths.len()
+        )));
+    }
+    let open = grid.open_boundaries.iter().flat_map(|b| &b.nodes);
+    let land = grid.land_boundaries.iter().flat_map(|b| &b.nodes);
+    let cells = mesh.cells.iter().flat_map(|c| c.nodes());
+    if let Some(n) = cells.chain(open).chain(land).find(|&&n| n >= np) {
+        return Err(IoError::Unsupported(format!("reference to node {} of {}", n, np)));
+    }
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    writeln!(w, "{}", grid.title)?;
+    writeln!(w, "{} {}", mesh.num_cells(), mesh.num_nodes())?;
+    for (i, p) in mesh.nodes.iter().enumerate() {
+        writeln!(w, "{} {} {} {}", grid.node_ids[i], f(p.x), f(p.y), f(grid.depths[i]))?;
+    }
+    for (e, cell) in mesh.cells.iter().enumerate() {
+        let Cell::Triangle([a, b, c]) = cell else {
+            return Err(IoError::Unsupported("non-triangle cells in fort.14".into()));
+        };
+        let id = |n: &usize| grid.node_ids[*n];
+        writeln!(w, "{} 3 {} {} {}", e + 1, id(a), id(b), id(c))?;
+    }
+    let neta: usize = grid.open_boundaries.iter().map(|b| b.nodes.len()).sum();
+    writeln!(w, "{} ! NOPE", grid.open_boundaries.len())?;
+    writeln!(w, "{} ! NETA", neta)?;
+    for seg in &grid.open_boundaries {
+        writeln!(w, "{} {} ! NVDLL IBTYPEE", seg.nodes.len(), seg.ibtypee)?;
+        for &n in &seg.nodes {
+            writeln!(w, "{}", grid.node_ids[n])?;
+        }
+    }
+    let nvel: usize = grid.land_boundaries.iter().map(|b| b.nodes.len()).sum();
+    writeln!(w, "{} ! NBOU", grid.land_boundaries.len())?;
+    writeln!(w, "{} ! NVEL", nvel)?;
+    for seg in &grid.land_boundaries {
+        writeln!(w, "{} {} ! NVELL IBTYPE", seg.nodes.len(), seg.ibtype)?;
+        for (k, &n) in seg.nodes.iter().enumerate() {
+            match seg.extra.get(k).filter(|e| !e.is_empty()) {
+                Some(extra) => writeln!(w, "{} {}", grid.node_ids[n], extra)?,
+                None => writeln!(w, "{}", grid.node_ids[n])?,
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::triangle_area;
+    use crate::mesh::CellPoints;
+
+    #[test]
+    fn barrier_boundary_roundtrip() {
+        let grid = "\
+weir test
+2 5
+10 0 0 1
+20 2 0 1
+30 2 2 1
+40 0 2 1
+50 1 1 3.5
+1 3 10 20 50
+2 3 20 30 50
+0 ! NOPE
+0 ! NETA
+1 ! NBOU
+2 ! NVEL
+2 4
+10 40 1.5 1.0 1.0
+20 30 1.5 1.0 1.0
+";
+        let f14 = read_fort14::<f64, _>(grid.as_bytes()).unwrap();
+        assert_eq!(f14.node_ids, vec![10, 20, 30, 40, 50]);
+        assert_eq!(f14.mesh.cells[1], Cell::Triangle([1, 2, 4]));
+        assert_eq!(f14.land_boundaries[0].nodes, vec![0, 1]);
+        assert_eq!(f14.land_boundaries[0].extra[0], "40 1.5 1.0 1.0");
+        let area: f64 = f14
+            .mesh
+            .to_mesh3(0.0)
+            .cell_points()
+            .map(|c| match c {
+                CellPoints::Triangle([a, b, c]) => triangle_area(a, b, c),
+                _ => 0.0,
+            })
+            .sum();
+        assert!((area - 2.0).abs() < 1e-12);
+
+        let mut out = Vec::new();
+        write_fort14(&mut out, &f14).unwrap();
+        assert_eq!(read_fort14::<f64, _>(&out[..]).unwrap(), f14);
+    }
+
+    #[test]
+    fn unknown_node_is_reported_with_line() {
+        let grid = "t\n1 3\n1 0 0 1\n2 1 0 1\n3 0 1 1\n1 3 1 2 9\n";
+        match read_fort14::<f64, _>(grid.as_bytes()) {
+            Err(IoError::Parse { line, .. }) => assert_eq!(line, 6),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn open_boundary_type_and_blank_tail() {
+        let head = "t\n1 3\n1 0 0 1\n2 1 0 1\n3 0 1 1\n1 3 1 2 3\n";
+        let grid = format!("{head}1 ! NOPE\n2 ! NETA\n2 12 ! NVDLL IBTYPEE\n1\n2\n0 ! NBOU\n0 ! NVEL\n");
+        let f14 = read_fort14::<f64, _>(grid.as_bytes()).unwrap();
+        assert_eq!(f14.open_boundaries, vec![OpenBoundary { ibtypee: 12, nodes: vec![0, 1] }]);
+        let mut out = Vec::new();
+        write_fort14(&mut out, &f14).unwrap();
+        assert_eq!(read_fort14::<f64, _>(&out[..]).unwrap(), f14);
+
+        let f14 = read_fort14::<f64, _>(format!("{head}\n  \n").as_bytes()).unwrap();
+        assert!(f14.open_boundaries.is_empty() && f14.land_boundaries.is_empty());
+    }
+
+    #[test]
+    fn huge_counts_and_short_vectors_are_errors() {
+        assert!(read_fort14::<f64, _>("t\n1 18446744073709551615\n1 0 0 1\n".as_bytes()).is_err());
+
+        let grid = "t\n1 3\n1 0 0 1\n2 1 0 1\n3 0 1 1\n1 3 1 2 3\n";
+        let mut f14 = read_fort14::<f64, _>(grid.as_bytes()).unwrap();
+        f14.depths.pop();
+        assert!(matches!(write_fort14(Vec::new(), &f14), Err(IoError::Unsupported(_))));
+    }
+}
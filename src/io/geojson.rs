@@ -0,0 +1,344 @@
+//! GeoJSON (RFC 7946) geometries.
+//!
+//! [`read_geojson`] accepts a `FeatureCollection`, a single `Feature`, a
+//! `GeometryCollection` or a bare geometry, and returns the geometries in
+//! document order; features without geometry are skipped and properties
+//! are ignored. [`write_geojson`] writes a `FeatureCollection` with one
+//! property-less feature per geometry. Ring orientation is kept as given.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::io::Geometry;
+//! use mesh_geometry::io::geojson::{read_geojson, write_geojson};
+//! use mesh_geometry::queries::point_to_segment_distance;
+//!
+//! let json = r#"{
+//!   "type": "FeatureCollection",
+//!   "features": [
+//!     { "type": "Feature", "properties": { "name": "jetty" },
+//!       "geometry": { "type": "LineString", "coordinates": [[0, 0], [0, 50]] } },
+//!     { "type": "Feature", "properties": {},
+//!       "geometry": { "type": "Polygon",
+//!                     "coordinates": [[[0, 0], [100, 0], [100, 80], [0, 80], [0, 0]]] } }
+//!   ]
+//! }"#;
+//! let geoms = read_geojson::<f64, _>(json.as_bytes()).unwrap();
+//! let Geometry::LineString(jetty) = &geoms[0] else { panic!() };
+//! assert_eq!(point_to_segment_distance(Point2::new(3.0, 20.0), jetty[0], jetty[1]), 3.0);
+//! assert_eq!(geoms[1].polygons()[0].area(), 8000.0);
+//!
+//! let mut out = Vec::new();
+//! write_geojson(&mut out, &geoms).unwrap();
+//! assert_eq!(read_geojson::<f64, _>(&out[..]).unwrap(), geoms);
+//! ```
+
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2};
+use crate::io::geometry::open_ring;
+use crate::io::{read_text, Geometry, IoError, Polygon, Result};
+
+/// Read the geometries of a GeoJSON document.
+pub fn read_geojson<T: Float, R: Read>(reader: R) -> Result<Vec<Geometry<T>>> {
+    let text = read_text(reader)?;
+    let mut p = Parser { text: &text, pos: 0, depth: 0 };
+    let root = p.value()?;
+    p.skip_space();
+    if p.pos < text.len() {
+        return p.err("unexpected text after the document");
+    }
+    let mut out = Vec::new();
+    collect(&root, &mut out)?;
+    Ok(out)
+}
+
+/// Write `geometries` as a GeoJSON `FeatureCollection`.
+pub fn write_geojson<T: Float, W: Write>(mut w: W, geometries: &[Geometry<T>]) -> Result<()> {
+    writeln!(w, "{{\"type\": \"FeatureCollection\", \"features\": [")?;
+    for (i, g) in geometries.iter().enumerate() {
+        let sep = if i + 1 < geometries.len() { "," } else { "" };
+        writeln!(w, "  {{\"type\": \"Feature\", \"properties\": {{}}, \"geometry\": {}}}{}", geometry_json(g), sep)?;
+    }
+    writeln!(w, "]}}")?;
+    Ok(())
+}
+
+fn geometry_json<T: Float>(g: &Geometry<T>) -> String {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    let pos = |p: &Point2<T>| format!("[{}, {}]", f(p.x), f(p.y));
+    let array = |items: Vec<String>| format!("[{}]", items.join(", "));
+    let line = |l: &[Point2<T>]| array(l.iter().map(pos).collect());
+    let ring = |r: &[Point2<T>]| array(r.iter().chain(r.first()).map(pos).collect());
+    let polygon = |p: &Polygon<T>| array(core::iter::once(&p.exterior).chain(&p.holes).map(|r| ring(r)).collect());
+    let (kind, coordinates) = match g {
+        Geometry::Point(p) => ("Point", pos(p)),
+        Geometry::LineString(l) => ("LineString", line(l)),
+        Geometry::Polygon(p) => ("Polygon", polygon(p)),
+        Geometry::MultiPoint(m) => ("MultiPoint", line(m)),
+        Geometry::MultiLineString(m) => ("MultiLineString", array(m.iter().map(|l| line(l)).collect())),
+        Geometry::MultiPolygon(m) => ("MultiPolygon", array(m.iter().map(polygon).collect())),
+    };
+    format!("{{\"type\": \"{}\", \"coordinates\": {}}}", kind, coordinates)
+}
+
+/// Append the geometries found in a GeoJSON object.
+fn collect<T: Float>(obj: &Json, out: &mut Vec<Geometry<T>>) -> Result<()> {
+    let err = |msg: String| IoError::Parse { line: 0, msg };
+    let kind = match obj.get("type") {
+        Some(Json::String(s)) => s.as_str(),
+        _ => return Err(err("object without a \"type\"".into())),
+    };
+    match kind {
+        "FeatureCollection" => match obj.get("features") {
+            Some(Json::Array(features)) => features.iter().try_for_each(|f| collect(f, out)),
+            _ => Err(err("FeatureCollection without \"features\"".into())),
+        },
+        "Feature" => match obj.get("geometry") {
+            None | Some(Json::Null) => Ok(()),
+            Some(g) => collect(g, out),
+        },
+        "GeometryCollection" => match obj.get("geometries") {
+            Some(Json::Array(geoms)) => geoms.iter().try_for_each(|g| collect(g, out)),
+            _ => Err(err("GeometryCollection without \"geometries\"".into())),
+        },
+        _ => {
+            let c = obj.get("coordinates").ok_or_else(|| err(format!("{} without \"coordinates\"", kind)))?;
+            let polygon = |c: &Json| -> Result<Polygon<T>> {
+                let mut rings = c.items()?.iter().map(|r| r.positions().map(open_ring));
+                let exterior = rings.next().transpose()?.unwrap_or_default();
+                Ok(Polygon { exterior, holes: rings.collect::<Result<_>>()? })
+            };
+            out.push(match kind {
+                "Point" => Geometry::Point(c.position()?),
+                "LineString" => Geometry::LineString(c.positions()?),
+                "Polygon" => Geometry::Polygon(polygon(c)?),
+                "MultiPoint" => Geometry::MultiPoint(c.positions()?),
+                "MultiLineString" => Geometry::MultiLineString(c.items()?.iter().map(Json::positions).collect::<Result<_>>()?),
+                "MultiPolygon" => Geometry::MultiPolygon(c.items()?.iter().map(polygon).collect::<Result<_>>()?),
+                _ => return Err(IoError::Unsupported(format!("GeoJSON type '{}'", kind))),
+            });
+            Ok(())
+        }
+    }
+}
+
+/// A parsed JSON value.
+#[derive(Debug)]
+enum Json {
+    Null,
+    Bool,
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn items(&self) -> Result<&[Json]> {
+        match self {
+            Json::Array(items) => Ok(items),
+            _ => Err(IoError::Parse { line: 0, msg: "expected an array of coordinates".into() }),
+        }
+    }
+
+    /// `[x, y, ...]`, dropping extra ordinates.
+    fn position<T: Float>(&self) -> Result<Point2<T>> {
+        match self.items()? {
+            [Json::Number(x), Json::Number(y), ..] => Ok(Point2::new(T::from(*x).unwrap(), T::from(*y).unwrap())),
+            _ => Err(IoError::Parse { line: 0, msg: "a position needs two numbers".into() }),
+        }
+    }
+
+    fn positions<T: Float>(&self) -> Result<Vec<Point2<T>>> {
+        self.items()?.iter().map(Json::position).collect()
+    }
+}
+
+/// Deepest array/object nesting accepted; GeoJSON needs a handful of levels,
+/// and recursing without a bound would overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        let line = self.text[..self.pos].matches('\n').count() + 1;
+        Err(IoError::Parse { line, msg: msg.into() })
+    }
+
+    fn skip_space(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_space();
+        if self.text[self.pos..].starts_with(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) { Ok(()) } else { self.err(format!("expected '{}'", c)) }
+    }
+
+    /// Enters an array or object; errors abort the parse, so only the
+    /// successful paths step back out.
+    fn nest(&mut self) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            return self.err(format!("nested deeper than {} levels", MAX_DEPTH));
+        }
+        self.depth += 1;
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_space();
+        let rest = &self.text[self.pos..];
+        for (word, value) in [("null", Json::Null), ("true", Json::Bool), ("false", Json::Bool)] {
+            if rest.starts_with(word) {
+                self.pos += word.len();
+                return Ok(value);
+            }
+        }
+        match rest.chars().next() {
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => {
+                self.nest()?;
+                let mut items = Vec::new();
+                if !self.eat(']') {
+                    loop {
+                        items.push(self.value()?);
+                        if self.eat(']') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Array(items))
+            }
+            Some('{') => {
+                self.nest()?;
+                let mut members = Vec::new();
+                if !self.eat('}') {
+                    loop {
+                        self.skip_space();
+                        let key = self.string()?;
+                        self.expect(':')?;
+                        members.push((key, self.value()?));
+                        if self.eat('}') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                self.depth -= 1;
+                Ok(Json::Object(members))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let end = rest.find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c))).unwrap_or(rest.len());
+                match rest[..end].parse() {
+                    Ok(v) => {
+                        self.pos += end;
+                        Ok(Json::Number(v))
+                    }
+                    Err(_) => self.err(format!("invalid number '{}'", &rest[..end])),
+                }
+            }
+            _ => self.err("expected a JSON value"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        if !self.text[self.pos..].starts_with('"') {
+            return self.err("expected a string");
+        }
+        self.pos += 1;
+        let mut out = String::new();
+        let mut chars = self.text[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, e)| e) {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex: String = chars.by_ref().take(4).map(|(_, h)| h).collect();
+                            // surrogate pairs are not combined; they become U+FFFD
+                            u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32).unwrap_or('\u{fffd}')
+                        }
+                        Some(e) => e,
+                        None => break,
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+        self.err("unterminated string")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipolygon_with_hole_and_collection() {
+        let json = r#"{"type": "GeometryCollection", "geometries": [
+            {"type": "Point", "coordinates": [1.5, -2, 30]},
+            {"type": "MultiPolygon", "coordinates": [
+                [[[0,0],[4,0],[4,4],[0,4],[0,0]], [[1,1],[1,3],[3,3],[3,1],[1,1]]],
+                [[[10,0],[11,0],[10,1],[10,0]]]
+            ]}
+        ]}"#;
+        let geoms = read_geojson::<f64, _>(json.as_bytes()).unwrap();
+        assert_eq!(geoms[0], Geometry::Point(Point2::new(1.5, -2.0)));
+        let polys = geoms[1].polygons();
+        assert_eq!(polys[0].holes[0].len(), 4);
+        assert_eq!(polys[0].area() + polys[1].area(), 12.5);
+        assert!(!polys[0].contains(Point2::new(2.0, 2.0)));
+
+        let mut out = Vec::new();
+        write_geojson(&mut out, &geoms).unwrap();
+        assert_eq!(read_geojson::<f64, _>(&out[..]).unwrap(), geoms);
+    }
+
+    #[test]
+    fn syntax_errors_report_line() {
+        let json = "{\"type\": \"Point\",\n \"coordinates\": [1, 2,]}";
+        match read_geojson::<f64, _>(json.as_bytes()) {
+            Err(IoError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        let deep = "[".repeat(1_000_000);
+        assert!(matches!(read_geojson::<f64, _>(deep.as_bytes()), Err(IoError::Parse { line: 1, .. })));
+    }
+}
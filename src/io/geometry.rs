@@ -0,0 +1,142 @@
+//! Simple-feature geometries exchanged through GeoJSON and WKT.
+//!
+//! Coordinates become [`Point2`]s (any `z` or `m` value is dropped). Polygon
+//! rings are stored open, without the repeated closing point the formats
+//! require, so they can be passed straight to `polygon_area`,
+//! `point_in_polygon` and `point_to_polygon_distance`; the writers close
+//! them again. Rings with fewer than three points, which the parsers accept
+//! (`POLYGON EMPTY`, `[[0,0],[1,1],[0,0]]`), are degenerate: they enclose
+//! no area, and degenerate holes are ignored.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::io::Polygon;
+//!
+//! let square = |x0: f64, y0: f64, s: f64| {
+//!     vec![Point2::new(x0, y0), Point2::new(x0 + s, y0), Point2::new(x0 + s, y0 + s), Point2::new(x0, y0 + s)]
+//! };
+//! let basin = Polygon { exterior: square(0.0, 0.0, 10.0), holes: vec![square(4.0, 4.0, 2.0)] };
+//! assert_eq!(basin.area(), 96.0);
+//! assert!(basin.contains(Point2::new(1.0, 1.0)));
+//! assert!(!basin.contains(Point2::new(5.0, 5.0)));
+//! assert_eq!(basin.distance(Point2::new(5.0, 5.0)), 1.0);
+//! ```
+
+use std::vec::Vec;
+
+use crate::{Float, Point2, polygon_area};
+use crate::queries::{point_in_polygon, point_to_polygon_distance, point_to_segment_distance};
+
+/// A polygon with optional holes, rings open.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon<T: Float> {
+    /// Outer ring
+    pub exterior: Vec<Point2<T>>,
+    /// Inner rings
+    pub holes: Vec<Vec<Point2<T>>>,
+}
+
+impl<T: Float> Polygon<T> {
+    /// Area of the exterior minus the holes; zero for a degenerate exterior.
+    pub fn area(&self) -> T {
+        if self.exterior.len() < 3 {
+            return T::zero();
+        }
+        self.holes().fold(polygon_area(&self.exterior), |a, h| a - polygon_area(h))
+    }
+
+    /// Whether `p` lies inside the exterior and outside every hole.
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        self.exterior.len() >= 3 && point_in_polygon(p, &self.exterior) && !self.holes().any(|h| point_in_polygon(p, h))
+    }
+
+    /// Distance from `p` to the polygon, zero inside. A degenerate exterior
+    /// counts as its points and segments; an empty one is infinitely far.
+    pub fn distance(&self, p: Point2<T>) -> T {
+        if self.exterior.len() < 3 {
+            return ring_distance(p, &self.exterior);
+        }
+        match self.holes().find(|h| point_in_polygon(p, h)) {
+            // inside a hole: distance to its boundary
+            Some(h) => ring_distance(p, h),
+            None => point_to_polygon_distance(p, &self.exterior),
+        }
+    }
+
+    /// Holes with at least three points.
+    fn holes(&self) -> impl Iterator<Item = &Vec<Point2<T>>> {
+        self.holes.iter().filter(|h| h.len() >= 3)
+    }
+}
+
+/// Distance from `p` to the edges of a closed ring.
+fn ring_distance<T: Float>(p: Point2<T>, ring: &[Point2<T>]) -> T {
+    let n = ring.len();
+    (0..n).fold(T::infinity(), |d, i| d.min(point_to_segment_distance(p, ring[i], ring[(i + 1) % n])))
+}
+
+/// A GeoJSON/WKT geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry<T: Float> {
+    /// Single position
+    Point(Point2<T>),
+    /// Polyline
+    LineString(Vec<Point2<T>>),
+    /// Polygon with holes
+    Polygon(Polygon<T>),
+    /// Several positions
+    MultiPoint(Vec<Point2<T>>),
+    /// Several polylines
+    MultiLineString(Vec<Vec<Point2<T>>>),
+    /// Several polygons
+    MultiPolygon(Vec<Polygon<T>>),
+}
+
+impl<T: Float> Geometry<T> {
+    /// The polygons of a `Polygon` or `MultiPolygon`, none otherwise.
+    pub fn polygons(&self) -> &[Polygon<T>] {
+        match self {
+            Geometry::Polygon(p) => core::slice::from_ref(p),
+            Geometry::MultiPolygon(p) => p,
+            _ => &[],
+        }
+    }
+}
+
+/// Drop the closing point of a ring, if present.
+pub(crate) fn open_ring<T: Float>(mut ring: Vec<Point2<T>>) -> Vec<Point2<T>> {
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::geojson::read_geojson;
+    use crate::io::wkt::parse_wkt;
+
+    #[test]
+    fn degenerate_rings_have_no_area() {
+        let empty = parse_wkt::<f64>("POLYGON EMPTY").unwrap();
+        let p = &empty.polygons()[0];
+        assert_eq!(p.area(), 0.0);
+        assert!(!p.contains(Point2::new(0.0, 0.0)));
+        assert_eq!(p.distance(Point2::new(0.0, 0.0)), f64::INFINITY);
+
+        let json = r#"{"type": "GeometryCollection", "geometries": [{"type": "Polygon", "coordinates": []},
+            {"type": "Polygon", "coordinates": [[[0,0],[1,1],[0,0]]]},
+            {"type": "Polygon", "coordinates": [[[0,0],[4,0],[4,4],[0,4],[0,0]], [[1,1],[2,2],[1,1]]]}]}"#;
+        let geometries = read_geojson::<f64, _>(json.as_bytes()).unwrap();
+        let [empty, sliver, square] = [0, 1, 2].map(|i| &geometries[i].polygons()[0]);
+        assert_eq!(empty.area(), 0.0);
+        assert_eq!(sliver.exterior.len(), 2);
+        assert_eq!(sliver.area(), 0.0);
+        assert_eq!(sliver.distance(Point2::new(1.0, 0.0)), 0.5_f64.sqrt());
+        assert_eq!(square.area(), 16.0);
+        assert!(square.contains(Point2::new(1.5, 1.5)));
+    }
+}
@@ -0,0 +1,427 @@
+//! GeoTIFF reader for single-band elevation rasters.
+//!
+//! Reads the first image of a classic (not Big) TIFF, little- or big-endian,
+//! stored in strips or tiles, uncompressed or deflate-compressed, with the
+//! horizontal-differencing predictor for integer samples. Samples may be 8,
+//! 16 or 32-bit integers (signed or unsigned) or 32/64-bit floats, one per
+//! pixel. The georeferencing comes from `ModelPixelScale` and
+//! `ModelTiepoint`, or from a `ModelTransformation` without rotation;
+//! `PixelIsPoint` rasters are shifted by half a pixel so that values sit at
+//! cell centres as [`Raster`] expects. `GDAL_NODATA` becomes the nodata value.
+//! Coordinate reference systems are not interpreted.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use mesh_geometry::io::geotiff::read_geotiff;
+//! use mesh_geometry::io::sms2dm::read_2dm;
+//!
+//! let dem = read_geotiff::<f64, _>(File::open("data/bathymetry.tif").unwrap()).unwrap();
+//! let estuary = read_2dm::<f64, _>(File::open("data/estuary.2dm").unwrap()).unwrap();
+//! let depths = dem.sample_nodes(&estuary.mesh);
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::Float;
+use crate::io::inflate::zlib_decompress;
+use crate::io::{IoError, Raster, Result};
+
+fn parse_err<V>(msg: impl Into<String>) -> Result<V> {
+    Err(IoError::Parse { line: 0, msg: msg.into() })
+}
+
+fn unsupported<V>(what: impl Into<String>) -> Result<V> {
+    Err(IoError::Unsupported(what.into()))
+}
+
+/// An IFD entry: field type, value count and the offset of its values.
+#[derive(Clone, Copy)]
+struct Entry {
+    typ: u16,
+    count: usize,
+    at: usize,
+}
+
+struct Tiff {
+    data: Vec<u8>,
+    le: bool,
+    tags: BTreeMap<u16, Entry>,
+}
+
+impl Tiff {
+    fn bytes(&self, at: usize, len: usize) -> Result<&[u8]> {
+        match at.checked_add(len).and_then(|end| self.data.get(at..end)) {
+            Some(b) => Ok(b),
+            None => parse_err(format!("TIFF data at offset {} runs past the end of the file", at)),
+        }
+    }
+
+    /// Unsigned integer of `size` bytes in the file's byte order.
+    fn uint(&self, b: &[u8]) -> u64 {
+        let fold = |v: u64, &x: &u8| v << 8 | u64::from(x);
+        if self.le { b.iter().rev().fold(0, fold) } else { b.iter().fold(0, fold) }
+    }
+
+    fn read_uint(&self, at: usize, size: usize) -> Result<u64> {
+        Ok(self.uint(self.bytes(at, size)?))
+    }
+
+    fn parse(data: Vec<u8>) -> Result<Self> {
+        let le = match data.get(..2) {
+            Some(b"II") => true,
+            Some(b"MM") => false,
+            _ => return parse_err("not a TIFF file"),
+        };
+        let mut tiff = Tiff { data, le, tags: BTreeMap::new() };
+        match tiff.read_uint(2, 2)? {
+            42 => {}
+            43 => return unsupported("BigTIFF"),
+            m => return parse_err(format!("bad TIFF magic number {}", m)),
+        }
+        let ifd = tiff.read_uint(4, 4)? as usize;
+        let n = tiff.read_uint(ifd, 2)? as usize;
+        for k in 0..n {
+            let e = ifd + 2 + 12 * k;
+            let tag = tiff.read_uint(e, 2)? as u16;
+            let typ = tiff.read_uint(e + 2, 2)? as u16;
+            let count = tiff.read_uint(e + 4, 4)? as usize;
+            let size = match typ {
+                1 | 2 | 6 | 7 => 1,
+                3 | 8 => 2,
+                4 | 9 | 11 => 4,
+                5 | 10 | 12 => 8,
+                // unknown types are skipped, as the specification asks
+                _ => continue,
+            };
+            let at = if size * count <= 4 { e + 8 } else { tiff.read_uint(e + 8, 4)? as usize };
+            tiff.tags.insert(tag, Entry { typ, count, at });
+        }
+        Ok(tiff)
+    }
+
+    /// Numeric values of a tag, `None` if it is absent.
+    fn values(&self, tag: u16) -> Result<Option<Vec<f64>>> {
+        let Some(&Entry { typ, count, at }) = self.tags.get(&tag) else { return Ok(None) };
+        let mut out = Vec::with_capacity(count);
+        for k in 0..count {
+            let v = match typ {
+                1 | 7 => self.read_uint(at + k, 1)? as f64,
+                6 => self.read_uint(at + k, 1)? as u8 as i8 as f64,
+                3 => self.read_uint(at + 2 * k, 2)? as f64,
+                8 => self.read_uint(at + 2 * k, 2)? as u16 as i16 as f64,
+                4 => self.read_uint(at + 4 * k, 4)? as f64,
+                9 => self.read_uint(at + 4 * k, 4)? as u32 as i32 as f64,
+                11 => f32::from_bits(self.read_uint(at + 4 * k, 4)? as u32) as f64,
+                12 => f64::from_bits(self.read_uint(at + 8 * k, 8)?),
+                5 | 10 => {
+                    let (num, den) = (self.read_uint(at + 8 * k, 4)?, self.read_uint(at + 8 * k + 4, 4)?);
+                    if typ == 5 {
+                        num as f64 / den as f64
+                    } else {
+                        num as u32 as i32 as f64 / den as u32 as i32 as f64
+                    }
+                }
+                _ => return parse_err(format!("TIFF tag {} is not numeric", tag)),
+            };
+            out.push(v);
+        }
+        Ok(Some(out))
+    }
+
+    fn value(&self, tag: u16, default: Option<usize>) -> Result<usize> {
+        match (self.values(tag)?.and_then(|v| v.first().copied()), default) {
+            (Some(v), _) => Ok(v as usize),
+            (None, Some(d)) => Ok(d),
+            (None, None) => parse_err(format!("missing TIFF tag {}", tag)),
+        }
+    }
+
+    fn text(&self, tag: u16) -> Result<Option<String>> {
+        let Some(&Entry { count, at, .. }) = self.tags.get(&tag) else { return Ok(None) };
+        let b = self.bytes(at, count)?;
+        Ok(Some(String::from_utf8_lossy(b).trim_matches(|c: char| c == '\0' || c.is_whitespace()).into()))
+    }
+}
+
+/// Read the first image of a GeoTIFF.
+pub fn read_geotiff<T: Float, R: Read>(mut reader: R) -> Result<Raster<T>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let tiff = Tiff::parse(data)?;
+
+    let width = tiff.value(256, None)?;
+    let height = tiff.value(257, None)?;
+    let bits = tiff.value(258, Some(1))?;
+    let compression = tiff.value(259, Some(1))?;
+    let spp = tiff.value(277, Some(1))?;
+    let predictor = tiff.value(317, Some(1))?;
+    let format = tiff.value(339, Some(1))?;
+    if spp != 1 {
+        return unsupported(format!("{} samples per pixel", spp));
+    }
+    let size = bits / 8;
+    match (format, bits) {
+        (1 | 2, 8 | 16 | 32) | (3, 32 | 64) => {}
+        _ => return unsupported(format!("{}-bit samples of format {}", bits, format)),
+    }
+    match (predictor, format) {
+        (1, _) | (2, 1 | 2) => {}
+        _ => return unsupported(format!("predictor {} for sample format {}", predictor, format)),
+    }
+    if !matches!(compression, 1 | 8 | 32946) {
+        return unsupported(format!("TIFF compression {}", compression));
+    }
+
+    // blocks are strips (full width) or tiles, laid out left to right, top to bottom
+    let (bw, bh, offsets, counts) = if tiff.tags.contains_key(&322) {
+        (tiff.value(322, None)?, tiff.value(323, None)?, tiff.values(324)?, tiff.values(325)?)
+    } else {
+        (width, tiff.value(278, Some(height))?.min(height), tiff.values(273)?, tiff.values(279)?)
+    };
+    let (Some(offsets), Some(counts)) = (offsets, counts) else {
+        return parse_err("missing TIFF strip or tile offsets");
+    };
+    if bw == 0 || bh == 0 {
+        return parse_err("zero TIFF block size");
+    }
+    let across = width.div_ceil(bw);
+    let Some(blocks) = across.checked_mul(height.div_ceil(bh)) else {
+        return parse_err(format!("{} x {} TIFF is too large", width, height));
+    };
+    if offsets.len() < blocks || counts.len() < offsets.len() {
+        return parse_err("too few TIFF strips or tiles");
+    }
+    // each stored byte is at most one sample byte, or 1032 once deflated
+    let stored = if compression == 1 { tiff.data.len() } else { tiff.data.len().saturating_mul(1032) };
+    let pixels = width.checked_mul(height).filter(|&n| n.checked_mul(size).is_some_and(|b| b <= stored));
+    let Some(pixels) = pixels else {
+        return parse_err(format!("{} x {} TIFF is larger than its data", width, height));
+    };
+
+    let mut values = vec![0.0; pixels];
+    let mask = if bits == 64 { u64::MAX } else { (1u64 << bits) - 1 };
+    for (b, (&off, &len)) in offsets.iter().zip(&counts).enumerate().take(blocks) {
+        let raw = tiff.bytes(off as usize, len as usize)?;
+        let block = if compression == 1 { raw.to_vec() } else { zlib_decompress(raw)? };
+        let (x0, y0) = ((b % across) * bw, (b / across) * bh);
+        let rows = bh.min(height - y0);
+        let expected = rows.checked_mul(bw).and_then(|n| n.checked_mul(size));
+        if expected.is_none_or(|n| block.len() < n) {
+            return parse_err(format!("TIFF block {} holds {} bytes, expected {} x {}", b, block.len(), rows, bw));
+        }
+        for r in 0..rows {
+            let mut prev = 0u64;
+            for c in 0..bw {
+                let at = (r * bw + c) * size;
+                let mut v = tiff.uint(&block[at..at + size]);
+                if predictor == 2 {
+                    v = v.wrapping_add(prev) & mask;
+                    prev = v;
+                }
+                if x0 + c < width {
+                    values[(y0 + r) * width + x0 + c] = match format {
+                        1 => v as f64,
+                        2 => ((v << (64 - bits)) as i64 >> (64 - bits)) as f64,
+                        _ if bits == 32 => f32::from_bits(v as u32) as f64,
+                        _ => f64::from_bits(v),
+                    };
+                }
+            }
+        }
+    }
+
+    let (x_min, y_max, dx, dy) = match (tiff.values(33550)?, tiff.values(33922)?, tiff.values(34264)?) {
+        (Some(scale), Some(tie), _) if scale.len() >= 2 && tie.len() >= 6 => {
+            (tie[3] - tie[0] * scale[0], tie[4] + tie[1] * scale[1], scale[0], scale[1])
+        }
+        (_, _, Some(m)) if m.len() >= 16 => {
+            if m[1] != 0.0 || m[4] != 0.0 {
+                return unsupported("rotated GeoTIFF transformation");
+            }
+            (m[3], m[7], m[0], -m[5])
+        }
+        _ => return parse_err("GeoTIFF has no pixel scale and tiepoint or transformation"),
+    };
+    if dx <= 0.0 || dy <= 0.0 {
+        return unsupported("GeoTIFF that is not north-up");
+    }
+    // GTRasterTypeGeoKey (1025) = RasterPixelIsPoint: the tiepoint is a pixel centre
+    let keys = tiff.values(34735)?.unwrap_or_default();
+    let pixel_is_point = keys.chunks_exact(4).skip(1).any(|k| k[0] == 1025.0 && k[1] == 0.0 && k[3] == 2.0);
+    let (x_min, y_max) = if pixel_is_point { (x_min - dx / 2.0, y_max + dy / 2.0) } else { (x_min, y_max) };
+    let nodata = match tiff.text(42113)? {
+        Some(s) => match s.parse::<f64>() {
+            Ok(v) => Some(v),
+            Err(_) => return parse_err(format!("invalid GDAL_NODATA '{}'", s)),
+        },
+        None => None,
+    };
+
+    let t = |v: f64| T::from(v).unwrap();
+    Ok(Raster {
+        ncols: width,
+        nrows: height,
+        x_min: t(x_min),
+        y_max: t(y_max),
+        dx: t(dx),
+        dy: t(dy),
+        nodata: nodata.map(t),
+        values: values.into_iter().map(t).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assemble a TIFF: header, then the pixel blocks, then the IFD and its
+    /// out-of-line values. Entries are `(tag, type, values)`; the block
+    /// offsets and byte counts are added under the two `block_tags`.
+    fn tiff(le: bool, mut entries: Vec<(u16, u16, Vec<f64>)>, block_tags: [u16; 2], blocks: &[Vec<u8>]) -> Vec<u8> {
+        let put = |out: &mut Vec<u8>, v: u64, size: usize| {
+            let b = v.to_be_bytes();
+            if le { out.extend(b[8 - size..].iter().rev()) } else { out.extend(&b[8 - size..]) }
+        };
+        let mut out = if le { b"II".to_vec() } else { b"MM".to_vec() };
+        put(&mut out, 42, 2);
+        put(&mut out, 0, 4);
+        let mut offsets = Vec::new();
+        for b in blocks {
+            offsets.push(out.len() as f64);
+            out.extend(b);
+        }
+        entries.push((block_tags[0], 4, offsets));
+        entries.push((block_tags[1], 4, blocks.iter().map(|b| b.len() as f64).collect()));
+        entries.sort_by_key(|e| e.0);
+
+        let ifd = out.len();
+        let ifd_bytes = if le { (ifd as u32).to_le_bytes() } else { (ifd as u32).to_be_bytes() };
+        out[4..8].copy_from_slice(&ifd_bytes);
+        put(&mut out, entries.len() as u64, 2);
+        let mut extra = Vec::new();
+        let extra_at = ifd + 2 + 12 * entries.len() + 4;
+        for (tag, typ, vals) in &entries {
+            let size = match typ {
+                1 | 2 => 1,
+                3 => 2,
+                4 => 4,
+                _ => 8,
+            };
+            let mut payload = Vec::new();
+            for &v in vals {
+                put(&mut payload, if *typ == 12 { v.to_bits() } else { v as u64 }, size);
+            }
+            put(&mut out, u64::from(*tag), 2);
+            put(&mut out, u64::from(*typ), 2);
+            put(&mut out, vals.len() as u64, 4);
+            if payload.len() <= 4 {
+                payload.resize(4, 0);
+                out.extend(&payload);
+            } else {
+                put(&mut out, (extra_at + extra.len()) as u64, 4);
+                extra.extend(payload);
+            }
+        }
+        put(&mut out, 0, 4);
+        out.extend(extra);
+        out
+    }
+
+    /// zlib stream made of one stored block.
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x78, 0x01, 0x01];
+        out.extend((data.len() as u16).to_le_bytes());
+        out.extend((!(data.len() as u16)).to_le_bytes());
+        out.extend(data);
+        let (mut a, mut b) = (1u32, 0u32);
+        for &x in data {
+            a = (a + u32::from(x)) % 65521;
+            b = (b + a) % 65521;
+        }
+        out.extend((b << 16 | a).to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn big_endian_strips_with_predictor_and_pixel_is_point() {
+        // 3×2 signed 16-bit, one row per strip, horizontally differenced
+        let rows: [[i16; 3]; 2] = [[-5, -7, -6], [-9999, 12, 10]];
+        let strips: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|r| {
+                let diffs = [r[0], r[1].wrapping_sub(r[0]), r[2].wrapping_sub(r[1])];
+                diffs.iter().flat_map(|d| d.to_be_bytes()).collect()
+            })
+            .collect();
+        let nodata: Vec<f64> = b"-9999\0".iter().map(|&c| f64::from(c)).collect();
+        let entries = vec![
+            (256, 3, vec![3.0]),
+            (257, 3, vec![2.0]),
+            (258, 3, vec![16.0]),
+            (278, 3, vec![1.0]),
+            (317, 3, vec![2.0]),
+            (339, 3, vec![2.0]),
+            (33550, 12, vec![30.0, 30.0, 0.0]),
+            (33922, 12, vec![0.0, 0.0, 0.0, 1000.0, 2000.0, 0.0]),
+            (34735, 3, vec![1.0, 1.0, 0.0, 1.0, 1025.0, 0.0, 1.0, 2.0]),
+            (42113, 2, nodata),
+        ];
+        let r = read_geotiff::<f64, _>(&tiff(false, entries, [273, 279], &strips)[..]).unwrap();
+        assert_eq!(r.values, vec![-5.0, -7.0, -6.0, -9999.0, 12.0, 10.0]);
+        assert_eq!((r.x_min, r.y_max, r.dx, r.dy), (985.0, 2015.0, 30.0, 30.0));
+        assert_eq!(r.get(0, 1), None);
+        assert_eq!(r.get(1, 1), Some(12.0));
+    }
+
+    #[test]
+    fn little_endian_deflate_tiles_and_bigtiff() {
+        // 20×3 floats in 16×16 tiles: two tiles across, padded
+        let value = |x: usize, y: usize| (x as f32) * 0.5 - y as f32;
+        let tiles: Vec<Vec<u8>> = (0..2)
+            .map(|tx| {
+                let px = (0..16 * 16).flat_map(|k| value(tx * 16 + k % 16, k / 16).to_le_bytes());
+                zlib_stored(&px.collect::<Vec<u8>>())
+            })
+            .collect();
+        let mut transform = vec![0.0; 16];
+        transform[0] = 2.0;
+        transform[3] = -100.0;
+        transform[5] = -4.0;
+        transform[7] = 50.0;
+        transform[15] = 1.0;
+        let entries = vec![
+            (256, 3, vec![20.0]),
+            (257, 3, vec![3.0]),
+            (258, 3, vec![32.0]),
+            (259, 3, vec![8.0]),
+            (322, 3, vec![16.0]),
+            (323, 3, vec![16.0]),
+            (339, 3, vec![3.0]),
+            (34264, 12, transform),
+        ];
+        let r = read_geotiff::<f32, _>(&tiff(true, entries, [324, 325], &tiles)[..]).unwrap();
+        assert_eq!((r.ncols, r.nrows, r.x_min, r.y_max, r.dx, r.dy), (20, 3, -100.0, 50.0, 2.0, 4.0));
+        assert_eq!(r.get(17, 2), Some(value(17, 2)));
+        assert_eq!(r.values.len(), 60);
+
+        let big = b"II\x2b\x00\x08\x00\x00\x00";
+        assert!(matches!(read_geotiff::<f64, _>(&big[..]), Err(IoError::Unsupported(_))));
+    }
+
+    #[test]
+    fn oversized_dimensions_are_errors() {
+        let sized = |width: f64, height: f64, tile: f64| {
+            let mut entries = vec![(256, 12, vec![width]), (257, 12, vec![height]), (258, 3, vec![8.0])];
+            entries.extend([(322, 3, vec![tile]), (323, 3, vec![1.0])]);
+            read_geotiff::<f64, _>(&tiff(true, entries, [324, 325], &[vec![0; 8]])[..])
+        };
+        assert!(matches!(sized(1e30, 2.0, 1.0), Err(IoError::Parse { .. })));
+        assert!(matches!(sized(1e9, 1e9, 256.0), Err(IoError::Parse { .. })));
+        assert!(matches!(sized(1.0, 1.0, 1e30), Err(IoError::Parse { .. })));
+    }
+}
@@ -0,0 +1,663 @@
+//! Gmsh `.msh` reader for format versions 2.2 and 4.1, ASCII and binary.
+//!
+//! [`read_msh`] returns a [`GmshMesh`]: node coordinates, every element with
+//! its Gmsh type code and node indices (0-based positions in `nodes`, not
+//! Gmsh node tags), the physical groups each element belongs to, and the
+//! `$PhysicalNames` table. [`GmshMesh::to_mesh`] turns the elements of one
+//! dimension into a [`Mesh3`], with wedge nodes reordered to the crate's
+//! VTK convention and high-order elements reduced to their corner nodes.
+//!
+//! Binary files must be little-endian with 8-byte `size_t`, as Gmsh writes
+//! them on all common platforms.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::gmsh::read_msh;
+//!
+//! let msh = "\
+//! $MeshFormat
+//! 2.2 0 8
+//! $EndMeshFormat
+//! $PhysicalNames
+//! 2
+//! 1 10 \"shore\"
+//! 2 20 \"water\"
+//! $EndPhysicalNames
+//! $Nodes
+//! 3
+//! 1 0 0 0
+//! 2 1 0 0
+//! 3 0 1 0
+//! $EndNodes
+//! $Elements
+//! 2
+//! 1 1 2 10 1 1 2
+//! 2 2 2 20 1 1 2 3
+//! $EndElements
+//! ";
+//! let gmsh = read_msh::<f64, _>(msh.as_bytes()).unwrap();
+//! let (mesh, elements) = gmsh.to_mesh(2);
+//! assert_eq!(mesh.num_cells(), 1);
+//! assert_eq!(gmsh.elements[elements[0]].physical, vec![20]);
+//! assert_eq!(gmsh.physical_name(1, 10), Some("shore"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::str::FromStr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point3};
+use crate::io::{IoError, Result};
+use crate::mesh::{Cell, Mesh3};
+
+/// One Gmsh element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GmshElement {
+    /// Gmsh element tag
+    pub tag: usize,
+    /// Gmsh element type code (2 = 3-node triangle, 4 = 4-node tetrahedron, ...)
+    pub element_type: u32,
+    /// Node indices into [`GmshMesh::nodes`], in Gmsh order
+    pub nodes: Vec<usize>,
+    /// Elementary entity tag
+    pub entity: i32,
+    /// Physical group tags the element belongs to
+    pub physical: Vec<i32>,
+}
+
+/// An entry of the `$PhysicalNames` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalName {
+    /// Dimension of the group
+    pub dim: usize,
+    /// Physical tag
+    pub tag: i32,
+    /// Group name
+    pub name: String,
+}
+
+/// Contents of a `.msh` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GmshMesh<T: Float> {
+    /// Node coordinates
+    pub nodes: Vec<Point3<T>>,
+    /// Gmsh tag of each node
+    pub node_tags: Vec<usize>,
+    /// All elements, in file order
+    pub elements: Vec<GmshElement>,
+    /// Named physical groups
+    pub physical_names: Vec<PhysicalName>,
+}
+
+/// Node count of a Gmsh element type.
+fn type_nodes(t: u32) -> Option<usize> {
+    Some(match t {
+        15 => 1,
+        1 => 2,
+        2 | 8 => 3,
+        3 | 4 => 4,
+        7 => 5,
+        6 | 9 => 6,
+        5 | 16 => 8,
+        10 | 20 => 9,
+        11 | 21 => 10,
+        19 => 13,
+        14 => 14,
+        18 => 15,
+        13 => 18,
+        17 => 20,
+        12 => 27,
+        _ => return None,
+    })
+}
+
+impl GmshElement {
+    /// Topological dimension, if the element type is known.
+    pub fn dim(&self) -> Option<usize> {
+        match self.element_type {
+            15 => Some(0),
+            1 | 8 => Some(1),
+            2 | 3 | 9 | 10 | 16 | 20 | 21 => Some(2),
+            4 | 5 | 6 | 7 | 11 | 12 | 13 | 14 | 17 | 18 | 19 => Some(3),
+            _ => None,
+        }
+    }
+
+    /// The element as a mesh cell (corner nodes only), for 2D and 3D types.
+    pub fn to_cell(&self) -> Option<Cell> {
+        let n = &self.nodes;
+        Some(match self.element_type {
+            2 | 9 | 20 | 21 => Cell::Triangle([n[0], n[1], n[2]]),
+            3 | 10 | 16 => Cell::Quad([n[0], n[1], n[2], n[3]]),
+            4 | 11 => Cell::Tetrahedron([n[0], n[1], n[2], n[3]]),
+            5 | 12 | 17 => Cell::Hexahedron([n[0], n[1], n[2], n[3], n[4], n[5], n[6], n[7]]),
+            // Gmsh's bottom triangle faces into the prism; VTK's faces out
+            6 | 13 | 18 => Cell::Wedge([n[0], n[2], n[1], n[3], n[5], n[4]]),
+            7 | 14 | 19 => Cell::Pyramid([n[0], n[1], n[2], n[3], n[4]]),
+            _ => return None,
+        })
+    }
+}
+
+impl<T: Float> GmshMesh<T> {
+    /// Mesh of all elements of dimension `dim` (2 or 3), sharing every node,
+    /// plus the index in [`elements`](Self::elements) of each cell.
+    pub fn to_mesh(&self, dim: usize) -> (Mesh3<T>, Vec<usize>) {
+        let mut mesh = Mesh3::from_parts(self.nodes.clone(), Vec::new());
+        let mut source = Vec::new();
+        for (i, e) in self.elements.iter().enumerate().filter(|(_, e)| e.dim() == Some(dim)) {
+            if let Some(cell) = e.to_cell() {
+                mesh.add_cell(cell);
+                source.push(i);
+            }
+        }
+        (mesh, source)
+    }
+
+    /// Name of physical group `tag` of dimension `dim`, if named.
+    pub fn physical_name(&self, dim: usize, tag: i32) -> Option<&str> {
+        self.physical_names.iter().find(|p| p.dim == dim && p.tag == tag).map(|p| p.name.as_str())
+    }
+}
+
+/// Byte cursor over the whole file, mixing text lines and binary blocks.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        Err(IoError::Parse { line: self.line, msg: msg.into() })
+    }
+
+    /// Next text line without its terminator, or `None` at end of input.
+    fn next_line(&mut self) -> Result<Option<&'a str>> {
+        if self.pos >= self.data.len() {
+            return Ok(None);
+        }
+        let rest = &self.data[self.pos..];
+        let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        self.pos += (end + 1).min(rest.len());
+        self.line += 1;
+        match std::str::from_utf8(&rest[..end]) {
+            Ok(s) => Ok(Some(s.trim_end_matches('\r'))),
+            Err(_) => self.err("invalid UTF-8 in text line"),
+        }
+    }
+
+    /// Skip lines up to and including `end`.
+    fn expect_end(&mut self, end: &str) -> Result<()> {
+        loop {
+            match self.next_line()? {
+                Some(l) if l.trim() == end => return Ok(()),
+                Some(l) if l.trim().is_empty() => {}
+                Some(l) => return self.err(format!("expected {}, found '{}'", end, l)),
+                None => return self.err(format!("missing {}", end)),
+            }
+        }
+    }
+
+    /// Whitespace-separated tokens of all lines before `end`.
+    fn tokens_until(&mut self, end: &str) -> Result<Tokens<'a>> {
+        let mut items = Vec::new();
+        loop {
+            let line = self.line + 1;
+            match self.next_line()? {
+                Some(l) if l.trim() == end => return Ok(Tokens { items, next: 0, line: self.line }),
+                Some(l) => items.extend(l.split_whitespace().map(|t| (line, t))),
+                None => return self.err(format!("missing {}", end)),
+            }
+        }
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        match self.data.get(self.pos..self.pos + N) {
+            Some(b) => {
+                self.pos += N;
+                self.line += b.iter().filter(|&&c| c == b'\n').count();
+                Ok(b.try_into().unwrap())
+            }
+            None => self.err("unexpected end of binary data"),
+        }
+    }
+
+    fn int(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.bytes()?))
+    }
+
+    fn size(&mut self) -> Result<usize> {
+        Ok(u64::from_le_bytes(self.bytes()?) as usize)
+    }
+
+    fn real(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.bytes()?))
+    }
+}
+
+/// Token stream of an ASCII section, remembering source lines.
+struct Tokens<'a> {
+    items: Vec<(usize, &'a str)>,
+    next: usize,
+    /// Line of the section end, for errors at end of input
+    line: usize,
+}
+
+impl Tokens<'_> {
+    fn get<V: FromStr>(&mut self) -> Result<V> {
+        match self.items.get(self.next) {
+            Some(&(line, tok)) => {
+                self.next += 1;
+                tok.parse().map_err(|_| IoError::Parse { line, msg: format!("invalid number '{}'", tok) })
+            }
+            None => Err(IoError::Parse { line: self.line, msg: "section ended early".into() }),
+        }
+    }
+}
+
+/// Source of section values, whichever the encoding.
+trait Values {
+    fn int(&mut self) -> Result<i32>;
+    fn size(&mut self) -> Result<usize>;
+    fn real(&mut self) -> Result<f64>;
+}
+
+impl Values for Tokens<'_> {
+    fn int(&mut self) -> Result<i32> {
+        self.get()
+    }
+    fn size(&mut self) -> Result<usize> {
+        self.get()
+    }
+    fn real(&mut self) -> Result<f64> {
+        self.get()
+    }
+}
+
+impl Values for Cursor<'_> {
+    fn int(&mut self) -> Result<i32> {
+        Cursor::int(self)
+    }
+    fn size(&mut self) -> Result<usize> {
+        Cursor::size(self)
+    }
+    fn real(&mut self) -> Result<f64> {
+        Cursor::real(self)
+    }
+}
+
+/// Everything parsed so far.
+struct Builder<T: Float> {
+    mesh: GmshMesh<T>,
+    node_index: BTreeMap<usize, usize>,
+    /// Physical tags of each `(dim, entity)` (v4 `$Entities`)
+    entities: BTreeMap<(usize, i32), Vec<i32>>,
+    /// Elements with Gmsh node tags, resolved once all nodes are read
+    raw_nodes: Vec<Vec<usize>>,
+}
+
+impl<T: Float> Builder<T> {
+    fn node(&mut self, tag: usize, x: f64, y: f64, z: f64) {
+        let p = Point3::new(T::from(x).unwrap(), T::from(y).unwrap(), T::from(z).unwrap());
+        self.node_index.insert(tag, self.mesh.nodes.len());
+        self.mesh.nodes.push(p);
+        self.mesh.node_tags.push(tag);
+    }
+
+    fn element(&mut self, tag: usize, element_type: u32, nodes: Vec<usize>, entity: i32, physical: Vec<i32>) {
+        self.mesh.elements.push(GmshElement { tag, element_type, nodes: Vec::new(), entity, physical });
+        self.raw_nodes.push(nodes);
+    }
+
+    fn finish(mut self) -> Result<GmshMesh<T>> {
+        for (e, raw) in self.mesh.elements.iter_mut().zip(self.raw_nodes) {
+            e.nodes = raw
+                .iter()
+                .map(|t| {
+                    self.node_index.get(t).copied().ok_or_else(|| IoError::Parse {
+                        line: 0,
+                        msg: format!("element {} references unknown node {}", e.tag, t),
+                    })
+                })
+                .collect::<Result<_>>()?;
+        }
+        Ok(self.mesh)
+    }
+}
+
+fn nodes_of_type(t: u32, line: usize) -> Result<usize> {
+    type_nodes(t).ok_or_else(|| IoError::Parse { line, msg: format!("unsupported element type {}", t) })
+}
+
+/// v2 `$Nodes` body.
+fn nodes_v2<T: Float>(b: &mut Builder<T>, v: &mut impl Values, n: usize) -> Result<()> {
+    for _ in 0..n {
+        let tag = v.int()? as usize;
+        let (x, y, z) = (v.real()?, v.real()?, v.real()?);
+        b.node(tag, x, y, z);
+    }
+    Ok(())
+}
+
+/// v4.1 `$Entities` body.
+fn entities_v4<T: Float>(b: &mut Builder<T>, v: &mut impl Values) -> Result<()> {
+    let counts = [v.size()?, v.size()?, v.size()?, v.size()?];
+    for (dim, &count) in counts.iter().enumerate() {
+        for _ in 0..count {
+            let tag = v.int()?;
+            for _ in 0..if dim == 0 { 3 } else { 6 } {
+                v.real()?;
+            }
+            let nphys = v.size()?;
+            let phys = (0..nphys).map(|_| v.int()).collect::<Result<Vec<_>>>()?;
+            if dim > 0 {
+                for _ in 0..v.size()? {
+                    v.int()?;
+                }
+            }
+            b.entities.insert((dim, tag), phys);
+        }
+    }
+    Ok(())
+}
+
+/// v4.1 `$Nodes` body.
+fn nodes_v4<T: Float>(b: &mut Builder<T>, v: &mut impl Values) -> Result<()> {
+    let blocks = v.size()?;
+    let _ = (v.size()?, v.size()?, v.size()?);
+    for _ in 0..blocks {
+        let dim = v.int()? as usize;
+        let _entity = v.int()?;
+        let parametric = v.int()? != 0;
+        let n = v.size()?;
+        let tags = (0..n).map(|_| v.size()).collect::<Result<Vec<_>>>()?;
+        for tag in tags {
+            let (x, y, z) = (v.real()?, v.real()?, v.real()?);
+            if parametric {
+                for _ in 0..dim {
+                    v.real()?;
+                }
+            }
+            b.node(tag, x, y, z);
+        }
+    }
+    Ok(())
+}
+
+/// v4.1 `$Elements` body.
+fn elements_v4<T: Float>(b: &mut Builder<T>, v: &mut impl Values, line: usize) -> Result<()> {
+    let blocks = v.size()?;
+    let _ = (v.size()?, v.size()?, v.size()?);
+    for _ in 0..blocks {
+        let dim = v.int()? as usize;
+        let entity = v.int()?;
+        let ty = v.int()? as u32;
+        let n = v.size()?;
+        let per = nodes_of_type(ty, line)?;
+        let physical = b.entities.get(&(dim, entity)).cloned().unwrap_or_default();
+        for _ in 0..n {
+            let tag = v.size()?;
+            let nodes = (0..per).map(|_| v.size()).collect::<Result<Vec<_>>>()?;
+            b.element(tag, ty, nodes, entity, physical.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Split v2 element tags into (physical, entity).
+fn v2_tags(tags: &[i32]) -> (Vec<i32>, i32) {
+    let physical = tags.first().copied().filter(|&p| p != 0).into_iter().collect();
+    (physical, tags.get(1).copied().unwrap_or(0))
+}
+
+/// Read a Gmsh `.msh` file (v2.2 or v4.1, ASCII or binary).
+pub fn read_msh<T: Float, R: Read>(mut reader: R) -> Result<GmshMesh<T>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let mut cur = Cursor { data: &data, pos: 0, line: 0 };
+    let mut b = Builder {
+        mesh: GmshMesh { nodes: Vec::new(), node_tags: Vec::new(), elements: Vec::new(), physical_names: Vec::new() },
+        node_index: BTreeMap::new(),
+        entities: BTreeMap::new(),
+        raw_nodes: Vec::new(),
+    };
+    let mut format: Option<(u32, bool)> = None;
+
+    while let Some(line) = cur.next_line()? {
+        let section = line.trim();
+        if section.is_empty() {
+            continue;
+        }
+        if section != "$MeshFormat" && section.starts_with('$') && format.is_none() {
+            return cur.err("file does not start with $MeshFormat");
+        }
+        match section {
+            "$MeshFormat" => {
+                let header = cur.next_line()?.unwrap_or("");
+                let mut parts = header.split_whitespace();
+                let major = match parts.next() {
+                    Some("2.2") => 2,
+                    Some("4.1") => 4,
+                    Some(v) => return Err(IoError::Unsupported(format!("msh format version {}", v))),
+                    None => return cur.err("empty $MeshFormat"),
+                };
+                let binary = parts.next() == Some("1");
+                if parts.next() != Some("8") {
+                    return Err(IoError::Unsupported("msh data size other than 8".into()));
+                }
+                if binary && cur.int()? != 1 {
+                    return Err(IoError::Unsupported("big-endian binary msh".into()));
+                }
+                format = Some((major, binary));
+                cur.expect_end("$EndMeshFormat")?;
+            }
+            "$PhysicalNames" => {
+                let n: usize = cur.next_line()?.unwrap_or("").trim().parse().or_else(|_| cur.err("bad count"))?;
+                for _ in 0..n {
+                    let l = cur.next_line()?.unwrap_or("");
+                    let mut parts = l.splitn(3, char::is_whitespace);
+                    let (dim, tag, name) = (parts.next(), parts.next(), parts.next());
+                    match (dim.and_then(|d| d.parse().ok()), tag.and_then(|t| t.parse().ok()), name) {
+                        (Some(dim), Some(tag), Some(name)) => b.mesh.physical_names.push(PhysicalName {
+                            dim,
+                            tag,
+                            name: name.trim().trim_matches('"').into(),
+                        }),
+                        _ => return cur.err(format!("bad physical name '{}'", l)),
+                    }
+                }
+                cur.expect_end("$EndPhysicalNames")?;
+            }
+            "$Entities" | "$Nodes" | "$Elements" => {
+                let (major, binary) = format.unwrap();
+                let end = format!("$End{}", &section[1..]);
+                let start = cur.line;
+                match (section, major, binary) {
+                    ("$Entities", 2, _) => return cur.err("$Entities in a v2 file"),
+                    ("$Entities", _, false) => entities_v4(&mut b, &mut cur.tokens_until(&end)?)?,
+                    ("$Entities", _, true) => {
+                        entities_v4(&mut b, &mut cur)?;
+                        cur.expect_end(&end)?;
+                    }
+                    ("$Nodes", 2, false) => {
+                        let mut t = cur.tokens_until(&end)?;
+                        let n = t.size()?;
+                        nodes_v2(&mut b, &mut t, n)?;
+                    }
+                    ("$Nodes", 2, true) => {
+                        let n = cur.next_line()?.unwrap_or("").trim().parse().or_else(|_| cur.err("bad node count"))?;
+                        nodes_v2(&mut b, &mut cur, n)?;
+                        cur.expect_end(&end)?;
+                    }
+                    ("$Nodes", _, false) => nodes_v4(&mut b, &mut cur.tokens_until(&end)?)?,
+                    ("$Nodes", _, true) => {
+                        nodes_v4(&mut b, &mut cur)?;
+                        cur.expect_end(&end)?;
+                    }
+                    ("$Elements", 2, false) => {
+                        let mut t = cur.tokens_until(&end)?;
+                        for _ in 0..t.size()? {
+                            let line = t.items.get(t.next).map_or(t.line, |i| i.0);
+                            let tag = t.size()?;
+                            let ty: u32 = t.get()?;
+                            let ntags = t.size()?;
+                            let tags = (0..ntags).map(|_| t.int()).collect::<Result<Vec<_>>>()?;
+                            let nodes = (0..nodes_of_type(ty, line)?).map(|_| t.size()).collect::<Result<_>>()?;
+                            let (physical, entity) = v2_tags(&tags);
+                            b.element(tag, ty, nodes, entity, physical);
+                        }
+                    }
+                    ("$Elements", 2, true) => {
+                        let n: usize =
+                            cur.next_line()?.unwrap_or("").trim().parse().or_else(|_| cur.err("bad element count"))?;
+                        let mut read = 0;
+                        while read < n {
+                            let ty = cur.int()? as u32;
+                            let count = cur.int()? as usize;
+                            let ntags = cur.int()? as usize;
+                            let per = nodes_of_type(ty, cur.line)?;
+                            for _ in 0..count {
+                                let tag = cur.int()? as usize;
+                                let tags = (0..ntags).map(|_| cur.int()).collect::<Result<Vec<_>>>()?;
+                                let nodes = (0..per).map(|_| cur.int().map(|t| t as usize)).collect::<Result<_>>()?;
+                                let (physical, entity) = v2_tags(&tags);
+                                b.element(tag, ty, nodes, entity, physical);
+                            }
+                            read += count;
+                        }
+                        cur.expect_end(&end)?;
+                    }
+                    ("$Elements", _, false) => elements_v4(&mut b, &mut cur.tokens_until(&end)?, start)?,
+                    _ => {
+                        elements_v4(&mut b, &mut cur, start)?;
+                        cur.expect_end(&end)?;
+                    }
+                }
+            }
+            s if s.starts_with('$') => {
+                // unknown or unused section ($Comments, $NodeData, $Periodic, ...)
+                let end = format!("$End{}", &s[1..]);
+                while cur.next_line()?.is_some_and(|l| l.trim() != end) {}
+            }
+            other => return cur.err(format!("unexpected line '{}'", other)),
+        }
+    }
+    if format.is_none() {
+        return cur.err("no $MeshFormat section");
+    }
+    b.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::tetrahedron_volume;
+
+    const V41: &str = "\
+$MeshFormat
+4.1 0 8
+$EndMeshFormat
+$PhysicalNames
+1
+3 7 \"fluid\"
+$EndPhysicalNames
+$Entities
+0 0 0 1
+1 0 0 0 1 1 1 1 7 0
+$EndEntities
+$Nodes
+1 5 1 5
+3 1 0 5
+1
+2
+3
+4
+5
+0 0 0
+1 0 0
+0 1 0
+0 0 1
+1 1 1
+$EndNodes
+$Elements
+1 2 1 2
+3 1 4 2
+1 1 2 3 4
+2 2 3 4 5
+$EndElements
+";
+
+    #[test]
+    fn ascii_v41_with_entities() {
+        let gmsh = read_msh::<f64, _>(V41.as_bytes()).unwrap();
+        assert_eq!(gmsh.nodes.len(), 5);
+        assert_eq!(gmsh.elements[1].nodes, vec![1, 2, 3, 4]);
+        assert_eq!(gmsh.elements[1].physical, vec![7]);
+        assert_eq!(gmsh.physical_name(3, 7), Some("fluid"));
+        let (mesh, _) = gmsh.to_mesh(3);
+        let vol: f64 = mesh
+            .cells
+            .iter()
+            .map(|c| {
+                let n = c.nodes();
+                tetrahedron_volume(mesh.nodes[n[0]], mesh.nodes[n[1]], mesh.nodes[n[2]], mesh.nodes[n[3]])
+            })
+            .sum();
+        assert!((vol - 1.0 / 6.0 - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn binary_v22_and_v41() {
+        let int = |v: i32| v.to_le_bytes().to_vec();
+        let size = |v: u64| v.to_le_bytes().to_vec();
+        let real = |v: f64| v.to_le_bytes().to_vec();
+        let coords = [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)];
+
+        // v2.2: one triangle in physical group 4
+        let mut v2 = b"$MeshFormat\n2.2 1 8\n".to_vec();
+        v2.extend(int(1));
+        v2.extend(b"\n$EndMeshFormat\n$Nodes\n3\n");
+        for (i, &(x, y, z)) in coords.iter().enumerate() {
+            v2.extend(int(i as i32 + 11));
+            v2.extend([real(x), real(y), real(z)].concat());
+        }
+        v2.extend(b"\n$EndNodes\n$Elements\n1\n");
+        v2.extend([int(2), int(1), int(2), int(1), int(4), int(9), int(11), int(12), int(13)].concat());
+        v2.extend(b"\n$EndElements\n");
+
+        // v4.1: the same triangle, no entities
+        let mut v4 = b"$MeshFormat\n4.1 1 8\n".to_vec();
+        v4.extend(int(1));
+        v4.extend(b"\n$EndMeshFormat\n$Nodes\n");
+        v4.extend([size(1), size(3), size(11), size(13), int(2), int(9), int(0), size(3)].concat());
+        v4.extend([size(11), size(12), size(13)].concat());
+        for &(x, y, z) in &coords {
+            v4.extend([real(x), real(y), real(z)].concat());
+        }
+        v4.extend(b"\n$EndNodes\n$Elements\n");
+        v4.extend([size(1), size(1), size(1), size(1), int(2), int(9), int(2), size(1)].concat());
+        v4.extend([size(1), size(11), size(12), size(13)].concat());
+        v4.extend(b"\n$EndElements\n");
+
+        let a = read_msh::<f64, _>(&v2[..]).unwrap();
+        let b = read_msh::<f64, _>(&v4[..]).unwrap();
+        assert_eq!(a.nodes, b.nodes);
+        assert_eq!(a.node_tags, vec![11, 12, 13]);
+        assert_eq!(a.elements[0].nodes, b.elements[0].nodes);
+        assert_eq!((a.elements[0].physical.clone(), a.elements[0].entity), (vec![4], 9));
+        assert_eq!(a.to_mesh(2).0.cells, vec![Cell::Triangle([0, 1, 2])]);
+    }
+
+    #[test]
+    fn errors_carry_line_numbers() {
+        let bad = "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n$Nodes\n1\n1 0 zero 0\n$EndNodes\n";
+        match read_msh::<f64, _>(bad.as_bytes()) {
+            Err(IoError::Parse { line, .. }) => assert_eq!(line, 6),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
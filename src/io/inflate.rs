@@ -0,0 +1,273 @@
+//! DEFLATE (RFC 1951) and zlib (RFC 1950) decompression.
+//!
+//! A small canonical-Huffman decoder in the style of zlib's `puff`, enough
+//! for deflate-compressed TIFF strips and tiles without an external crate.
+
+use std::string::String;
+use std::vec::Vec;
+
+use crate::io::{IoError, Result};
+
+/// Base lengths of length codes 257..285, and their extra bits.
+const LENGTH_BASE: [u16; 29] =
+    [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+
+/// Base distances of distance codes 0..29, and their extra bits.
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097,
+    6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+/// Order in which code-length code lengths are stored.
+const CLEN_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+fn corrupt<V>(msg: impl Into<String>) -> Result<V> {
+    Err(IoError::Parse { line: 0, msg: format!("deflate: {}", msg.into()) })
+}
+
+/// Decompress a zlib stream, checking its Adler-32 checksum.
+pub(crate) fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>> {
+    if data.len() < 6 || (u16::from(data[0]) << 8 | u16::from(data[1])) % 31 != 0 || data[0] & 0x0F != 8 {
+        return corrupt("invalid zlib header");
+    }
+    if data[1] & 0x20 != 0 {
+        return Err(IoError::Unsupported("zlib preset dictionary".into()));
+    }
+    let out = inflate(&data[2..])?;
+    let expected = u32::from_be_bytes(data[data.len() - 4..].try_into().unwrap());
+    if adler32(&out) != expected {
+        return corrupt("Adler-32 checksum mismatch");
+    }
+    Ok(out)
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &x in chunk {
+            a += u32::from(x);
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    b << 16 | a
+}
+
+/// Decompress a raw DEFLATE stream.
+pub(crate) fn inflate(data: &[u8]) -> Result<Vec<u8>> {
+    let mut s = Bits { data, pos: 0, buf: 0, count: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = s.bits(1)? == 1;
+        match s.bits(2)? {
+            0 => {
+                // stored: skip to a byte boundary, then LEN and its complement
+                s.buf = 0;
+                s.count = 0;
+                let header = s.data.get(s.pos..s.pos + 4).map(|h| h.to_vec());
+                let Some(h) = header else { return corrupt("truncated stored block") };
+                let len = usize::from(u16::from_le_bytes([h[0], h[1]]));
+                if u16::from_le_bytes([h[0], h[1]]) != !u16::from_le_bytes([h[2], h[3]]) {
+                    return corrupt("stored block length mismatch");
+                }
+                let Some(block) = s.data.get(s.pos + 4..s.pos + 4 + len) else {
+                    return corrupt("truncated stored block");
+                };
+                out.extend_from_slice(block);
+                s.pos += 4 + len;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let lit = Huffman::new(&lengths)?;
+                let dist = Huffman::new(&[5; 30])?;
+                s.codes(&mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = s.dynamic_tables()?;
+                s.codes(&mut out, &lit, &dist)?;
+            }
+            _ => return corrupt("invalid block type"),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+/// Canonical Huffman code: number of codes of each length, and the symbols
+/// ordered by code.
+struct Huffman {
+    count: [u16; 16],
+    symbol: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self> {
+        let mut count = [0u16; 16];
+        for &l in lengths {
+            count[l as usize] += 1;
+        }
+        // reject over-subscribed codes; incomplete ones are allowed
+        let mut left = 1i32;
+        for &c in &count[1..] {
+            left = 2 * left - i32::from(c);
+            if left < 0 {
+                return corrupt("over-subscribed Huffman code");
+            }
+        }
+        let mut offs = [0u16; 16];
+        for len in 1..15 {
+            offs[len + 1] = offs[len] + count[len];
+        }
+        let mut symbol = vec![0u16; lengths.len()];
+        for (sym, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbol[offs[l as usize] as usize] = sym as u16;
+                offs[l as usize] += 1;
+            }
+        }
+        Ok(Huffman { count, symbol })
+    }
+}
+
+/// LSB-first bit reader.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buf: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, n: u32) -> Result<u32> {
+        while self.count < n {
+            let Some(&b) = self.data.get(self.pos) else { return corrupt("unexpected end of data") };
+            self.pos += 1;
+            self.buf |= u32::from(b) << self.count;
+            self.count += 8;
+        }
+        let v = self.buf & ((1u32 << n) - 1);
+        self.buf >>= n;
+        self.count -= n;
+        Ok(v)
+    }
+
+    fn decode(&mut self, h: &Huffman) -> Result<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= self.bits(1)? as i32;
+            let count = i32::from(h.count[len]);
+            if code - count < first {
+                return Ok(h.symbol[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        corrupt("invalid Huffman code")
+    }
+
+    fn dynamic_tables(&mut self) -> Result<(Huffman, Huffman)> {
+        let nlen = self.bits(5)? as usize + 257;
+        let ndist = self.bits(5)? as usize + 1;
+        let ncode = self.bits(4)? as usize + 4;
+        if nlen > 286 || ndist > 30 {
+            return corrupt("too many length or distance codes");
+        }
+        let mut clen = [0u8; 19];
+        for &k in &CLEN_ORDER[..ncode] {
+            clen[k] = self.bits(3)? as u8;
+        }
+        let clen = Huffman::new(&clen)?;
+        let mut lengths = Vec::with_capacity(nlen + ndist);
+        while lengths.len() < nlen + ndist {
+            let (value, repeat) = match self.decode(&clen)? {
+                sym @ 0..=15 => (sym as u8, 1),
+                16 => match lengths.last() {
+                    Some(&prev) => (prev, 3 + self.bits(2)?),
+                    None => return corrupt("repeat with no previous length"),
+                },
+                17 => (0, 3 + self.bits(3)?),
+                _ => (0, 11 + self.bits(7)?),
+            };
+            if lengths.len() + repeat as usize > nlen + ndist {
+                return corrupt("too many code lengths");
+            }
+            lengths.extend(core::iter::repeat_n(value, repeat as usize));
+        }
+        if lengths[256] == 0 {
+            return corrupt("missing end-of-block code");
+        }
+        Ok((Huffman::new(&lengths[..nlen])?, Huffman::new(&lengths[nlen..])?))
+    }
+
+    fn codes(&mut self, out: &mut Vec<u8>, lit: &Huffman, dist: &Huffman) -> Result<()> {
+        loop {
+            match self.decode(lit)? {
+                sym @ 0..=255 => out.push(sym as u8),
+                256 => return Ok(()),
+                sym => {
+                    let k = usize::from(sym - 257);
+                    if k >= 29 {
+                        return corrupt("invalid length code");
+                    }
+                    let len = usize::from(LENGTH_BASE[k]) + self.bits(u32::from(LENGTH_EXTRA[k]))? as usize;
+                    let d = usize::from(self.decode(dist)?);
+                    if d >= 30 {
+                        return corrupt("invalid distance code");
+                    }
+                    let back = usize::from(DIST_BASE[d]) + self.bits(u32::from(DIST_EXTRA[d]))? as usize;
+                    if back > out.len() {
+                        return corrupt("distance before start of output");
+                    }
+                    // byte by byte, as the copy may overlap its own output
+                    let start = out.len() - back;
+                    for i in 0..len {
+                        out.push(out[start + i]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_and_stored_blocks() {
+        let fixed = [0x78, 0xda, 0x2b, 0xc9, 0x4c, 0x49, 0x55, 0x28, 0x41, 0x21, 0x00, 0x46, 0x27, 0x06, 0xf9];
+        assert_eq!(zlib_decompress(&fixed).unwrap(), b"tide tide tide tide");
+        // one stored block: BFINAL=1, BTYPE=00, LEN=3
+        assert_eq!(inflate(&[0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c']).unwrap(), b"abc");
+        let mut bad = fixed;
+        bad[14] ^= 1;
+        assert!(zlib_decompress(&bad).is_err());
+    }
+
+    #[test]
+    fn dynamic_block() {
+        // zlib.compress(data, 9) from Python
+        let compressed = [
+            0x78, 0xda, 0x4d, 0xd0, 0x4d, 0x0a, 0x82, 0x31, 0x0c, 0x84, 0xe1, 0xab, 0x78, 0x82, 0x8f, 0x26, 0xd3,
+            0xf4, 0xc7, 0x5e, 0x47, 0xc1, 0xa5, 0x0b, 0xef, 0x8f, 0x15, 0x6a, 0x78, 0x77, 0x93, 0x69, 0xa0, 0x0f,
+            0x29, 0xf7, 0xc7, 0xf3, 0xfd, 0x79, 0xdd, 0xca, 0x55, 0x96, 0x9d, 0xac, 0x2b, 0x96, 0x67, 0x1f, 0x4b,
+            0x27, 0xd7, 0xbd, 0x53, 0x4f, 0xb6, 0x9d, 0x23, 0xfb, 0x58, 0x2d, 0xfb, 0x58, 0xfd, 0xe4, 0xd8, 0x3b,
+            0xe3, 0x64, 0xdf, 0x79, 0x66, 0x1f, 0xcb, 0x4a, 0x3e, 0xec, 0xe1, 0xff, 0x73, 0xfb, 0x29, 0x3c, 0x19,
+            0x7b, 0x10, 0x7d, 0x15, 0x40, 0x0b, 0x08, 0xad, 0x81, 0x68, 0x1d, 0x46, 0x1b, 0x40, 0xda, 0x84, 0xd2,
+            0x0b, 0x98, 0x6e, 0x70, 0xba, 0x03, 0xea, 0x02, 0xd4, 0x2b, 0xa0, 0x1e, 0x80, 0x7a, 0x03, 0xd4, 0x3b,
+            0x2f, 0x39, 0x00, 0xf5, 0x09, 0xa8, 0x0a, 0xa0, 0x32, 0x40, 0xe5, 0x80, 0x4a, 0x80, 0xaa, 0x02, 0xaa,
+            0x00, 0x54, 0x0d, 0x50, 0x75, 0x40, 0x35, 0x00, 0xd5, 0x04, 0xf4, 0x0b, 0x17, 0x76, 0x8f, 0x97,
+        ];
+        let expected: String = (0..40).map(|i| format!("{}:depth {:.1};", i, ((i * 7) % 13) as f64 * 0.5)).collect();
+        assert_eq!(zlib_decompress(&compressed).unwrap(), expected.as_bytes());
+    }
+}
@@ -0,0 +1,204 @@
+//! Mesh file readers and writers.
+//!
+//! Every format lives in its own submodule and reports failures through
+//! [`IoError`]. Readers and writers work on any `std::io::Read`/`Write`, so
+//! files, buffers and sockets are handled alike.
+
+use std::fmt;
+use std::string::String;
+
+/// VTK legacy and XML unstructured-grid writers.
+pub mod vtk;
+
+/// Gmsh `.msh` reader.
+pub mod gmsh;
+
+/// ADCIRC `fort.14` reader and writer.
+pub mod fort14;
+
+/// SMS `.2dm` reader and writer.
+pub mod sms2dm;
+
+/// OpenFOAM `polyMesh` reader.
+pub mod openfoam;
+
+/// Triangle and polygon surfaces shared by the surface formats.
+pub mod surface;
+pub use surface::Surface;
+
+/// STL reader and writer.
+pub mod stl;
+
+/// Wavefront OBJ reader and writer.
+pub mod obj;
+
+/// PLY reader and writer.
+pub mod ply;
+
+/// NetCDF classic (CDF-1/CDF-2) reader and writer.
+pub mod netcdf;
+
+/// UGRID mesh topologies in NetCDF files.
+pub mod ugrid;
+
+/// Simple-feature geometries shared by the GIS formats.
+pub mod geometry;
+pub use geometry::{Geometry, Polygon};
+
+/// GeoJSON reader and writer.
+pub mod geojson;
+
+/// WKT parser and formatter.
+pub mod wkt;
+
+/// ESRI Shapefile reader.
+pub mod shapefile;
+
+/// Raster grids and their sampling onto meshes.
+pub mod raster;
+pub use raster::Raster;
+
+/// ESRI ASCII grid reader.
+pub mod asc;
+
+/// GeoTIFF reader.
+pub mod geotiff;
+
+mod inflate;
+
+/// How a writer stores values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// Plain text
+    #[default]
+    Ascii,
+    /// The format's binary layout: big-endian in legacy VTK, inline base64
+    /// in `.vtu`, little-endian in STL and PLY
+    Binary,
+}
+
+/// Error raised while reading or writing a mesh file.
+#[derive(Debug)]
+pub enum IoError {
+    /// Underlying I/O failure
+    Io(std::io::Error),
+    /// Malformed input, with the 1-based line (0 when not line-oriented)
+    Parse {
+        /// Line the problem was found on
+        line: usize,
+        /// What was wrong
+        msg: String,
+    },
+    /// Valid input or request that this implementation does not handle
+    Unsupported(String),
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoError::Io(e) => write!(f, "I/O error: {}", e),
+            IoError::Parse { line, msg } => write!(f, "parse error on line {}: {}", line, msg),
+            IoError::Unsupported(what) => write!(f, "unsupported: {}", what),
+        }
+    }
+}
+
+impl std::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IoError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(e: std::io::Error) -> Self {
+        IoError::Io(e)
+    }
+}
+
+/// Result type of the `io` module.
+pub type Result<T> = core::result::Result<T, IoError>;
+
+/// Line-oriented text reader that tracks line numbers for errors.
+pub(crate) struct Lines<'a> {
+    lines: core::str::Lines<'a>,
+    /// 1-based number of the last line returned
+    pub line: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        Lines { lines: text.lines(), line: 0 }
+    }
+
+    /// Next line, or `None` at end of input.
+    pub(crate) fn next_line(&mut self) -> Option<&'a str> {
+        let l = self.lines.next()?;
+        self.line += 1;
+        Some(l)
+    }
+
+    /// Next line, failing at end of input.
+    pub(crate) fn expect_line(&mut self) -> Result<&'a str> {
+        match self.next_line() {
+            Some(l) => Ok(l),
+            None => self.err("unexpected end of file"),
+        }
+    }
+
+    pub(crate) fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        Err(IoError::Parse { line: self.line, msg: msg.into() })
+    }
+
+    /// Parse a token of the current line as `what`.
+    pub(crate) fn parse<V: core::str::FromStr>(&self, tok: Option<&str>, what: &str) -> Result<V> {
+        match tok.map(str::parse) {
+            Some(Ok(v)) => Ok(v),
+            Some(Err(_)) => self.err(format!("invalid {} '{}'", what, tok.unwrap_or(""))),
+            None => self.err(format!("missing {}", what)),
+        }
+    }
+}
+
+/// Read all of `reader` as UTF-8 text.
+pub(crate) fn read_text<R: std::io::Read>(mut reader: R) -> Result<String> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text).map_err(|e| match e.kind() {
+        std::io::ErrorKind::InvalidData => IoError::Parse { line: 0, msg: "input is not valid UTF-8".into() },
+        _ => IoError::Io(e),
+    })?;
+    Ok(text)
+}
+
+/// Standard base64 (RFC 4648) with padding.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for k in 0..4 {
+            if k <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * k) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_rfc4648_vectors() {
+        let cases = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foobar", "Zm9vYmFy")];
+        for (plain, coded) in cases {
+            assert_eq!(base64_encode(plain.as_bytes()), coded);
+        }
+    }
+}
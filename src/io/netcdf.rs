@@ -0,0 +1,646 @@
+//! NetCDF classic (CDF-1) and 64-bit offset (CDF-2) files, in pure Rust.
+//!
+//! [`NcFile`] holds the dimensions, global attributes and variables of a
+//! file, with every variable's values in memory in row-major order (record
+//! variables with the record dimension first). [`NcFile::read`] accepts both
+//! versions, record variables and streamed files; [`NcFile::write`] lays out
+//! fixed-size variables first and record variables after them, as libnetcdf
+//! does. NetCDF-4/HDF5 files are not supported.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::netcdf::{NcData, NcFile, Version};
+//!
+//! let mut nc = NcFile::new(Version::Classic);
+//! let time = nc.add_unlimited_dimension("time", 2);
+//! let node = nc.add_dimension("node", 3);
+//! nc.add_variable("zeta", &[time, node], NcData::Double(vec![0.0, 0.1, 0.2, 1.0, 1.1, 1.2]))
+//!     .set_attribute("units", "m".into());
+//!
+//! let mut out = Vec::new();
+//! nc.write(&mut out).unwrap();
+//! let back = NcFile::read(&out[..]).unwrap();
+//! assert_eq!(back, nc);
+//! assert_eq!(back.variable("zeta").unwrap().attribute("units").unwrap().as_text(), Some("m"));
+//! ```
+
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::io::{IoError, Result};
+
+const NC_DIMENSION: u32 = 0x0A;
+const NC_VARIABLE: u32 = 0x0B;
+const NC_ATTRIBUTE: u32 = 0x0C;
+const STREAMING: u32 = 0xFFFF_FFFF;
+
+/// File format version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Version {
+    /// CDF-1, 32-bit offsets
+    #[default]
+    Classic,
+    /// CDF-2, 64-bit offsets
+    Offset64,
+}
+
+/// Values of an attribute or variable, by external type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NcData {
+    /// `byte`
+    Byte(Vec<i8>),
+    /// `char`
+    Char(Vec<u8>),
+    /// `short`
+    Short(Vec<i16>),
+    /// `int`
+    Int(Vec<i32>),
+    /// `float`
+    Float(Vec<f32>),
+    /// `double`
+    Double(Vec<f64>),
+}
+
+impl From<&str> for NcData {
+    fn from(text: &str) -> Self {
+        NcData::Char(text.as_bytes().to_vec())
+    }
+}
+
+impl NcData {
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        match self {
+            NcData::Byte(v) => v.len(),
+            NcData::Char(v) => v.len(),
+            NcData::Short(v) => v.len(),
+            NcData::Int(v) => v.len(),
+            NcData::Float(v) => v.len(),
+            NcData::Double(v) => v.len(),
+        }
+    }
+
+    /// `true` when there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All values as `f64` (characters as their byte values).
+    pub fn to_f64(&self) -> Vec<f64> {
+        match self {
+            NcData::Byte(v) => v.iter().map(|&x| x as f64).collect(),
+            NcData::Char(v) => v.iter().map(|&x| x as f64).collect(),
+            NcData::Short(v) => v.iter().map(|&x| x as f64).collect(),
+            NcData::Int(v) => v.iter().map(|&x| x as f64).collect(),
+            NcData::Float(v) => v.iter().map(|&x| x as f64).collect(),
+            NcData::Double(v) => v.clone(),
+        }
+    }
+
+    /// Text of `char` data, without trailing NULs.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            NcData::Char(v) => std::str::from_utf8(v).ok().map(|s| s.trim_end_matches('\0')),
+            _ => None,
+        }
+    }
+
+    fn type_code(&self) -> u32 {
+        match self {
+            NcData::Byte(_) => 1,
+            NcData::Char(_) => 2,
+            NcData::Short(_) => 3,
+            NcData::Int(_) => 4,
+            NcData::Float(_) => 5,
+            NcData::Double(_) => 6,
+        }
+    }
+
+    /// Empty data of type `code` with room for `n` values.
+    fn with_type(code: u32, n: usize) -> Option<NcData> {
+        Some(match code {
+            1 => NcData::Byte(Vec::with_capacity(n)),
+            2 => NcData::Char(Vec::with_capacity(n)),
+            3 => NcData::Short(Vec::with_capacity(n)),
+            4 => NcData::Int(Vec::with_capacity(n)),
+            5 => NcData::Float(Vec::with_capacity(n)),
+            6 => NcData::Double(Vec::with_capacity(n)),
+            _ => return None,
+        })
+    }
+
+    fn type_size(&self) -> usize {
+        match self {
+            NcData::Byte(_) | NcData::Char(_) => 1,
+            NcData::Short(_) => 2,
+            NcData::Int(_) | NcData::Float(_) => 4,
+            NcData::Double(_) => 8,
+        }
+    }
+
+    /// Append the big-endian values in `bytes`.
+    fn extend_from(&mut self, bytes: &[u8]) {
+        match self {
+            NcData::Byte(v) => v.extend(bytes.iter().map(|&b| b as i8)),
+            NcData::Char(v) => v.extend_from_slice(bytes),
+            NcData::Short(v) => v.extend(bytes.chunks(2).map(|c| i16::from_be_bytes([c[0], c[1]]))),
+            NcData::Int(v) => v.extend(bytes.chunks(4).map(|c| i32::from_be_bytes(c.try_into().unwrap()))),
+            NcData::Float(v) => v.extend(bytes.chunks(4).map(|c| f32::from_be_bytes(c.try_into().unwrap()))),
+            NcData::Double(v) => v.extend(bytes.chunks(8).map(|c| f64::from_be_bytes(c.try_into().unwrap()))),
+        }
+    }
+
+    /// Big-endian bytes of values `range`.
+    fn bytes(&self, range: core::ops::Range<usize>, out: &mut Vec<u8>) {
+        match self {
+            NcData::Byte(v) => out.extend(v[range].iter().map(|&x| x as u8)),
+            NcData::Char(v) => out.extend_from_slice(&v[range]),
+            NcData::Short(v) => v[range].iter().for_each(|x| out.extend(x.to_be_bytes())),
+            NcData::Int(v) => v[range].iter().for_each(|x| out.extend(x.to_be_bytes())),
+            NcData::Float(v) => v[range].iter().for_each(|x| out.extend(x.to_be_bytes())),
+            NcData::Double(v) => v[range].iter().for_each(|x| out.extend(x.to_be_bytes())),
+        }
+    }
+}
+
+/// A named dimension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dimension {
+    /// Dimension name
+    pub name: String,
+    /// Length (the number of records for the unlimited dimension)
+    pub len: usize,
+    /// Whether this is the record dimension
+    pub unlimited: bool,
+}
+
+/// A named attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    /// Attribute name
+    pub name: String,
+    /// Attribute values
+    pub value: NcData,
+}
+
+/// A variable and all its values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+    /// Variable name
+    pub name: String,
+    /// Dimension indices, slowest varying first
+    pub dims: Vec<usize>,
+    /// Variable attributes
+    pub attributes: Vec<Attribute>,
+    /// Values in row-major order
+    pub data: NcData,
+}
+
+impl Variable {
+    /// Value of attribute `name`.
+    pub fn attribute(&self, name: &str) -> Option<&NcData> {
+        find_attribute(&self.attributes, name)
+    }
+
+    /// Add or replace attribute `name`.
+    pub fn set_attribute(&mut self, name: &str, value: NcData) -> &mut Self {
+        set_attribute(&mut self.attributes, name, value);
+        self
+    }
+}
+
+fn find_attribute<'a>(list: &'a [Attribute], name: &str) -> Option<&'a NcData> {
+    list.iter().find(|a| a.name == name).map(|a| &a.value)
+}
+
+fn set_attribute(list: &mut Vec<Attribute>, name: &str, value: NcData) {
+    match list.iter_mut().find(|a| a.name == name) {
+        Some(a) => a.value = value,
+        None => list.push(Attribute { name: name.into(), value }),
+    }
+}
+
+/// Contents of a NetCDF classic file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NcFile {
+    /// Format version
+    pub version: Version,
+    /// Dimensions
+    pub dimensions: Vec<Dimension>,
+    /// Global attributes
+    pub attributes: Vec<Attribute>,
+    /// Variables
+    pub variables: Vec<Variable>,
+}
+
+impl NcFile {
+    /// Empty file of the given version.
+    pub fn new(version: Version) -> Self {
+        NcFile { version, ..Default::default() }
+    }
+
+    /// Index of dimension `name`.
+    pub fn dimension(&self, name: &str) -> Option<usize> {
+        self.dimensions.iter().position(|d| d.name == name)
+    }
+
+    /// Variable `name`.
+    pub fn variable(&self, name: &str) -> Option<&Variable> {
+        self.variables.iter().find(|v| v.name == name)
+    }
+
+    /// Global attribute `name`.
+    pub fn attribute(&self, name: &str) -> Option<&NcData> {
+        find_attribute(&self.attributes, name)
+    }
+
+    /// Add or replace global attribute `name`.
+    pub fn set_attribute(&mut self, name: &str, value: NcData) -> &mut Self {
+        set_attribute(&mut self.attributes, name, value);
+        self
+    }
+
+    /// Add a fixed-size dimension and return its index.
+    pub fn add_dimension(&mut self, name: &str, len: usize) -> usize {
+        self.dimensions.push(Dimension { name: name.into(), len, unlimited: false });
+        self.dimensions.len() - 1
+    }
+
+    /// Add the record dimension with `records` records and return its index.
+    /// Panics if the file already has one.
+    pub fn add_unlimited_dimension(&mut self, name: &str, records: usize) -> usize {
+        assert!(self.dimensions.iter().all(|d| !d.unlimited), "only one unlimited dimension is allowed");
+        self.dimensions.push(Dimension { name: name.into(), len: records, unlimited: true });
+        self.dimensions.len() - 1
+    }
+
+    /// Add a variable. Panics if the number of values does not match its
+    /// dimensions, or the record dimension is not the first.
+    pub fn add_variable(&mut self, name: &str, dims: &[usize], data: NcData) -> &mut Variable {
+        let size: usize = dims.iter().map(|&d| self.dimensions[d].len).product();
+        assert_eq!(data.len(), size, "variable '{}' needs {} values", name, size);
+        assert!(
+            dims.iter().skip(1).all(|&d| !self.dimensions[d].unlimited),
+            "the record dimension must come first"
+        );
+        self.variables.push(Variable { name: name.into(), dims: dims.to_vec(), attributes: Vec::new(), data });
+        self.variables.last_mut().unwrap()
+    }
+
+    fn is_record(&self, v: &Variable) -> bool {
+        v.dims.first().is_some_and(|&d| self.dimensions[d].unlimited)
+    }
+
+    /// Values per record (or in total, for fixed-size variables).
+    fn slab_len(&self, v: &Variable) -> usize {
+        let skip = usize::from(self.is_record(v));
+        v.dims[skip.min(v.dims.len())..].iter().map(|&d| self.dimensions[d].len).product()
+    }
+
+    /// Read a CDF-1 or CDF-2 file.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let mut h = Header { data: &data, pos: 0 };
+        let version = match h.take(4)? {
+            b"CDF\x01" => Version::Classic,
+            b"CDF\x02" => Version::Offset64,
+            b"CDF\x05" => return Err(IoError::Unsupported("CDF-5 (64-bit data) files".into())),
+            m if m.starts_with(b"\x89HDF") => return Err(IoError::Unsupported("NetCDF-4/HDF5 files".into())),
+            _ => return h.err("not a NetCDF classic file"),
+        };
+        let numrecs = h.u32()?;
+        let mut nc = NcFile::new(version);
+
+        for _ in 0..h.list(NC_DIMENSION)? {
+            let name = h.name()?;
+            let len = h.u32()? as usize;
+            nc.dimensions.push(Dimension { name, len, unlimited: len == 0 });
+        }
+        nc.attributes = h.attributes()?;
+        // (variable, type, begin) waiting for their data
+        let mut pending = Vec::new();
+        for _ in 0..h.list(NC_VARIABLE)? {
+            let name = h.name()?;
+            let dims = (0..h.u32()?)
+                .map(|_| match h.u32()? as usize {
+                    d if d < nc.dimensions.len() => Ok(d),
+                    d => h.err(format!("variable '{}' uses missing dimension {}", name, d)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let attributes = h.attributes()?;
+            let code = h.u32()?;
+            let _vsize = h.u32()?;
+            let begin = match version {
+                Version::Classic => h.u32()? as usize,
+                Version::Offset64 => h.u64()? as usize,
+            };
+            let Some(data) = NcData::with_type(code, 0) else {
+                return h.err(format!("variable '{}' has unknown type {}", name, code));
+            };
+            nc.variables.push(Variable { name, dims, attributes, data });
+            pending.push(begin);
+        }
+
+        let too_large = || IoError::Parse { line: 0, msg: "variable sizes overflow".into() };
+        let record_vars: Vec<usize> = (0..nc.variables.len()).filter(|&i| nc.is_record(&nc.variables[i])).collect();
+        // bytes per record (or in total), checked since dimension lengths come from the file
+        let slab_bytes = nc
+            .variables
+            .iter()
+            .map(|v| {
+                let skip = usize::from(nc.is_record(v));
+                let dims = &v.dims[skip.min(v.dims.len())..];
+                dims.iter().try_fold(v.data.type_size(), |acc, &d| acc.checked_mul(nc.dimensions[d].len))
+            })
+            .collect::<Option<Vec<usize>>>()
+            .ok_or_else(too_large)?;
+        // a lone record variable is not padded
+        let recsize: usize = match record_vars[..] {
+            [i] => slab_bytes[i],
+            _ => record_vars
+                .iter()
+                .try_fold(0usize, |acc, &i| acc.checked_add(slab_bytes[i].checked_add(3)? / 4 * 4))
+                .ok_or_else(too_large)?,
+        };
+        let numrecs = if numrecs == STREAMING {
+            let start = record_vars.iter().map(|&i| pending[i]).min().unwrap_or(data.len());
+            (data.len() - start.min(data.len())).checked_div(recsize).unwrap_or(0)
+        } else {
+            numrecs as usize
+        };
+        if let Some(d) = nc.dimensions.iter_mut().find(|d| d.unlimited) {
+            d.len = numrecs;
+        }
+
+        for (i, &begin) in pending.iter().enumerate() {
+            let bytes = slab_bytes[i];
+            let records = if nc.is_record(&nc.variables[i]) { numrecs } else { 1 };
+            // the last slab must lie inside the file before anything is allocated
+            let end = match records.checked_sub(1) {
+                Some(last) => last.checked_mul(recsize).and_then(|o| o.checked_add(begin)).and_then(|s| s.checked_add(bytes)),
+                None => Some(0),
+            };
+            let total = bytes.checked_mul(records);
+            let (Some(total), Some(end)) = (total, end) else { return Err(too_large()) };
+            if end > data.len() {
+                let name = &nc.variables[i].name;
+                return Err(IoError::Parse { line: 0, msg: format!("data of variable '{}' is truncated", name) });
+            }
+            let code = nc.variables[i].data.type_code();
+            let mut values = NcData::with_type(code, total / nc.variables[i].data.type_size()).unwrap();
+            for r in 0..records {
+                let start = begin + r * recsize;
+                values.extend_from(&data[start..start + bytes]);
+            }
+            nc.variables[i].data = values;
+        }
+        Ok(nc)
+    }
+
+    /// Write the file in its `version`. Fixed dimensions of length 0 are
+    /// unsupported, as the format reserves length 0 for the record dimension.
+    pub fn write<W: Write>(&self, mut w: W) -> Result<()> {
+        if let Some(d) = self.dimensions.iter().find(|d| !d.unlimited && d.len == 0) {
+            return Err(IoError::Unsupported(format!("fixed dimension '{}' of length 0", d.name)));
+        }
+        let records = self.dimensions.iter().find(|d| d.unlimited).map_or(0, |d| d.len);
+        for v in &self.variables {
+            let size = self.slab_len(v) * if self.is_record(v) { records } else { 1 };
+            if v.data.len() != size {
+                return Err(IoError::Unsupported(format!(
+                    "variable '{}' has {} values but its dimensions give {}",
+                    v.name,
+                    v.data.len(),
+                    size
+                )));
+            }
+        }
+        let (fixed, record): (Vec<usize>, Vec<usize>) =
+            (0..self.variables.len()).partition(|&i| !self.is_record(&self.variables[i]));
+        let slab_bytes = |i: usize| self.slab_len(&self.variables[i]) * self.variables[i].data.type_size();
+        let vsize = |i: usize| pad4(slab_bytes(i));
+        // the header size does not depend on the offsets, so lay it out once
+        // with zeros to find where the data starts
+        let mut begins = vec![0u64; self.variables.len()];
+        let header_len = self.header(records, &begins).len() as u64;
+        let mut offset = header_len;
+        for &i in fixed.iter().chain(&record) {
+            begins[i] = offset;
+            offset += vsize(i) as u64;
+        }
+        if self.version == Version::Classic && offset > i32::MAX as u64 {
+            return Err(IoError::Unsupported("data beyond 2 GiB in CDF-1; use Version::Offset64".into()));
+        }
+        w.write_all(&self.header(records, &begins))?;
+
+        let mut out = Vec::new();
+        for &i in &fixed {
+            let v = &self.variables[i];
+            v.data.bytes(0..v.data.len(), &mut out);
+            out.resize(pad4(out.len()), 0);
+            w.write_all(&out)?;
+            out.clear();
+        }
+        for r in 0..records {
+            for &i in &record {
+                let v = &self.variables[i];
+                let n = self.slab_len(v);
+                v.data.bytes(r * n..(r + 1) * n, &mut out);
+                if record.len() > 1 {
+                    out.resize(pad4(out.len()), 0);
+                }
+            }
+            w.write_all(&out)?;
+            out.clear();
+        }
+        Ok(())
+    }
+
+    fn header(&self, records: usize, begins: &[u64]) -> Vec<u8> {
+        let mut h = Vec::new();
+        h.extend(match self.version {
+            Version::Classic => b"CDF\x01",
+            Version::Offset64 => b"CDF\x02",
+        });
+        put_u32(&mut h, records);
+        put_list(&mut h, NC_DIMENSION, self.dimensions.len());
+        for d in &self.dimensions {
+            put_name(&mut h, &d.name);
+            put_u32(&mut h, if d.unlimited { 0 } else { d.len });
+        }
+        put_attributes(&mut h, &self.attributes);
+        put_list(&mut h, NC_VARIABLE, self.variables.len());
+        for (v, &begin) in self.variables.iter().zip(begins) {
+            put_name(&mut h, &v.name);
+            put_u32(&mut h, v.dims.len());
+            v.dims.iter().for_each(|&d| put_u32(&mut h, d));
+            put_attributes(&mut h, &v.attributes);
+            put_u32(&mut h, v.data.type_code() as usize);
+            put_u32(&mut h, pad4(self.slab_len(v) * v.data.type_size()).min(u32::MAX as usize));
+            match self.version {
+                Version::Classic => h.extend((begin as u32).to_be_bytes()),
+                Version::Offset64 => h.extend(begin.to_be_bytes()),
+            }
+        }
+        h
+    }
+}
+
+fn pad4(n: usize) -> usize {
+    n.div_ceil(4) * 4
+}
+
+fn put_u32(h: &mut Vec<u8>, v: usize) {
+    h.extend((v as u32).to_be_bytes());
+}
+
+fn put_list(h: &mut Vec<u8>, tag: u32, n: usize) {
+    // an empty list is written as ABSENT: two zero words
+    put_u32(h, if n == 0 { 0 } else { tag as usize });
+    put_u32(h, n);
+}
+
+fn put_name(h: &mut Vec<u8>, name: &str) {
+    put_u32(h, name.len());
+    h.extend(name.as_bytes());
+    h.resize(pad4(h.len()), 0);
+}
+
+fn put_attributes(h: &mut Vec<u8>, list: &[Attribute]) {
+    put_list(h, NC_ATTRIBUTE, list.len());
+    for a in list {
+        put_name(h, &a.name);
+        put_u32(h, a.value.type_code() as usize);
+        put_u32(h, a.value.len());
+        a.value.bytes(0..a.value.len(), h);
+        h.resize(pad4(h.len()), 0);
+    }
+}
+
+/// Big-endian cursor over the header.
+struct Header<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Header<'a> {
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        Err(IoError::Parse { line: 0, msg: format!("{} (header byte {})", msg.into(), self.pos) })
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        match self.pos.checked_add(n).and_then(|end| self.data.get(self.pos..end)) {
+            Some(b) => {
+                self.pos += n;
+                Ok(b)
+            }
+            None => self.err("header is truncated"),
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Length of a tagged list, 0 when ABSENT.
+    fn list(&mut self, tag: u32) -> Result<usize> {
+        let (t, n) = (self.u32()?, self.u32()? as usize);
+        match t {
+            0 if n == 0 => Ok(0),
+            t if t == tag => Ok(n),
+            _ => self.err(format!("expected list tag {:#x}", tag)),
+        }
+    }
+
+    fn name(&mut self) -> Result<String> {
+        let n = self.u32()? as usize;
+        let bytes = self.take(pad4(n))?;
+        match std::str::from_utf8(&bytes[..n]) {
+            Ok(s) => Ok(s.into()),
+            Err(_) => self.err("name is not valid UTF-8"),
+        }
+    }
+
+    fn attributes(&mut self) -> Result<Vec<Attribute>> {
+        (0..self.list(NC_ATTRIBUTE)?)
+            .map(|_| {
+                let name = self.name()?;
+                let code = self.u32()?;
+                let n = self.u32()? as usize;
+                let Some(mut value) = NcData::with_type(code, 0) else {
+                    return self.err(format!("attribute '{}' has unknown type {}", name, code));
+                };
+                // check the values are present before allocating for them
+                let Some(bytes) = n.checked_mul(value.type_size()) else {
+                    return self.err(format!("attribute '{}' is too large", name));
+                };
+                let raw = &self.take(pad4(bytes))?[..bytes];
+                value = NcData::with_type(code, n).unwrap();
+                value.extend_from(raw);
+                Ok(Attribute { name, value })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_layout_matches_spec() {
+        // one fixed `short` variable with 3 values: padded to 8 bytes
+        let mut nc = NcFile::new(Version::Classic);
+        let n = nc.add_dimension("n", 3);
+        nc.add_variable("s", &[n], NcData::Short(vec![1, -2, 3]));
+        let mut out = Vec::new();
+        nc.write(&mut out).unwrap();
+        assert_eq!(&out[..8], b"CDF\x01\0\0\0\0");
+        let begin = u32::from_be_bytes(out[out.len() - 12..out.len() - 8].try_into().unwrap()) as usize;
+        assert_eq!(begin, out.len() - 8);
+        assert_eq!(&out[begin..], &[0, 1, 0xFF, 0xFE, 0, 3, 0, 0]);
+    }
+
+    #[test]
+    fn offset64_with_interleaved_records() {
+        let mut nc = NcFile::new(Version::Offset64);
+        let t = nc.add_unlimited_dimension("time", 3);
+        let c = nc.add_dimension("c", 2);
+        nc.add_variable("time", &[t], NcData::Double(vec![0.0, 3600.0, 7200.0]));
+        nc.add_variable("flag", &[t, c], NcData::Byte(vec![1, 2, 3, 4, 5, 6]));
+        nc.add_variable("depth", &[c], NcData::Float(vec![1.5, 2.5]));
+        nc.set_attribute("Conventions", "CF-1.8 UGRID-1.0".into());
+        let mut out = Vec::new();
+        nc.write(&mut out).unwrap();
+        assert_eq!(NcFile::read(&out[..]).unwrap(), nc);
+
+        // streamed files give the record count as 0xFFFFFFFF
+        out[4..8].copy_from_slice(&STREAMING.to_be_bytes());
+        assert_eq!(NcFile::read(&out[..]).unwrap(), nc);
+    }
+
+    #[test]
+    fn rejects_empty_fixed_dimensions_and_oversized_counts() {
+        let mut nc = NcFile::new(Version::Classic);
+        let n = nc.add_dimension("nFaces", 0);
+        nc.add_variable("face_x", &[n], NcData::Double(Vec::new()));
+        assert!(matches!(nc.write(Vec::new()), Err(IoError::Unsupported(_))));
+
+        // a global attribute claiming 2^31 doubles in a 40-byte file
+        let mut data = b"CDF\x01".to_vec();
+        for word in [0u32, 0, 0, NC_ATTRIBUTE, 1, 1] {
+            data.extend(word.to_be_bytes());
+        }
+        data.extend(b"a\0\0\0");
+        data.extend(6u32.to_be_bytes());
+        data.extend(0x8000_0000u32.to_be_bytes());
+        assert!(matches!(NcFile::read(&data[..]), Err(IoError::Parse { .. })));
+    }
+}
@@ -0,0 +1,109 @@
+//! Wavefront OBJ polygon surfaces.
+//!
+//! [`read_obj`] reads `v` vertices and `f` faces; face corners may carry
+//! texture and normal references (`1/2/3`, `1//3`), which are dropped, and
+//! negative indices count back from the latest vertex. Groups, materials,
+//! lines and free-form geometry are ignored. [`write_obj`] writes `v` and
+//! `f` records only.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::obj::{read_obj, write_obj};
+//!
+//! let text = "\
+//! # breakwater crest
+//! o crest
+//! v 0 0 2
+//! v 4 0 2
+//! v 4 1 2.5
+//! v 0 1 2.5
+//! vn 0 -0.45 0.89
+//! f 1//1 2//1 3//1 4//1
+//! ";
+//! let surface = read_obj::<f64, _>(text.as_bytes()).unwrap();
+//! assert_eq!(surface.faces, vec![vec![0, 1, 2, 3]]);
+//!
+//! let mut out = Vec::new();
+//! write_obj(&mut out, &surface).unwrap();
+//! assert_eq!(read_obj::<f64, _>(&out[..]).unwrap(), surface);
+//! ```
+
+use std::io::{Read, Write};
+use std::vec::Vec;
+
+use crate::{Float, Point3};
+use crate::io::{read_text, Lines, Result, Surface};
+
+/// Read a Wavefront OBJ file.
+pub fn read_obj<T: Float, R: Read>(reader: R) -> Result<Surface<T>> {
+    let text = read_text(reader)?;
+    let mut lines = Lines::new(&text);
+    let mut surface = Surface::new(Vec::new(), Vec::new());
+    while let Some(l) = lines.next_line() {
+        let mut t = l.split_whitespace();
+        match t.next() {
+            Some("v") => {
+                let x: f64 = lines.parse(t.next(), "x")?;
+                let y: f64 = lines.parse(t.next(), "y")?;
+                let z: f64 = lines.parse(t.next(), "z")?;
+                surface.points.push(Point3::new(T::from(x).unwrap(), T::from(y).unwrap(), T::from(z).unwrap()));
+            }
+            Some("f") => {
+                let face = t
+                    .map(|corner| {
+                        let v = corner.split('/').next();
+                        let i: i64 = lines.parse(v, "vertex index")?;
+                        let n = surface.points.len() as i64;
+                        let k = if i < 0 { n + i } else { i - 1 };
+                        if i == 0 || !(0..n).contains(&k) {
+                            return lines.err(format!("vertex index {} out of range", i));
+                        }
+                        Ok(k as usize)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if face.len() < 3 {
+                    return lines.err("face with fewer than 3 vertices");
+                }
+                surface.faces.push(face);
+            }
+            _ => {}
+        }
+    }
+    Ok(surface)
+}
+
+/// Write `surface` as Wavefront OBJ.
+pub fn write_obj<T: Float, W: Write>(mut w: W, surface: &Surface<T>) -> Result<()> {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    for p in &surface.points {
+        writeln!(w, "v {} {} {}", f(p.x), f(p.y), f(p.z))?;
+    }
+    for face in &surface.faces {
+        write!(w, "f")?;
+        for n in face {
+            write!(w, " {}", n + 1)?;
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::IoError;
+
+    #[test]
+    fn relative_indices_and_errors() {
+        let text = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3/1 -2/2 -1/3\nv 1 1 0\nf 2 4 -2\n";
+        let surface = read_obj::<f64, _>(text.as_bytes()).unwrap();
+        assert_eq!(surface.faces, vec![vec![0, 1, 2], vec![1, 3, 2]]);
+        assert!((surface.area() - 1.0).abs() < 1e-12);
+
+        match read_obj::<f64, _>("v 0 0 0\nv 1 0 0\nf 1 2 5\n".as_bytes()) {
+            Err(IoError::Parse { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,517 @@
+//! OpenFOAM `polyMesh` reader.
+//!
+//! A polyMesh directory holds `points`, `faces`, `owner`, `neighbour` and
+//! `boundary`. Faces are listed internal faces first, each oriented with its
+//! normal from owner to neighbour, followed by the boundary faces of each
+//! patch. [`read_polymesh`] reads such a directory (ASCII or binary format,
+//! `faceList` or `faceCompactList`, 32- or 64-bit labels) into a
+//! [`PolyMesh`], and [`PolyMesh::to_mesh`] rebuilds one polyhedral cell per
+//! OpenFOAM cell, with every face outward, so `polyhedron_volume`,
+//! `FvGeometry` and `face_normal` can be checked against `checkMesh`.
+//! Compressed (`.gz`) files are not supported.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::openfoam::PolyMesh;
+//! use mesh_geometry::mesh::FvGeometry;
+//!
+//! let header = |class: &str| {
+//!     format!("FoamFile {{ version 2.0; format ascii; class {}; object x; }}\n", class)
+//! };
+//! let points = header("vectorField")
+//!     + "4 ((0 0 0) (1 0 0) (0 1 0) (0 0 1))";
+//! let faces = header("faceList") + "4 (3(0 2 1) 3(0 1 3) 3(1 2 3) 3(0 3 2))";
+//! let owner = header("labelList") + "4{0}";
+//! let neighbour = header("labelList") + "0()";
+//! let boundary = header("polyBoundaryMesh")
+//!     + "1 ( walls { type wall; nFaces 4; startFace 0; } )";
+//!
+//! let foam = PolyMesh::<f64>::from_bytes(
+//!     points.as_bytes(), faces.as_bytes(), owner.as_bytes(), neighbour.as_bytes(), boundary.as_bytes(),
+//! ).unwrap();
+//! assert_eq!(foam.patches[0].name, "walls");
+//! let geo = FvGeometry::new(&foam.to_mesh()).unwrap();
+//! assert!((geo.cell_volumes[0] - 1.0 / 6.0).abs() < 1e-12);
+//! ```
+
+use std::path::Path;
+use std::str::FromStr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point3};
+use crate::io::{IoError, Result};
+use crate::mesh::{Cell, Mesh3};
+
+/// A boundary patch: a contiguous range of boundary faces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoamPatch {
+    /// Patch name
+    pub name: String,
+    /// Patch type (`patch`, `wall`, `empty`, `symmetryPlane`, ...)
+    pub patch_type: String,
+    /// First face of the patch
+    pub start_face: usize,
+    /// Number of faces
+    pub n_faces: usize,
+}
+
+/// Contents of an OpenFOAM `polyMesh` directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolyMesh<T: Float> {
+    /// Point coordinates
+    pub points: Vec<Point3<T>>,
+    /// Face point lists, normals from owner to neighbour
+    pub faces: Vec<Vec<usize>>,
+    /// Owner cell of every face
+    pub owner: Vec<usize>,
+    /// Neighbour cell of every internal face
+    pub neighbour: Vec<usize>,
+    /// Boundary patches
+    pub patches: Vec<FoamPatch>,
+}
+
+impl<T: Float> PolyMesh<T> {
+    /// Number of cells
+    pub fn num_cells(&self) -> usize {
+        self.owner.iter().chain(&self.neighbour).max().map_or(0, |&c| c + 1)
+    }
+
+    /// Number of internal faces (those with a neighbour)
+    pub fn num_internal_faces(&self) -> usize {
+        self.neighbour.len()
+    }
+
+    /// One polyhedral cell per OpenFOAM cell, faces oriented outward.
+    pub fn to_mesh(&self) -> Mesh3<T> {
+        let mut cell_faces: Vec<Vec<Vec<usize>>> = vec![Vec::new(); self.num_cells()];
+        for (f, face) in self.faces.iter().enumerate() {
+            cell_faces[self.owner[f]].push(face.clone());
+            if let Some(&nb) = self.neighbour.get(f) {
+                cell_faces[nb].push(face.iter().rev().copied().collect());
+            }
+        }
+        let cells = cell_faces.iter().map(|faces| Cell::polyhedron(faces)).collect();
+        Mesh3::from_parts(self.points.clone(), cells)
+    }
+
+    /// Parse the five polyMesh files from memory.
+    pub fn from_bytes(points: &[u8], faces: &[u8], owner: &[u8], neighbour: &[u8], boundary: &[u8]) -> Result<Self> {
+        let mut p = Foam::new(points)?;
+        let points = p.points()?;
+        let mut f = Foam::new(faces)?;
+        let faces = f.faces()?;
+        let mut o = Foam::new(owner)?;
+        let owner = o.labels(faces.len())?;
+        let neighbour = Foam::new(neighbour)?.labels(faces.len())?;
+        let patches = Foam::new(boundary)?.patches()?;
+        if owner.len() != faces.len() || neighbour.len() > faces.len() {
+            return Err(IoError::Parse { line: 0, msg: "owner/neighbour sizes do not match faces".into() });
+        }
+        if let Some(&bad) = faces.iter().flatten().find(|&&n| n >= points.len()) {
+            return Err(IoError::Parse { line: 0, msg: format!("face references missing point {}", bad) });
+        }
+        // without an `nCells` note, every cell still needs a face of its own
+        let n_cells = o.n_cells.unwrap_or(faces.len());
+        if let Some(&bad) = owner.iter().chain(&neighbour).find(|&&c| c >= n_cells) {
+            return Err(IoError::Parse { line: 0, msg: format!("cell {} is beyond the {} cells", bad, n_cells) });
+        }
+        Ok(PolyMesh { points, faces, owner, neighbour, patches })
+    }
+}
+
+/// Read `points`, `faces`, `owner`, `neighbour` and `boundary` from `dir`.
+pub fn read_polymesh<T: Float>(dir: impl AsRef<Path>) -> Result<PolyMesh<T>> {
+    let dir = dir.as_ref();
+    let read = |name: &str| -> Result<Vec<u8>> {
+        let path = dir.join(name);
+        if !path.exists() && dir.join(format!("{}.gz", name)).exists() {
+            return Err(IoError::Unsupported(format!("compressed {}.gz", name)));
+        }
+        Ok(std::fs::read(path)?)
+    };
+    PolyMesh::from_bytes(&read("points")?, &read("faces")?, &read("owner")?, &read("neighbour")?, &read("boundary")?)
+}
+
+/// Cursor over one OpenFOAM file, after its `FoamFile` header.
+struct Foam<'a> {
+    data: &'a [u8],
+    pos: usize,
+    binary: bool,
+    label_bytes: usize,
+    scalar_bytes: usize,
+    class: String,
+    /// Cell count from the header's `note`, when given
+    n_cells: Option<usize>,
+}
+
+impl<'a> Foam<'a> {
+    fn new(data: &'a [u8]) -> Result<Self> {
+        let mut foam =
+            Foam { data, pos: 0, binary: false, label_bytes: 4, scalar_bytes: 8, class: String::new(), n_cells: None };
+        if foam.word()? != "FoamFile" {
+            return foam.err("missing FoamFile header");
+        }
+        foam.expect(b'{')?;
+        loop {
+            foam.skip_space();
+            if foam.peek() == Some(b'}') {
+                foam.pos += 1;
+                break;
+            }
+            let key = foam.word()?;
+            foam.skip_space();
+            // quoted values (`arch "LSB;label=32;scalar=64"`) contain ';'
+            let value = if foam.peek() == Some(b'"') {
+                foam.pos += 1;
+                let v = foam.until(b'"')?;
+                foam.expect(b';')?;
+                v
+            } else {
+                foam.until(b';')?.trim()
+            };
+            match key {
+                "format" => foam.binary = value == "binary",
+                "class" => foam.class = value.into(),
+                // `note "nPoints:12 nCells:2 nFaces:11 nInternalFaces:1"`
+                "note" => {
+                    foam.n_cells = value.split_whitespace().find_map(|w| w.strip_prefix("nCells:")?.parse().ok());
+                }
+                "arch" => {
+                    for part in value.split(';') {
+                        match part.trim() {
+                            "label=64" => foam.label_bytes = 8,
+                            "scalar=32" => foam.scalar_bytes = 4,
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(foam)
+    }
+
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        let end = self.pos.min(self.data.len());
+        let line = self.data[..end].iter().filter(|&&b| b == b'\n').count() + 1;
+        Err(IoError::Parse { line, msg: msg.into() })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    /// Skip whitespace and `//` / `/* */` comments.
+    fn skip_space(&mut self) {
+        loop {
+            let rest = &self.data[self.pos.min(self.data.len())..];
+            if rest.first().is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            } else if rest.starts_with(b"//") {
+                self.pos += rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+            } else if rest.starts_with(b"/*") {
+                self.pos += rest.windows(2).position(|w| w == b"*/").map_or(rest.len(), |i| i + 2);
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        self.skip_space();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.err(format!("expected '{}'", c as char))
+        }
+    }
+
+    /// Next token, up to whitespace or punctuation.
+    fn word(&mut self) -> Result<&'a str> {
+        self.skip_space();
+        let start = self.pos;
+        while self.peek().is_some_and(|b| !b.is_ascii_whitespace() && !b"(){};".contains(&b)) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return self.err("expected a word");
+        }
+        std::str::from_utf8(&self.data[start..self.pos]).or_else(|_| self.err("invalid UTF-8"))
+    }
+
+    /// Text up to (and consuming) `end`.
+    fn until(&mut self, end: u8) -> Result<&'a str> {
+        let start = self.pos;
+        match self.data[start..].iter().position(|&b| b == end) {
+            Some(len) => {
+                self.pos += len + 1;
+                std::str::from_utf8(&self.data[start..start + len]).or_else(|_| self.err("invalid UTF-8"))
+            }
+            None => self.err(format!("missing '{}'", end as char)),
+        }
+    }
+
+    fn number<V: FromStr>(&mut self) -> Result<V> {
+        let w = self.word()?;
+        w.parse().or_else(|_| self.err(format!("invalid number '{}'", w)))
+    }
+
+    /// A list length, checked by [`Foam::fits`].
+    fn count(&mut self) -> Result<usize> {
+        let n: usize = self.number()?;
+        self.fits(n)?;
+        Ok(n)
+    }
+
+    /// Rejects a list of `n` entries that cannot fit in the bytes left, since
+    /// every listed entry takes at least one.
+    fn fits(&self, n: usize) -> Result<()> {
+        if n > self.data.len().saturating_sub(self.pos) {
+            return self.err(format!("list of {} entries is longer than the file", n));
+        }
+        Ok(())
+    }
+
+    fn raw(&mut self, n: usize) -> Result<&'a [u8]> {
+        match self.pos.checked_add(n).and_then(|end| self.data.get(self.pos..end)) {
+            Some(b) => {
+                self.pos += n;
+                Ok(b)
+            }
+            None => self.err("binary list runs past end of file"),
+        }
+    }
+
+    fn binary_labels(&mut self, n: usize) -> Result<Vec<usize>> {
+        let width = self.label_bytes;
+        let Some(len) = n.checked_mul(width) else { return self.err("binary list is too long") };
+        let bytes = self.raw(len)?;
+        bytes
+            .chunks(width)
+            .map(|c| {
+                let label = match width {
+                    8 => usize::try_from(i64::from_le_bytes(c.try_into().unwrap())),
+                    _ => usize::try_from(i32::from_le_bytes(c.try_into().unwrap())),
+                };
+                label.or_else(|_| self.err("negative label"))
+            })
+            .collect()
+    }
+
+    /// A `labelList`: `N(...)`, binary `N(<bytes>)` or uniform `N{v}`. A
+    /// uniform list takes no room per entry, so its length is checked against
+    /// `uniform_max` instead of the bytes left.
+    fn labels(&mut self, uniform_max: usize) -> Result<Vec<usize>> {
+        let n: usize = self.number()?;
+        self.skip_space();
+        if self.peek() == Some(b'{') {
+            if n > uniform_max {
+                return self.err(format!("uniform list of {} entries, expected at most {}", n, uniform_max));
+            }
+            self.pos += 1;
+            let v = self.number()?;
+            self.expect(b'}')?;
+            return Ok(vec![v; n]);
+        }
+        self.fits(n)?;
+        self.expect(b'(')?;
+        let out = if self.binary && n > 0 {
+            self.binary_labels(n)?
+        } else {
+            (0..n).map(|_| self.number()).collect::<Result<_>>()?
+        };
+        self.expect(b')')?;
+        Ok(out)
+    }
+
+    fn points<T: Float>(&mut self) -> Result<Vec<Point3<T>>> {
+        let n = self.count()?;
+        self.expect(b'(')?;
+        let Some(len) = n.checked_mul(3) else { return self.err("point list is too long") };
+        let mut coords: Vec<f64> = Vec::with_capacity(len);
+        if self.binary && n > 0 {
+            let width = self.scalar_bytes;
+            let Some(bytes) = len.checked_mul(width) else { return self.err("point list is too long") };
+            for c in self.raw(bytes)?.chunks(width) {
+                coords.push(match width {
+                    4 => f32::from_le_bytes(c.try_into().unwrap()) as f64,
+                    _ => f64::from_le_bytes(c.try_into().unwrap()),
+                });
+            }
+        } else {
+            for _ in 0..n {
+                self.expect(b'(')?;
+                for _ in 0..3 {
+                    coords.push(self.number()?);
+                }
+                self.expect(b')')?;
+            }
+        }
+        self.expect(b')')?;
+        Ok(coords
+            .chunks(3)
+            .map(|c| Point3::new(T::from(c[0]).unwrap(), T::from(c[1]).unwrap(), T::from(c[2]).unwrap()))
+            .collect())
+    }
+
+    fn faces(&mut self) -> Result<Vec<Vec<usize>>> {
+        if self.class == "faceCompactList" {
+            let offsets = self.labels(0)?;
+            let flat = self.labels(0)?;
+            if offsets.last().is_some_and(|&end| end > flat.len()) || offsets.windows(2).any(|w| w[0] > w[1]) {
+                return self.err("inconsistent faceCompactList offsets");
+            }
+            return Ok(offsets.windows(2).map(|w| flat[w[0]..w[1]].to_vec()).collect());
+        }
+        if self.binary {
+            return Err(IoError::Unsupported("binary faceList; expected faceCompactList".into()));
+        }
+        let n = self.count()?;
+        self.expect(b'(')?;
+        let faces = (0..n).map(|_| self.labels(0)).collect::<Result<_>>()?;
+        self.expect(b')')?;
+        Ok(faces)
+    }
+
+    fn patches(&mut self) -> Result<Vec<FoamPatch>> {
+        let n = self.count()?;
+        self.expect(b'(')?;
+        let mut patches = Vec::with_capacity(n);
+        for _ in 0..n {
+            let name = self.word()?.into();
+            self.expect(b'{')?;
+            let mut patch = FoamPatch { name, patch_type: String::new(), start_face: 0, n_faces: 0 };
+            loop {
+                self.skip_space();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    break;
+                }
+                let key = self.word()?;
+                let value = self.until(b';')?.trim();
+                let count = |s: &str| s.parse().or_else(|_| self.err(format!("invalid {} '{}'", key, s)));
+                match key {
+                    "type" => patch.patch_type = value.into(),
+                    "nFaces" => patch.n_faces = count(value)?,
+                    "startFace" => patch.start_face = count(value)?,
+                    _ => {}
+                }
+            }
+            patches.push(patch);
+        }
+        self.expect(b')')?;
+        Ok(patches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::FvGeometry;
+    use crate::metrics::face_normal;
+
+    /// Two unit hexes along x, as OpenFOAM would number them.
+    fn block(binary: bool) -> [Vec<u8>; 5] {
+        let format = if binary { "binary" } else { "ascii" };
+        let header = |class: &str| {
+            format!("/* test */\nFoamFile\n{{\n    format {};\n    arch \"LSB;label=32;scalar=64\";\n    class {};\n}}\n// comment\n", format, class)
+        };
+        let pts: Vec<[f64; 3]> = (0..12).map(|i| [(i % 3) as f64, ((i / 3) % 2) as f64, (i / 6) as f64]).collect();
+        // one internal face, then 10 boundary faces
+        let faces: Vec<Vec<usize>> = vec![
+            vec![1, 4, 10, 7],
+            vec![0, 6, 9, 3],
+            vec![2, 5, 11, 8],
+            vec![0, 1, 7, 6],
+            vec![1, 2, 8, 7],
+            vec![3, 9, 10, 4],
+            vec![4, 10, 11, 5],
+            vec![0, 3, 4, 1],
+            vec![1, 4, 5, 2],
+            vec![6, 7, 10, 9],
+            vec![7, 8, 11, 10],
+        ];
+        let owner = [0, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1];
+        let mut points = header("vectorField").into_bytes();
+        let mut face_bytes;
+        let mut owner_bytes = header("labelList").into_bytes();
+        let mut nb = header("labelList").into_bytes();
+        if binary {
+            let label = |v: usize| (v as i32).to_le_bytes();
+            points.extend(b"12\n(");
+            pts.iter().flatten().for_each(|c| points.extend(c.to_le_bytes()));
+            points.extend(b")\n");
+            face_bytes = header("faceCompactList").into_bytes();
+            face_bytes.extend(b"12\n(");
+            (0..12).for_each(|k| face_bytes.extend(label(4 * k)));
+            face_bytes.extend(b")\n44\n(");
+            faces.iter().flatten().for_each(|&n| face_bytes.extend(label(n)));
+            face_bytes.extend(b")\n");
+            owner_bytes.extend(b"11\n(");
+            owner.iter().for_each(|&o| owner_bytes.extend(label(o)));
+            owner_bytes.extend(b")\n");
+            nb.extend(b"1\n(");
+            nb.extend(label(1));
+            nb.extend(b")\n");
+        } else {
+            let list = |v: &[usize]| v.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ");
+            let p: Vec<String> = pts.iter().map(|c| format!("({} {} {})", c[0], c[1], c[2])).collect();
+            points.extend(format!("12\n(\n{}\n)\n", p.join("\n")).into_bytes());
+            let f: Vec<String> = faces.iter().map(|f| format!("4({})", list(f))).collect();
+            face_bytes = header("faceList").into_bytes();
+            face_bytes.extend(format!("11\n(\n{}\n)\n", f.join("\n")).into_bytes());
+            owner_bytes.extend(format!("11\n(\n{}\n)\n", list(&owner)).into_bytes());
+            nb.extend(b"1\n(\n1\n)\n");
+        }
+        let boundary = header("polyBoundaryMesh")
+            + "3\n(\n    inlet\n    {\n        type patch;\n        nFaces 1;\n        startFace 1;\n    }\n"
+            + "    outlet { type patch; nFaces 1; startFace 2; inGroups List<word> 1(outflow); }\n"
+            + "    walls { type wall; nFaces 8; startFace 3; }\n)\n";
+        [points, face_bytes, owner_bytes, nb, boundary.into_bytes()]
+    }
+
+    #[test]
+    fn ascii_and_binary_agree() {
+        let [p, f, o, n, b] = block(false);
+        let ascii = PolyMesh::<f64>::from_bytes(&p, &f, &o, &n, &b).unwrap();
+        let [p, f, o, n, b] = block(true);
+        let binary = PolyMesh::<f64>::from_bytes(&p, &f, &o, &n, &b).unwrap();
+        assert_eq!(ascii, binary);
+        assert_eq!(ascii.num_cells(), 2);
+        assert_eq!(ascii.patches[2], FoamPatch { name: "walls".into(), patch_type: "wall".into(), start_face: 3, n_faces: 8 });
+    }
+
+    #[test]
+    fn cells_close_with_unit_volume() {
+        let [p, f, o, n, b] = block(false);
+        let foam = PolyMesh::<f64>::from_bytes(&p, &f, &o, &n, &b).unwrap();
+        let internal = face_normal(&foam.faces[0].iter().map(|&i| foam.points[i]).collect::<Vec<_>>());
+        assert!(internal.x > 0.0, "internal face points from owner to neighbour");
+        let geo = FvGeometry::new(&foam.to_mesh()).unwrap();
+        assert!(geo.cell_volumes.iter().all(|v| (v - 1.0).abs() < 1e-12));
+        assert!((geo.cell_centroids[1].x - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bad_counts_and_labels_are_errors() {
+        let [_, f, o, n, b] = block(false);
+        let huge = b"FoamFile { format ascii; class vectorField; }\n9223372036854775807\n((0 0 0))\n";
+        assert!(matches!(PolyMesh::<f64>::from_bytes(huge, &f, &o, &n, &b), Err(IoError::Parse { .. })));
+        let [p, f, _, n, b] = block(false);
+        let uniform = b"FoamFile { format ascii; class labelList; }\n9223372036854775807{0}\n";
+        assert!(matches!(PolyMesh::<f64>::from_bytes(&p, &f, uniform, &n, &b), Err(IoError::Parse { .. })));
+        let [p, f, mut o, n, b] = block(true);
+        let end = o.len() - 2;
+        o[end - 4..end].copy_from_slice(&(-1i32).to_le_bytes());
+        assert!(matches!(PolyMesh::<f64>::from_bytes(&p, &f, &o, &n, &b), Err(IoError::Parse { .. })));
+        let [p, f, o, mut n, b] = block(false);
+        let end = n.len() - 4;
+        n[end] = b'2';
+        let noted = String::from_utf8(o).unwrap().replace("class labelList;", "class labelList; note \"nCells:2\";");
+        let err = PolyMesh::<f64>::from_bytes(&p, &f, noted.as_bytes(), &n, &b);
+        assert!(matches!(err, Err(IoError::Parse { .. })), "neighbour 2 of 2 cells");
+    }
+}
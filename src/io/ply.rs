@@ -0,0 +1,336 @@
+//! PLY polygon surfaces, ASCII and binary.
+//!
+//! [`read_ply`] reads the `x`, `y`, `z` properties of the `vertex` element
+//! and the `vertex_indices` (or `vertex_index`) list of the `face` element,
+//! in any of the three PLY formats and with any property types. Other
+//! properties and elements are skipped. [`write_ply`] writes `double`
+//! coordinates and `int` indices, little-endian when binary.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::Encoding;
+//! use mesh_geometry::io::ply::{read_ply, write_ply};
+//!
+//! let text = "\
+//! ply
+//! format ascii 1.0
+//! comment surveyed pier deck
+//! element vertex 4
+//! property float x
+//! property float y
+//! property float z
+//! property uchar red
+//! element face 1
+//! property list uchar int vertex_indices
+//! end_header
+//! 0 0 3 255
+//! 2 0 3 255
+//! 2 8 3 255
+//! 0 8 3 255
+//! 4 0 1 2 3
+//! ";
+//! let surface = read_ply::<f64, _>(text.as_bytes()).unwrap();
+//! assert!((surface.area() - 16.0).abs() < 1e-12);
+//!
+//! let mut out = Vec::new();
+//! write_ply(&mut out, &surface, Encoding::Binary).unwrap();
+//! assert_eq!(read_ply::<f64, _>(&out[..]).unwrap(), surface);
+//! ```
+
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point3};
+use crate::io::{Encoding, IoError, Result, Surface};
+
+/// Body layout declared in the header.
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Ascii,
+    LittleEndian,
+    BigEndian,
+}
+
+/// Scalar property type.
+#[derive(Clone, Copy)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Scalar> {
+        Some(match name {
+            "char" | "int8" => Scalar::I8,
+            "uchar" | "uint8" => Scalar::U8,
+            "short" | "int16" => Scalar::I16,
+            "ushort" | "uint16" => Scalar::U16,
+            "int" | "int32" => Scalar::I32,
+            "uint" | "uint32" => Scalar::U32,
+            "float" | "float32" => Scalar::F32,
+            "double" | "float64" => Scalar::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Scalar::I8 | Scalar::U8 => 1,
+            Scalar::I16 | Scalar::U16 => 2,
+            Scalar::I32 | Scalar::U32 | Scalar::F32 => 4,
+            Scalar::F64 => 8,
+        }
+    }
+}
+
+/// A property: a scalar, or a list with its count type.
+struct Property {
+    name: String,
+    count: Option<Scalar>,
+    value: Scalar,
+}
+
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// Body reader over ASCII tokens or binary values.
+struct Body<'a> {
+    data: &'a [u8],
+    pos: usize,
+    format: Format,
+}
+
+impl Body<'_> {
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        Err(IoError::Parse { line: 0, msg: msg.into() })
+    }
+
+    fn value(&mut self, ty: Scalar) -> Result<f64> {
+        if self.format == Format::Ascii {
+            while self.data.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+                self.pos += 1;
+            }
+            let start = self.pos;
+            while self.data.get(self.pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+            let tok = std::str::from_utf8(&self.data[start..self.pos]).unwrap_or("");
+            return match tok.parse() {
+                Ok(v) => Ok(v),
+                Err(_) if tok.is_empty() => self.err("unexpected end of data"),
+                Err(_) => self.err(format!("invalid value '{}'", tok)),
+            };
+        }
+        let Some(b) = self.data.get(self.pos..self.pos + ty.size()) else {
+            return self.err("unexpected end of data");
+        };
+        self.pos += ty.size();
+        let mut buf = [0u8; 8];
+        buf[..b.len()].copy_from_slice(b);
+        if self.format == Format::BigEndian {
+            buf[..b.len()].reverse();
+        }
+        Ok(match ty {
+            Scalar::I8 => buf[0] as i8 as f64,
+            Scalar::U8 => buf[0] as f64,
+            Scalar::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+            Scalar::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+            Scalar::I32 => i32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+            Scalar::U32 => u32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+            Scalar::F32 => f32::from_le_bytes(buf[..4].try_into().unwrap()) as f64,
+            Scalar::F64 => f64::from_le_bytes(buf),
+        })
+    }
+
+    /// A list length or vertex index, which must be a non-negative whole number.
+    fn index(&mut self, ty: Scalar) -> Result<usize> {
+        let v = self.value(ty)?;
+        if v >= 0.0 && v.fract() == 0.0 && v <= u32::MAX as f64 {
+            Ok(v as usize)
+        } else {
+            self.err(format!("invalid index {}", v))
+        }
+    }
+}
+
+/// Read an ASCII or binary PLY file.
+pub fn read_ply<T: Float, R: Read>(mut reader: R) -> Result<Surface<T>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let end = data
+        .windows(10)
+        .position(|w| w == b"end_header")
+        .and_then(|i| data[i..].iter().position(|&b| b == b'\n').map(|n| i + n + 1))
+        .ok_or_else(|| IoError::Parse { line: 0, msg: "missing end_header".into() })?;
+    let header = std::str::from_utf8(&data[..end])
+        .map_err(|_| IoError::Parse { line: 0, msg: "PLY header is not valid UTF-8".into() })?;
+    if !header.starts_with("ply") {
+        return Err(IoError::Parse { line: 1, msg: "missing 'ply' magic".into() });
+    }
+
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    for (i, l) in header.lines().enumerate() {
+        let err = |msg: String| IoError::Parse { line: i + 1, msg };
+        let t: Vec<&str> = l.split_whitespace().collect();
+        match t[..] {
+            ["ply"] | ["end_header"] | [] => {}
+            ["comment", ..] | ["obj_info", ..] => {}
+            ["format", f, _] => {
+                format = Some(match f {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::LittleEndian,
+                    "binary_big_endian" => Format::BigEndian,
+                    _ => return Err(err(format!("unknown format '{}'", f))),
+                })
+            }
+            ["element", name, count] => {
+                let count = count.parse().map_err(|_| err(format!("invalid element count '{}'", count)))?;
+                elements.push(Element { name: name.into(), count, properties: Vec::new() });
+            }
+            ["property", "list", c, v, name] => {
+                let scalar = |s: &str| Scalar::parse(s).ok_or_else(|| err(format!("unknown type '{}'", s)));
+                let property = Property { name: name.into(), count: Some(scalar(c)?), value: scalar(v)? };
+                elements.last_mut().ok_or_else(|| err("property before element".into()))?.properties.push(property);
+            }
+            ["property", v, name] => {
+                let value = Scalar::parse(v).ok_or_else(|| err(format!("unknown type '{}'", v)))?;
+                let property = Property { name: name.into(), count: None, value };
+                elements.last_mut().ok_or_else(|| err("property before element".into()))?.properties.push(property);
+            }
+            _ => return Err(err(format!("unexpected header line '{}'", l))),
+        }
+    }
+    let format = format.ok_or_else(|| IoError::Parse { line: 0, msg: "missing format line".into() })?;
+
+    let mut body = Body { data: &data[end..], pos: 0, format };
+    let mut surface = Surface::new(Vec::new(), Vec::new());
+    for element in &elements {
+        let find = |name: &str| element.properties.iter().position(|p| p.name == name);
+        let xyz = [find("x"), find("y"), find("z")];
+        let indices = find("vertex_indices").or_else(|| find("vertex_index"));
+        for _ in 0..element.count {
+            let mut coords = [0.0; 3];
+            for (k, p) in element.properties.iter().enumerate() {
+                let Some(count) = p.count else {
+                    let v = body.value(p.value)?;
+                    if let Some(axis) = xyz.iter().position(|&a| a == Some(k)) {
+                        coords[axis] = v;
+                    }
+                    continue;
+                };
+                let n = body.index(count)?;
+                if element.name == "face" && indices == Some(k) {
+                    surface.faces.push((0..n).map(|_| body.index(p.value)).collect::<Result<Vec<_>>>()?);
+                } else {
+                    (0..n).try_for_each(|_| body.value(p.value).map(drop))?;
+                }
+            }
+            if element.name == "vertex" {
+                let c = coords.map(|x| T::from(x).unwrap());
+                surface.points.push(Point3::new(c[0], c[1], c[2]));
+            }
+        }
+    }
+    if let Some(bad) = surface.faces.iter().flatten().find(|&&n| n >= surface.points.len()) {
+        return Err(IoError::Parse { line: 0, msg: format!("face references missing vertex {}", bad) });
+    }
+    Ok(surface)
+}
+
+/// Write `surface` as PLY, binary little-endian or ASCII.
+pub fn write_ply<T: Float, W: Write>(mut w: W, surface: &Surface<T>, encoding: Encoding) -> Result<()> {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    let wide = surface.faces.iter().any(|face| face.len() > 255);
+    let format = match encoding {
+        Encoding::Ascii => "ascii",
+        Encoding::Binary => "binary_little_endian",
+    };
+    writeln!(w, "ply\nformat {} 1.0\ncomment mesh-geometry", format)?;
+    writeln!(w, "element vertex {}", surface.points.len())?;
+    writeln!(w, "property double x\nproperty double y\nproperty double z")?;
+    writeln!(w, "element face {}", surface.faces.len())?;
+    writeln!(w, "property list {} int vertex_indices", if wide { "int" } else { "uchar" })?;
+    writeln!(w, "end_header")?;
+    match encoding {
+        Encoding::Ascii => {
+            for p in &surface.points {
+                writeln!(w, "{} {} {}", f(p.x), f(p.y), f(p.z))?;
+            }
+            for face in &surface.faces {
+                write!(w, "{}", face.len())?;
+                for n in face {
+                    write!(w, " {}", n)?;
+                }
+                writeln!(w)?;
+            }
+        }
+        Encoding::Binary => {
+            let mut out = Vec::new();
+            for p in &surface.points {
+                [p.x, p.y, p.z].iter().for_each(|&x| out.extend(f(x).to_le_bytes()));
+            }
+            for face in &surface.faces {
+                if wide {
+                    out.extend((face.len() as i32).to_le_bytes());
+                } else {
+                    out.push(face.len() as u8);
+                }
+                face.iter().for_each(|&n| out.extend((n as i32).to_le_bytes()));
+            }
+            w.write_all(&out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_endian_with_extra_elements() {
+        let mut data = b"ply\nformat binary_big_endian 1.0\nelement vertex 3\nproperty short id\n\
+property float x\nproperty float y\nproperty float z\nelement face 1\nproperty uchar flags\n\
+property list ushort uint vertex_index\nelement edge 1\nproperty int vertex1\nproperty int vertex2\nend_header\n"
+            .to_vec();
+        for (i, p) in [[0.0f32, 0.0, 1.0], [3.0, 0.0, 1.0], [0.0, 2.0, 1.0]].iter().enumerate() {
+            data.extend((i as i16).to_be_bytes());
+            p.iter().for_each(|x| data.extend(x.to_be_bytes()));
+        }
+        data.push(7);
+        data.extend(3u16.to_be_bytes());
+        [0u32, 1, 2].iter().for_each(|n| data.extend(n.to_be_bytes()));
+        data.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+
+        let surface = read_ply::<f64, _>(&data[..]).unwrap();
+        assert_eq!(surface.points[1], Point3::new(3.0, 0.0, 1.0));
+        assert_eq!(surface.faces, vec![vec![0, 1, 2]]);
+        assert!((surface.area() - 3.0).abs() < 1e-12);
+
+        let mut out = Vec::new();
+        write_ply(&mut out, &surface, Encoding::Ascii).unwrap();
+        assert_eq!(read_ply::<f64, _>(&out[..]).unwrap(), surface);
+    }
+
+    #[test]
+    fn rejects_negative_and_fractional_indices() {
+        let head = "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+element face 1\nproperty list uchar float vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n";
+        assert_eq!(read_ply::<f64, _>(format!("{head}3 0 1 2\n").as_bytes()).unwrap().faces, vec![vec![0, 1, 2]]);
+        for face in ["3 0 -1 2", "3 0 1.5 2"] {
+            assert!(matches!(read_ply::<f64, _>(format!("{head}{face}\n").as_bytes()), Err(IoError::Parse { .. })));
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Gridded rasters and their sampling onto meshes.
+//!
+//! A [`Raster`] is a north-up grid of cell values, as read from ESRI ASCII
+//! grids ([`read_asc`](crate::io::asc::read_asc)) and GeoTIFFs
+//! ([`read_geotiff`](crate::io::geotiff::read_geotiff)). Values sit at cell
+//! centres. [`Raster::sample`] interpolates bilinearly between the four
+//! surrounding centres (clamped to the edge cells within the raster's
+//! extent), and [`Raster::average`] weights every raster cell by its overlap
+//! area with a polygon, using the polygon's [`Aabb`] to limit the cells
+//! visited and `polygon_area` of the clipped polygon as the weight. `nodata`
+//! cells are left out of both, with the remaining weights renormalised.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::io::Raster;
+//! use mesh_geometry::mesh::{Cell, Mesh2};
+//!
+//! // 2×2 cells of size 10 with the upper-left corner at (0, 20)
+//! let dem = Raster { ncols: 2, nrows: 2, x_min: 0.0, y_max: 20.0, dx: 10.0, dy: 10.0,
+//!                    nodata: None, values: vec![1.0, 2.0, 3.0, 4.0] };
+//! assert_eq!(dem.sample(Point2::new(10.0, 10.0)), Some(2.5));
+//!
+//! let mut mesh = Mesh2::new();
+//! for (x, y) in [(0.0, 0.0), (20.0, 0.0), (20.0, 20.0), (0.0, 20.0)] {
+//!     mesh.add_node(Point2::new(x, y));
+//! }
+//! mesh.add_cell(Cell::Triangle([0, 1, 3]));
+//! // the lower-left triangle covers all of the cell valued 3 and half of 4 and 1
+//! let depth: f64 = dem.average_cells(&mesh)[0].unwrap();
+//! assert!((depth - (3.0 * 100.0 + 4.0 * 50.0 + 1.0 * 50.0) / 200.0).abs() < 1e-12);
+//! ```
+
+use std::vec::Vec;
+
+use crate::{Float, Point2, Point3, polygon_area};
+use crate::aabb::Aabb;
+use crate::mesh::Mesh2;
+
+/// A north-up grid of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raster<T: Float> {
+    /// Number of columns
+    pub ncols: usize,
+    /// Number of rows
+    pub nrows: usize,
+    /// x of the left edge
+    pub x_min: T,
+    /// y of the top edge
+    pub y_max: T,
+    /// Cell width
+    pub dx: T,
+    /// Cell height
+    pub dy: T,
+    /// Value marking missing cells
+    pub nodata: Option<T>,
+    /// Values row by row, top row first
+    pub values: Vec<T>,
+}
+
+impl<T: Float> Raster<T> {
+    /// Value of a cell, `None` if it is `nodata` or NaN.
+    pub fn get(&self, col: usize, row: usize) -> Option<T> {
+        let v = self.values[row * self.ncols + col];
+        (!v.is_nan() && Some(v) != self.nodata).then_some(v)
+    }
+
+    /// Area covered by the raster (with `z = 0`).
+    pub fn extent(&self) -> Aabb<T> {
+        Aabb {
+            min: Point3::new(self.x_min, self.y_max - self.dy * self.len_t(self.nrows), T::zero()),
+            max: Point3::new(self.x_min + self.dx * self.len_t(self.ncols), self.y_max, T::zero()),
+        }
+    }
+
+    fn len_t(&self, n: usize) -> T {
+        T::from(n).unwrap()
+    }
+
+    /// Bilinear interpolation at `p`; `None` outside the raster or when all
+    /// contributing cells are `nodata`.
+    pub fn sample(&self, p: Point2<T>) -> Option<T> {
+        if !self.extent().contains(Point3::new(p.x, p.y, T::zero())) || self.ncols == 0 || self.nrows == 0 {
+            return None;
+        }
+        let half = T::from(0.5).unwrap();
+        // fractional cell-centre coordinates, clamped to the edge centres
+        let axis = |t: T, n: usize| -> (usize, T) {
+            let t = t.max(T::zero()).min(self.len_t(n - 1));
+            let i = t.floor().to_usize().unwrap_or(0).min(n.saturating_sub(2));
+            (i, t - self.len_t(i))
+        };
+        let (i, fu) = axis((p.x - self.x_min) / self.dx - half, self.ncols);
+        let (j, fv) = axis((self.y_max - p.y) / self.dy - half, self.nrows);
+        let mut sum = T::zero();
+        let mut weight = T::zero();
+        for (di, wu) in [(0, T::one() - fu), (1, fu)] {
+            for (dj, wv) in [(0, T::one() - fv), (1, fv)] {
+                let w = wu * wv;
+                if w == T::zero() || i + di >= self.ncols || j + dj >= self.nrows {
+                    continue;
+                }
+                if let Some(v) = self.get(i + di, j + dj) {
+                    sum = sum + w * v;
+                    weight = weight + w;
+                }
+            }
+        }
+        (weight > T::zero()).then(|| sum / weight)
+    }
+
+    /// Area-weighted mean of the cells overlapping `polygon`; `None` when it
+    /// covers no valid cell.
+    pub fn average(&self, polygon: &[Point2<T>]) -> Option<T> {
+        let lifted: Vec<Point3<T>> = polygon.iter().map(|p| Point3::new(p.x, p.y, T::zero())).collect();
+        let bounds = Aabb::from_points(&lifted);
+        if polygon.len() < 3 || !bounds.intersects(&self.extent()) {
+            return None;
+        }
+        let cols = self.span((bounds.min.x - self.x_min) / self.dx, (bounds.max.x - self.x_min) / self.dx, self.ncols);
+        let rows = self.span((self.y_max - bounds.max.y) / self.dy, (self.y_max - bounds.min.y) / self.dy, self.nrows);
+        let mut sum = T::zero();
+        let mut weight = T::zero();
+        for row in rows {
+            let top = self.y_max - self.dy * self.len_t(row);
+            for col in cols.clone() {
+                let Some(v) = self.get(col, row) else { continue };
+                let left = self.x_min + self.dx * self.len_t(col);
+                let clipped = clip_to_rect(polygon, left, top - self.dy, left + self.dx, top);
+                if clipped.len() >= 3 {
+                    let a = polygon_area(&clipped);
+                    sum = sum + a * v;
+                    weight = weight + a;
+                }
+            }
+        }
+        (weight > T::zero()).then(|| sum / weight)
+    }
+
+    /// Cell indices covering `[lo, hi]` (in cell units), clamped to `0..n`.
+    fn span(&self, lo: T, hi: T, n: usize) -> core::ops::Range<usize> {
+        let lo = lo.floor().max(T::zero()).to_usize().unwrap_or(0).min(n);
+        let hi = hi.ceil().max(T::zero()).to_usize().unwrap_or(n).min(n);
+        lo..hi
+    }
+
+    /// Bilinear samples at every mesh node.
+    pub fn sample_nodes(&self, mesh: &Mesh2<T>) -> Vec<Option<T>> {
+        mesh.nodes.iter().map(|&p| self.sample(p)).collect()
+    }
+
+    /// Area-weighted averages over every mesh cell.
+    pub fn average_cells(&self, mesh: &Mesh2<T>) -> Vec<Option<T>> {
+        mesh.cell_points().map(|c| self.average(c.points())).collect()
+    }
+}
+
+/// Sutherland–Hodgman clip of `poly` to an axis-aligned rectangle.
+fn clip_to_rect<T: Float>(poly: &[Point2<T>], x0: T, y0: T, x1: T, y1: T) -> Vec<Point2<T>> {
+    // each edge: signed distance inside (>= 0 keeps)
+    let edges: [&dyn Fn(Point2<T>) -> T; 4] = [&|p| p.x - x0, &|p| x1 - p.x, &|p| p.y - y0, &|p| y1 - p.y];
+    let mut out = poly.to_vec();
+    for inside in edges {
+        let input = core::mem::take(&mut out);
+        for (k, &b) in input.iter().enumerate() {
+            let a = input[(k + input.len() - 1) % input.len()];
+            let (da, db) = (inside(a), inside(b));
+            if (da >= T::zero()) != (db >= T::zero()) {
+                let t = da / (da - db);
+                out.push(Point2::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t));
+            }
+            if db >= T::zero() {
+                out.push(b);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp() -> Raster<f64> {
+        // value = column + 10·row on a 4×3 grid of unit cells, one hole
+        let mut values: Vec<f64> = (0..12).map(|k| (k % 4) as f64 + 10.0 * (k / 4) as f64).collect();
+        values[5] = -9999.0;
+        Raster { ncols: 4, nrows: 3, x_min: 100.0, y_max: 53.0, dx: 1.0, dy: 1.0, nodata: Some(-9999.0), values }
+    }
+
+    #[test]
+    fn bilinear_edges_and_nodata() {
+        let r = ramp();
+        assert_eq!(r.sample(Point2::new(100.5, 52.5)), Some(0.0));
+        assert_eq!(r.sample(Point2::new(102.0, 52.5)), Some(1.5));
+        // clamped near the right edge
+        assert_eq!(r.sample(Point2::new(103.9, 52.5)), Some(3.0));
+        // the nodata cell (1, 1) drops out of the interpolation
+        assert_eq!(r.sample(Point2::new(101.5, 51.5)), None);
+        assert_eq!(r.sample(Point2::new(101.5, 51.0)), Some(21.0));
+        assert_eq!(r.sample(Point2::new(99.0, 52.0)), None);
+    }
+
+    #[test]
+    fn area_weights_skip_nodata() {
+        let r = ramp();
+        // square over cells (0,0), (1,0), (0,1), (1,1): a quarter of each
+        let square = [
+            Point2::new(100.5, 51.5),
+            Point2::new(101.5, 51.5),
+            Point2::new(101.5, 52.5),
+            Point2::new(100.5, 52.5),
+        ];
+        assert!((r.average(&square).unwrap() - (0.0 + 1.0 + 10.0) / 3.0).abs() < 1e-12);
+        let outside = [Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(0.0, 1.0)];
+        assert_eq!(r.average(&outside), None);
+    }
+}
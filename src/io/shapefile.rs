@@ -0,0 +1,430 @@
+//! ESRI Shapefile reader (`.shp`, `.shx`, `.dbf`).
+//!
+//! Reads point, multipoint, polyline and polygon shapes (including their `Z`
+//! and `M` variants, whose extra ordinates are dropped) as [`Geometry`]
+//! values, with the matching `.dbf` attribute row. Polygon rings follow the
+//! Shapefile rule: clockwise rings are outer boundaries and counter-clockwise
+//! rings are holes, each hole belonging to the smallest outer ring that
+//! contains it. Rings are returned open and re-oriented to the crate's
+//! convention (outer counter-clockwise, holes clockwise), so
+//! `polygon_signed_area` is positive for outer rings. `MultiPatch` shapes
+//! are not supported. Text attributes are decoded as UTF-8, lossily.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use mesh_geometry::io::shapefile::read_shapefile;
+//!
+//! // reads coast.shp, plus coast.shx and coast.dbf when present
+//! let coast = read_shapefile::<f64>("data/coast.shp").unwrap();
+//! for (i, shape) in coast.records.iter().enumerate() {
+//!     let area: f64 = shape.geometry.iter().flat_map(|g| g.polygons()).map(|p| p.area()).sum();
+//!     println!("{:?}: {}", coast.attribute(i, "NAME"), area);
+//! }
+//! ```
+
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2, polygon_area, polygon_signed_area};
+use crate::io::geometry::open_ring;
+use crate::io::{Geometry, IoError, Polygon, Result};
+use crate::queries::point_in_polygon;
+
+/// A `.dbf` column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbfField {
+    /// Column name
+    pub name: String,
+    /// dBASE type letter (`C`, `N`, `F`, `L`, `D`, ...)
+    pub field_type: char,
+    /// Width in bytes
+    pub length: usize,
+    /// Digits after the decimal point
+    pub decimals: usize,
+}
+
+/// One attribute value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbfValue {
+    /// Character field, trailing blanks removed
+    Text(String),
+    /// Numeric or float field; `None` when blank
+    Number(Option<f64>),
+    /// Logical field; `None` when unset (`?`)
+    Logical(Option<bool>),
+    /// Date field as `YYYYMMDD`
+    Date(String),
+}
+
+/// One shape and its attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape<T: Float> {
+    /// Geometry, `None` for null shapes
+    pub geometry: Option<Geometry<T>>,
+    /// Attribute values in field order (empty without a `.dbf`)
+    pub attributes: Vec<DbfValue>,
+}
+
+/// Contents of a shapefile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shapefile<T: Float> {
+    /// Shape type code from the `.shp` header (1, 3, 5, 8 and Z/M variants)
+    pub shape_type: u32,
+    /// Bounding box `[xmin, ymin, xmax, ymax]` from the header
+    pub bbox: [f64; 4],
+    /// Attribute columns
+    pub fields: Vec<DbfField>,
+    /// Shapes in file order
+    pub records: Vec<Shape<T>>,
+}
+
+impl<T: Float> Shapefile<T> {
+    /// Value of column `name` for record `i`.
+    pub fn attribute(&self, i: usize, name: &str) -> Option<&DbfValue> {
+        let col = self.fields.iter().position(|f| f.name == name)?;
+        self.records.get(i)?.attributes.get(col)
+    }
+
+    /// Parse a shapefile from memory; `.shx` and `.dbf` are optional.
+    pub fn from_bytes(shp: &[u8], shx: Option<&[u8]>, dbf: Option<&[u8]>) -> Result<Self> {
+        if shp.len() < 100 || be_i32(shp, 0) != 9994 {
+            return parse_err("not a .shp file");
+        }
+        let shape_type = le_i32(shp, 32) as u32;
+        let bbox = [le_f64(shp, 36), le_f64(shp, 44), le_f64(shp, 52), le_f64(shp, 60)];
+
+        // record offsets come from the index when there is one
+        let offsets: Vec<usize> = match shx {
+            Some(shx) if shx.len() >= 100 => match shx[100..].chunks_exact(8).map(|e| words(e, 0)).collect() {
+                Some(offsets) => offsets,
+                None => return parse_err(".shx has an invalid offset"),
+            },
+            _ => {
+                let mut offsets = Vec::new();
+                let mut pos = 100;
+                while pos + 8 <= shp.len() {
+                    offsets.push(pos);
+                    let Some(next) = words(shp, pos + 4).and_then(|len| len.checked_add(pos + 8)) else {
+                        return parse_err(format!("record {} has an invalid length", offsets.len()));
+                    };
+                    pos = next;
+                }
+                offsets
+            }
+        };
+        let mut records = Vec::with_capacity(offsets.len());
+        for (i, &pos) in offsets.iter().enumerate() {
+            let Some(len) = pos.checked_add(8).and_then(|end| shp.get(pos + 4..end)).map(|b| words(b, 0)) else {
+                return parse_err(format!("record {} is outside the .shp file", i + 1));
+            };
+            let Some(content) = len.and_then(|len| shp.get(pos + 8..(pos + 8).checked_add(len)?)) else {
+                return parse_err(format!("record {} is truncated", i + 1));
+            };
+            records.push(Shape { geometry: shape(content, i)?, attributes: Vec::new() });
+        }
+
+        let mut fields = Vec::new();
+        if let Some(dbf) = dbf {
+            let rows;
+            (fields, rows) = read_dbf(dbf)?;
+            if rows.len() != records.len() {
+                return parse_err(format!(".dbf has {} rows for {} shapes", rows.len(), records.len()));
+            }
+            for (r, row) in records.iter_mut().zip(rows) {
+                r.attributes = row;
+            }
+        }
+        Ok(Shapefile { shape_type, bbox, fields, records })
+    }
+}
+
+/// Read `path` (a `.shp`) with the `.shx` and `.dbf` beside it, if present.
+pub fn read_shapefile<T: Float>(path: impl AsRef<Path>) -> Result<Shapefile<T>> {
+    let path = path.as_ref();
+    let shp = std::fs::read(path)?;
+    let sibling = |ext: &str| std::fs::read(path.with_extension(ext)).ok();
+    let (shx, dbf) = (sibling("shx"), sibling("dbf"));
+    Shapefile::from_bytes(&shp, shx.as_deref(), dbf.as_deref())
+}
+
+fn parse_err<V>(msg: impl Into<String>) -> Result<V> {
+    Err(IoError::Parse { line: 0, msg: msg.into() })
+}
+
+fn be_i32(b: &[u8], at: usize) -> i32 {
+    i32::from_be_bytes(b[at..at + 4].try_into().unwrap())
+}
+
+/// A length or offset in 16-bit words, in bytes; `None` if negative or too large.
+fn words(b: &[u8], at: usize) -> Option<usize> {
+    usize::try_from(be_i32(b, at)).ok()?.checked_mul(2)
+}
+
+fn le_i32(b: &[u8], at: usize) -> i32 {
+    i32::from_le_bytes(b[at..at + 4].try_into().unwrap())
+}
+
+fn le_f64(b: &[u8], at: usize) -> f64 {
+    f64::from_le_bytes(b[at..at + 8].try_into().unwrap())
+}
+
+/// Decode one record's content.
+fn shape<T: Float>(c: &[u8], index: usize) -> Result<Option<Geometry<T>>> {
+    let short = || parse_err(format!("record {} is too short", index + 1));
+    if c.len() < 4 {
+        return short();
+    }
+    let point = |at: usize| Point2::new(T::from(le_f64(c, at)).unwrap(), T::from(le_f64(c, at + 8)).unwrap());
+    Ok(Some(match le_i32(c, 0) {
+        0 => return Ok(None),
+        1 | 11 | 21 => {
+            if c.len() < 20 {
+                return short();
+            }
+            Geometry::Point(point(4))
+        }
+        8 | 18 | 28 => {
+            let n = c.get(36..40).map_or(Some(0), |b| usize::try_from(le_i32(b, 0)).ok());
+            let Some(n) = n.filter(|&n| n.checked_mul(16).is_some_and(|b| b <= c.len().saturating_sub(40))) else {
+                return short();
+            };
+            Geometry::MultiPoint((0..n).map(|k| point(40 + 16 * k)).collect())
+        }
+        t @ (3 | 13 | 23 | 5 | 15 | 25) => {
+            if c.len() < 44 {
+                return short();
+            }
+            let (Ok(parts), Ok(n)) = (usize::try_from(le_i32(c, 36)), usize::try_from(le_i32(c, 40))) else {
+                return parse_err(format!("record {} has negative part or point counts", index + 1));
+            };
+            let points_at = parts.checked_mul(4).and_then(|b| b.checked_add(44));
+            let end = points_at.zip(n.checked_mul(16)).and_then(|(at, b)| at.checked_add(b));
+            let (Some(points_at), Some(end)) = (points_at, end) else { return short() };
+            if c.len() < end {
+                return short();
+            }
+            let starts: Option<Vec<usize>> = (0..parts).map(|k| usize::try_from(le_i32(c, 44 + 4 * k)).ok()).collect();
+            let Some(mut starts) = starts.filter(|s| s.iter().chain([&n]).is_sorted()) else {
+                return parse_err(format!("record {} has invalid part offsets", index + 1));
+            };
+            starts.push(n);
+            let mut lines: Vec<Vec<Point2<T>>> =
+                starts.windows(2).map(|w| (w[0]..w[1]).map(|k| point(points_at + 16 * k)).collect()).collect();
+            if matches!(t, 3 | 13 | 23) {
+                match lines.len() {
+                    1 => Geometry::LineString(lines.pop().unwrap()),
+                    _ => Geometry::MultiLineString(lines),
+                }
+            } else {
+                let mut polygons = assemble(lines.into_iter().map(open_ring).filter(|r| r.len() >= 3).collect());
+                match polygons.len() {
+                    1 => Geometry::Polygon(polygons.pop().unwrap()),
+                    _ => Geometry::MultiPolygon(polygons),
+                }
+            }
+        }
+        31 => return Err(IoError::Unsupported("MultiPatch shapes".into())),
+        t => return parse_err(format!("record {} has unknown shape type {}", index + 1, t)),
+    }))
+}
+
+/// Group rings into polygons: clockwise rings are outer, the rest are holes
+/// of the smallest outer ring containing them. Holes outside every outer
+/// ring are kept as polygons of their own.
+fn assemble<T: Float>(rings: Vec<Vec<Point2<T>>>) -> Vec<Polygon<T>> {
+    let (outers, holes): (Vec<_>, Vec<_>) = rings.into_iter().partition(|r| polygon_signed_area(r) < T::zero());
+    let mut polygons: Vec<Polygon<T>> = outers
+        .into_iter()
+        .map(|mut r| {
+            r.reverse();
+            Polygon { exterior: r, holes: Vec::new() }
+        })
+        .collect();
+    let areas: Vec<T> = polygons.iter().map(|p| polygon_area(&p.exterior)).collect();
+    for mut hole in holes {
+        let owner = (0..polygons.len())
+            .filter(|&k| point_in_polygon(hole[0], &polygons[k].exterior))
+            .min_by(|&a, &b| areas[a].partial_cmp(&areas[b]).unwrap_or(core::cmp::Ordering::Equal));
+        match owner {
+            Some(k) => {
+                hole.reverse();
+                polygons[k].holes.push(hole);
+            }
+            None => polygons.push(Polygon { exterior: hole, holes: Vec::new() }),
+        }
+    }
+    polygons
+}
+
+/// Parse a dBASE III table.
+fn read_dbf(dbf: &[u8]) -> Result<(Vec<DbfField>, Vec<Vec<DbfValue>>)> {
+    if dbf.len() < 32 {
+        return parse_err(".dbf header is truncated");
+    }
+    let rows = u32::from_le_bytes(dbf[4..8].try_into().unwrap()) as usize;
+    let header_len = u16::from_le_bytes([dbf[8], dbf[9]]) as usize;
+    let record_len = u16::from_le_bytes([dbf[10], dbf[11]]) as usize;
+    let mut fields = Vec::new();
+    let mut at = 32;
+    while at + 32 <= dbf.len().min(header_len) && dbf[at] != 0x0D {
+        let d = &dbf[at..at + 32];
+        let name = String::from_utf8_lossy(&d[..11]).trim_end_matches('\0').trim().into();
+        fields.push(DbfField { name, field_type: d[11] as char, length: d[16] as usize, decimals: d[17] as usize });
+        at += 32;
+    }
+    if 1 + fields.iter().map(|f| f.length).sum::<usize>() > record_len {
+        return parse_err(".dbf fields are wider than its records");
+    }
+
+    // the rows must fit in the file before room is made for them
+    if rows.checked_mul(record_len).and_then(|b| b.checked_add(header_len)).is_none_or(|end| end > dbf.len()) {
+        return parse_err(format!(".dbf is too short for its {} rows", rows));
+    }
+    let mut table = Vec::with_capacity(rows);
+    for r in 0..rows {
+        let start = header_len + r * record_len;
+        let Some(row) = dbf.get(start..start + record_len) else {
+            return parse_err(format!(".dbf row {} is truncated", r + 1));
+        };
+        // byte 0 is the deletion flag; deleted rows are kept to stay aligned with the shapes
+        let mut at = 1;
+        let values = fields
+            .iter()
+            .map(|f| {
+                let raw = String::from_utf8_lossy(&row[at..at + f.length]);
+                at += f.length;
+                let text = raw.trim();
+                match f.field_type {
+                    'N' | 'F' => DbfValue::Number(text.parse().ok()),
+                    'L' => DbfValue::Logical(match text {
+                        "T" | "t" | "Y" | "y" => Some(true),
+                        "F" | "f" | "N" | "n" => Some(false),
+                        _ => None,
+                    }),
+                    'D' => DbfValue::Date(text.into()),
+                    _ => DbfValue::Text(raw.trim_end().into()),
+                }
+            })
+            .collect();
+        table.push(values);
+    }
+    Ok((fields, table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `.shp` and `.shx` bytes for polygon/polyline records given as parts.
+    fn shapefile(shape_type: i32, records: &[Vec<Vec<[f64; 2]>>]) -> (Vec<u8>, Vec<u8>) {
+        let header = |len_bytes: usize| {
+            let mut h = vec![0u8; 100];
+            h[..4].copy_from_slice(&9994i32.to_be_bytes());
+            h[24..28].copy_from_slice(&((len_bytes / 2) as i32).to_be_bytes());
+            h[28..32].copy_from_slice(&1000i32.to_le_bytes());
+            h[32..36].copy_from_slice(&shape_type.to_le_bytes());
+            h
+        };
+        let mut body = Vec::new();
+        let mut index = Vec::new();
+        for (i, parts) in records.iter().enumerate() {
+            let mut c = Vec::new();
+            c.extend(shape_type.to_le_bytes());
+            c.extend([0u8; 32]);
+            let n: usize = parts.iter().map(Vec::len).sum();
+            c.extend((parts.len() as i32).to_le_bytes());
+            c.extend((n as i32).to_le_bytes());
+            let mut start = 0;
+            for p in parts {
+                c.extend((start as i32).to_le_bytes());
+                start += p.len();
+            }
+            parts.iter().flatten().flatten().for_each(|x| c.extend(x.to_le_bytes()));
+            index.extend((((100 + body.len()) / 2) as i32).to_be_bytes());
+            index.extend(((c.len() / 2) as i32).to_be_bytes());
+            body.extend(((i + 1) as i32).to_be_bytes());
+            body.extend(((c.len() / 2) as i32).to_be_bytes());
+            body.extend(c);
+        }
+        let mut shp = header(100 + body.len());
+        shp.extend(body);
+        let mut shx = header(100 + index.len());
+        shx.extend(index);
+        (shp, shx)
+    }
+
+    fn dbf(names: &[&str], rows: &[(&str, &str)]) -> Vec<u8> {
+        let mut d = vec![0u8; 32];
+        d[0] = 3;
+        d[4..8].copy_from_slice(&(rows.len() as u32).to_le_bytes());
+        d[8..10].copy_from_slice(&(32u16 + 64 + 1).to_le_bytes());
+        d[10..12].copy_from_slice(&(1u16 + 10 + 8).to_le_bytes());
+        for (name, (ty, len)) in names.iter().zip([(b'C', 10u8), (b'N', 8)]) {
+            let mut f = [0u8; 32];
+            f[..name.len()].copy_from_slice(name.as_bytes());
+            f[11] = ty;
+            f[16] = len;
+            d.extend(f);
+        }
+        d.push(0x0D);
+        for (text, num) in rows {
+            d.push(b' ');
+            d.extend(format!("{:<10}{:>8}", text, num).bytes());
+        }
+        d.push(0x1A);
+        d
+    }
+
+    #[test]
+    fn clockwise_outer_rings_and_holes() {
+        // island (clockwise) with a lake (counter-clockwise), plus a second island
+        let island = vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0], [0.0, 0.0]];
+        let lake = vec![[2.0, 2.0], [4.0, 2.0], [4.0, 4.0], [2.0, 4.0], [2.0, 2.0]];
+        let islet = vec![[20.0, 0.0], [20.0, 1.0], [21.0, 1.0], [20.0, 0.0]];
+        let (shp, shx) = shapefile(5, &[vec![lake, island, islet]]);
+        let table = dbf(&["NAME", "POP"], &[("Isle", "12.5")]);
+        let file = Shapefile::<f64>::from_bytes(&shp, Some(&shx), Some(&table)).unwrap();
+
+        let polys = file.records[0].geometry.as_ref().unwrap().polygons();
+        assert_eq!(polys.len(), 2);
+        assert_eq!(polys[0].holes.len(), 1);
+        assert!(polygon_signed_area(&polys[0].exterior) > 0.0);
+        assert_eq!(polys[0].area(), 96.0);
+        assert!(!polys[0].contains(Point2::new(3.0, 3.0)));
+        assert_eq!(polys[1].area(), 0.5);
+        assert_eq!(file.attribute(0, "NAME"), Some(&DbfValue::Text("Isle".into())));
+        assert_eq!(file.attribute(0, "POP"), Some(&DbfValue::Number(Some(12.5))));
+    }
+
+    #[test]
+    fn polylines_without_index() {
+        let coast = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.5]];
+        let (shp, _) = shapefile(3, &[vec![coast.clone()], vec![coast.clone(), coast]]);
+        let file = Shapefile::<f64>::from_bytes(&shp, None, None).unwrap();
+        assert!(matches!(&file.records[0].geometry, Some(Geometry::LineString(l)) if l.len() == 3));
+        assert!(matches!(&file.records[1].geometry, Some(Geometry::MultiLineString(m)) if m.len() == 2));
+        assert!(Shapefile::<f64>::from_bytes(&shp[..shp.len() - 8], None, None).is_err());
+    }
+
+    #[test]
+    fn corrupt_counts_are_parse_errors() {
+        let (shp, shx) = shapefile(3, &[vec![vec![[0.0, 0.0], [1.0, 1.0]]]]);
+        let corrupt = |mut bytes: Vec<u8>, at: usize, word: [u8; 4]| {
+            bytes[at..at + 4].copy_from_slice(&word);
+            bytes
+        };
+        let parse_error = |shp: &[u8], shx: Option<&[u8]>, dbf: Option<&[u8]>| {
+            matches!(Shapefile::<f64>::from_bytes(shp, shx, dbf), Err(IoError::Parse { .. }))
+        };
+        // record length, part count and point count
+        assert!(parse_error(&corrupt(shp.clone(), 104, (-4i32).to_be_bytes()), None, None));
+        assert!(parse_error(&corrupt(shp.clone(), 144, i32::MAX.to_le_bytes()), None, None));
+        assert!(parse_error(&corrupt(shp.clone(), 148, (-1i32).to_le_bytes()), None, None));
+        // index offset and .dbf row count
+        assert!(parse_error(&shp, Some(&corrupt(shx, 100, i32::MIN.to_be_bytes())), None));
+        let table = corrupt(dbf(&["NAME", "POP"], &[("a", "1")]), 4, u32::MAX.to_le_bytes());
+        assert!(parse_error(&shp, None, Some(&table)));
+    }
+}
@@ -0,0 +1,246 @@
+//! SMS `.2dm` mesh files.
+//!
+//! Reads and writes the `MESH2D` subset used for 2D hydrodynamic meshes:
+//! `ND` nodes with elevation, `E3T` triangles and `E4Q` quads with material
+//! ids, and `NS` nodestrings (a string ends at its negated last node). Other
+//! cards are skipped, except higher-order or line elements, which are
+//! reported as unsupported. Cards may come in any order, as SMS writes
+//! elements before nodes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::sms2dm::read_2dm;
+//! use mesh_geometry::metrics::polygon_area;
+//!
+//! let text = "\
+//! MESH2D
+//! E3T 1 1 2 3 1
+//! E4Q 2 2 4 5 3 2
+//! ND 1 0 0 -1
+//! ND 2 1 0 -1
+//! ND 3 1 1 -2
+//! ND 4 2 0 -1
+//! ND 5 2 1 -2
+//! NS 1 2 -4
+//! ";
+//! let sms = read_2dm::<f64, _>(text.as_bytes()).unwrap();
+//! let area: f64 = sms.mesh.cell_points().map(|c| polygon_area(c.points())).sum();
+//! assert!((area - 1.5).abs() < 1e-12);
+//! assert_eq!(sms.materials, vec![1, 2]);
+//! assert_eq!(sms.nodestrings, vec![vec![0, 1, 3]]);
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2};
+use crate::io::{read_text, IoError, Lines, Result};
+use crate::mesh::{Cell, Mesh2};
+
+/// Contents of a `.2dm` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sms2dm<T: Float> {
+    /// `MESHNAME`, if given
+    pub name: Option<String>,
+    /// Mesh of triangles and quads
+    pub mesh: Mesh2<T>,
+    /// `z` value of each node (elevation, positive up)
+    pub elevations: Vec<T>,
+    /// File node number of each node
+    pub node_ids: Vec<usize>,
+    /// Material id of each cell
+    pub materials: Vec<i32>,
+    /// Nodestrings, as node indices
+    pub nodestrings: Vec<Vec<usize>>,
+}
+
+/// Read an SMS `.2dm` mesh.
+pub fn read_2dm<T: Float, R: Read>(reader: R) -> Result<Sms2dm<T>> {
+    let text = read_text(reader)?;
+    let mut lines = Lines::new(&text);
+    match lines.next_line() {
+        Some(l) if l.trim() == "MESH2D" => {}
+        _ => return lines.err("expected MESH2D"),
+    }
+    let mut sms = Sms2dm {
+        name: None,
+        mesh: Mesh2::new(),
+        elevations: Vec::new(),
+        node_ids: Vec::new(),
+        materials: Vec::new(),
+        nodestrings: Vec::new(),
+    };
+    let mut index = BTreeMap::new();
+    // cells and nodestrings with file node numbers, and their lines
+    let mut raw_cells: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut raw_strings: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut open_string = false;
+
+    while let Some(l) = lines.next_line() {
+        let mut t = l.split_whitespace();
+        match t.next() {
+            Some("ND") => {
+                let id: usize = lines.parse(t.next(), "node number")?;
+                let x: f64 = lines.parse(t.next(), "x")?;
+                let y: f64 = lines.parse(t.next(), "y")?;
+                let z: f64 = lines.parse(t.next(), "z")?;
+                if index.insert(id, sms.node_ids.len()).is_some() {
+                    return lines.err(format!("duplicate node number {}", id));
+                }
+                sms.node_ids.push(id);
+                sms.mesh.add_node(Point2::new(T::from(x).unwrap(), T::from(y).unwrap()));
+                sms.elevations.push(T::from(z).unwrap());
+            }
+            Some(card @ ("E3T" | "E4Q")) => {
+                let n = if card == "E3T" { 3 } else { 4 };
+                let _id: usize = lines.parse(t.next(), "element number")?;
+                let nodes = (0..n).map(|_| lines.parse(t.next(), "node number")).collect::<Result<Vec<usize>>>()?;
+                sms.materials.push(t.next().map_or(Ok(1), |m| lines.parse(Some(m), "material id"))?);
+                raw_cells.push((lines.line, nodes));
+            }
+            Some(card @ ("E2L" | "E3L" | "E6T" | "E8Q" | "E9Q")) => {
+                return Err(IoError::Unsupported(format!("{} elements in .2dm", card)));
+            }
+            Some("NS") => {
+                for tok in t {
+                    let Ok(id) = tok.parse::<i64>() else {
+                        // a name may follow the terminating node
+                        break;
+                    };
+                    if !open_string {
+                        raw_strings.push((lines.line, Vec::new()));
+                        open_string = true;
+                    }
+                    raw_strings.last_mut().unwrap().1.push(id.unsigned_abs() as usize);
+                    if id < 0 {
+                        open_string = false;
+                        break;
+                    }
+                }
+            }
+            Some("MESHNAME") => {
+                let name = l.trim_start()["MESHNAME".len()..].trim().trim_matches('"');
+                sms.name = Some(name.into());
+            }
+            _ => {}
+        }
+    }
+
+    let resolve = |line: usize, ids: &[usize]| -> Result<Vec<usize>> {
+        ids.iter()
+            .map(|id| {
+                index.get(id).copied().ok_or_else(|| IoError::Parse { line, msg: format!("unknown node {}", id) })
+            })
+            .collect()
+    };
+    for (line, ids) in raw_cells {
+        let n = resolve(line, &ids)?;
+        sms.mesh.add_cell(match n[..] {
+            [a, b, c] => Cell::Triangle([a, b, c]),
+            [a, b, c, d] => Cell::Quad([a, b, c, d]),
+            _ => unreachable!(),
+        });
+    }
+    for (line, ids) in raw_strings {
+        sms.nodestrings.push(resolve(line, &ids)?);
+    }
+    Ok(sms)
+}
+
+/// Write an SMS `.2dm` mesh. The mesh must contain only triangles and quads.
+///
+/// Nodes are numbered from 1 unless `node_ids` has one id per node; missing
+/// elevations are written as 0 and missing materials as 1.
+pub fn write_2dm<T: Float, W: Write>(mut w: W, sms: &Sms2dm<T>) -> Result<()> {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    let numbered = sms.node_ids.len() == sms.mesh.num_nodes();
+    let id = |n: usize| if numbered { sms.node_ids[n] } else { n + 1 };
+    writeln!(w, "MESH2D")?;
+    if let Some(name) = &sms.name {
+        writeln!(w, "MESHNAME \"{}\"", name)?;
+    }
+    for (e, cell) in sms.mesh.cells.iter().enumerate() {
+        let mat = sms.materials.get(e).copied().unwrap_or(1);
+        match cell {
+            Cell::Triangle([a, b, c]) => writeln!(w, "E3T {} {} {} {} {}", e + 1, id(*a), id(*b), id(*c), mat)?,
+            Cell::Quad([a, b, c, d]) => {
+                writeln!(w, "E4Q {} {} {} {} {} {}", e + 1, id(*a), id(*b), id(*c), id(*d), mat)?
+            }
+            _ => return Err(IoError::Unsupported("cells other than triangles and quads in .2dm".into())),
+        }
+    }
+    for (i, p) in sms.mesh.nodes.iter().enumerate() {
+        writeln!(w, "ND {} {} {} {}", id(i), f(p.x), f(p.y), f(sms.elevations.get(i).copied().unwrap_or_else(T::zero)))?;
+    }
+    for s in &sms.nodestrings {
+        for (k, chunk) in s.chunks(10).enumerate() {
+            let last_chunk = (k + 1) * 10 >= s.len();
+            let ids: Vec<String> = chunk
+                .iter()
+                .enumerate()
+                .map(|(j, &n)| if last_chunk && j + 1 == chunk.len() { format!("-{}", id(n)) } else { id(n).to_string() })
+                .collect();
+            writeln!(w, "NS {}", ids.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::LoopKind;
+
+    #[test]
+    fn long_nodestring_roundtrip() {
+        // 12×1 strip of quads; the south edge is one 13-node nodestring
+        let mut text = String::from("MESH2D\nMESHNAME \"strip\"\n");
+        for i in 0..12 {
+            text += &format!("E4Q {} {} {} {} {} 3\n", i + 1, i + 1, i + 2, i + 15, i + 14);
+        }
+        for j in 0..2 {
+            for i in 0..13 {
+                text += &format!("ND {} {} {} 0.5\n", j * 13 + i + 1, i, j);
+            }
+        }
+        let south: Vec<String> = (1..=13).map(|i| i.to_string()).collect();
+        text += &format!("NS {}\nNS {}\n", south[..7].join(" "), south[7..].join(" ").replace("13", "-13"));
+
+        let sms = read_2dm::<f64, _>(text.as_bytes()).unwrap();
+        assert_eq!(sms.name.as_deref(), Some("strip"));
+        assert_eq!(sms.nodestrings, vec![(0..13).collect::<Vec<_>>()]);
+        let loops = sms.mesh.boundary_loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].kind, LoopKind::Outer);
+
+        let mut out = Vec::new();
+        write_2dm(&mut out, &sms).unwrap();
+        assert_eq!(read_2dm::<f64, _>(&out[..]).unwrap(), sms);
+    }
+
+    #[test]
+    fn missing_ids_and_elevations_fall_back() {
+        let mut mesh = Mesh2::new();
+        for (x, y) in [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)] {
+            mesh.add_node(Point2::new(x, y));
+        }
+        mesh.add_cell(Cell::Triangle([0, 1, 2]));
+        let sms = Sms2dm {
+            name: None,
+            mesh,
+            elevations: vec![2.0],
+            node_ids: vec![7],
+            materials: Vec::new(),
+            nodestrings: Vec::new(),
+        };
+        let mut out = Vec::new();
+        write_2dm(&mut out, &sms).unwrap();
+        let back = read_2dm::<f64, _>(&out[..]).unwrap();
+        assert_eq!(back.node_ids, vec![1, 2, 3]);
+        assert_eq!(back.elevations, vec![2.0, 0.0, 0.0]);
+        assert_eq!(back.materials, vec![1]);
+    }
+}
@@ -0,0 +1,203 @@
+//! STL triangle surfaces, ASCII and binary.
+//!
+//! STL stores every facet with its own three corners. [`read_stl`] detects
+//! the encoding (binary files may also start with `solid`, so the size is
+//! checked) and merges bit-identical corners into shared nodes; run
+//! `weld_vertices` afterwards to merge nearly coincident ones. Facet normals
+//! are ignored on input and recomputed on output. [`write_stl`] fans
+//! polygon faces into triangles.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::io::Encoding;
+//! use mesh_geometry::io::stl::{read_stl, write_stl};
+//!
+//! let text = "\
+//! solid pier
+//!   facet normal 0 0 1
+//!     outer loop
+//!       vertex 0 0 0
+//!       vertex 1 0 0
+//!       vertex 0 1 0
+//!     endloop
+//!   endfacet
+//!   facet normal 0 0 1
+//!     outer loop
+//!       vertex 1 0 0
+//!       vertex 1 1 0
+//!       vertex 0 1 0
+//!     endloop
+//!   endfacet
+//! endsolid pier
+//! ";
+//! let surface = read_stl::<f64, _>(text.as_bytes()).unwrap();
+//! assert_eq!(surface.points.len(), 4);
+//! assert!((surface.area() - 1.0).abs() < 1e-12);
+//!
+//! let mut out = Vec::new();
+//! write_stl(&mut out, &surface, Encoding::Binary).unwrap();
+//! assert_eq!(out.len(), 84 + 2 * 50);
+//! assert_eq!(read_stl::<f64, _>(&out[..]).unwrap(), surface);
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::vec::Vec;
+
+use crate::{Float, Point3};
+use crate::io::{Encoding, IoError, Result, Surface};
+
+/// Read an ASCII or binary STL file.
+pub fn read_stl<T: Float, R: Read>(mut reader: R) -> Result<Surface<T>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    let mut nodes = Nodes::default();
+    let binary_len = data.get(80..84).map(|n| 84 + 50 * u32::from_le_bytes(n.try_into().unwrap()) as u64);
+    if binary_len == Some(data.len() as u64) || (binary_len.is_some() && !data.starts_with(b"solid")) {
+        if binary_len != Some(data.len() as u64) {
+            return Err(IoError::Parse { line: 0, msg: "binary STL size does not match its triangle count".into() });
+        }
+        for facet in data[84..].chunks(50) {
+            let f = |k: usize| f32::from_le_bytes(facet[4 * k..4 * k + 4].try_into().unwrap()) as f64;
+            // skip the normal (floats 0..3) and the attribute word
+            let face = (0..3).map(|v| nodes.add([f(3 + 3 * v), f(4 + 3 * v), f(5 + 3 * v)])).collect();
+            nodes.faces.push(face);
+        }
+    } else {
+        let text = std::str::from_utf8(&data)
+            .map_err(|_| IoError::Parse { line: 0, msg: "ASCII STL is not valid UTF-8".into() })?;
+        let mut corners = Vec::with_capacity(3);
+        for (i, l) in text.lines().enumerate() {
+            let mut t = l.split_whitespace();
+            match t.next() {
+                Some("vertex") => {
+                    let mut c = [0.0; 3];
+                    for x in &mut c {
+                        *x = match t.next().map(str::parse) {
+                            Some(Ok(v)) => v,
+                            _ => return Err(IoError::Parse { line: i + 1, msg: "invalid vertex".into() }),
+                        };
+                    }
+                    corners.push(nodes.add(c));
+                }
+                Some("endloop") => {
+                    if corners.len() < 3 {
+                        return Err(IoError::Parse { line: i + 1, msg: "facet with fewer than 3 vertices".into() });
+                    }
+                    nodes.faces.push(std::mem::take(&mut corners));
+                }
+                _ => {}
+            }
+        }
+    }
+    let points = nodes
+        .coords
+        .iter()
+        .map(|c| Point3::new(T::from(c[0]).unwrap(), T::from(c[1]).unwrap(), T::from(c[2]).unwrap()))
+        .collect();
+    Ok(Surface::new(points, nodes.faces))
+}
+
+/// Write `surface` as STL, fanning polygons into triangles.
+pub fn write_stl<T: Float, W: Write>(mut w: W, surface: &Surface<T>, encoding: Encoding) -> Result<()> {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    let corners = |tri: [usize; 3]| tri.map(|n| surface.points[n]);
+    let normal = |[a, b, c]: [Point3<T>; 3]| {
+        let n = (b - a).cross(c - a);
+        let len = n.magnitude();
+        if len > T::zero() { [f(n.x / len), f(n.y / len), f(n.z / len)] } else { [0.0; 3] }
+    };
+    match encoding {
+        Encoding::Ascii => {
+            writeln!(w, "solid mesh")?;
+            for tri in surface.triangles() {
+                let p = corners(tri);
+                let [nx, ny, nz] = normal(p);
+                writeln!(w, "  facet normal {} {} {}", nx, ny, nz)?;
+                writeln!(w, "    outer loop")?;
+                for v in p {
+                    writeln!(w, "      vertex {} {} {}", f(v.x), f(v.y), f(v.z))?;
+                }
+                writeln!(w, "    endloop")?;
+                writeln!(w, "  endfacet")?;
+            }
+            writeln!(w, "endsolid mesh")?;
+        }
+        Encoding::Binary => {
+            let count = surface.triangles().count();
+            let count = u32::try_from(count)
+                .map_err(|_| IoError::Unsupported(format!("{} triangles in binary STL", count)))?;
+            let mut header = [b' '; 80];
+            header[..15].copy_from_slice(b"mesh-geometry  ");
+            w.write_all(&header)?;
+            w.write_all(&count.to_le_bytes())?;
+            for tri in surface.triangles() {
+                let p = corners(tri);
+                let mut facet = Vec::with_capacity(50);
+                let values = normal(p).into_iter().chain(p.iter().flat_map(|v| [f(v.x), f(v.y), f(v.z)]));
+                values.for_each(|x| facet.extend((x as f32).to_le_bytes()));
+                facet.extend([0, 0]);
+                w.write_all(&facet)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Node table merging bit-identical coordinates.
+#[derive(Default)]
+struct Nodes {
+    index: BTreeMap<[u64; 3], usize>,
+    coords: Vec<[f64; 3]>,
+    faces: Vec<Vec<usize>>,
+}
+
+impl Nodes {
+    fn add(&mut self, c: [f64; 3]) -> usize {
+        // +0.0 and -0.0 are the same point
+        let key = c.map(|x| (x + 0.0).to_bits());
+        let next = self.coords.len();
+        let i = *self.index.entry(key).or_insert(next);
+        if i == next {
+            self.coords.push(c);
+        }
+        i
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tetrahedron() -> Surface<f64> {
+        let points = vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+        ];
+        // nodes in order of first use, as the reader numbers them
+        Surface::new(points, vec![vec![0, 1, 2], vec![1, 0, 3], vec![2, 1, 3], vec![0, 2, 3]])
+    }
+
+    #[test]
+    fn ascii_and_binary_roundtrip() {
+        let tet = tetrahedron();
+        for encoding in [Encoding::Ascii, Encoding::Binary] {
+            let mut out = Vec::new();
+            write_stl(&mut out, &tet, encoding).unwrap();
+            assert_eq!(read_stl::<f64, _>(&out[..]).unwrap(), tet);
+        }
+    }
+
+    #[test]
+    fn binary_starting_with_solid_is_detected() {
+        let mut out = Vec::new();
+        write_stl(&mut out, &tetrahedron(), Encoding::Binary).unwrap();
+        out[..5].copy_from_slice(b"solid");
+        assert_eq!(read_stl::<f64, _>(&out[..]).unwrap(), tetrahedron());
+        out.pop();
+        assert!(matches!(read_stl::<f64, _>(&out[..]), Err(IoError::Parse { .. })));
+    }
+}
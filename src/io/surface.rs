@@ -0,0 +1,113 @@
+//! Polygon surfaces read from and written to STL, OBJ and PLY.
+//!
+//! A [`Surface`] is a plain `Point3` array plus faces as node indices,
+//! counter-clockwise when seen from the front. Faces may have any number of
+//! nodes; [`Surface::triangles`] fans them into triangles for
+//! `triangle_area` or `ray_intersects_triangle`, and [`Surface::to_mesh`]
+//! gives a [`Mesh3`] for `Bvh::from_mesh` and `Bvh::raycast_mesh`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point3;
+//! use mesh_geometry::io::Surface;
+//!
+//! let points = vec![
+//!     Point3::new(0.0_f64, 0.0, 0.0),
+//!     Point3::new(2.0, 0.0, 0.0),
+//!     Point3::new(2.0, 1.0, 0.0),
+//!     Point3::new(0.0, 1.0, 0.0),
+//! ];
+//! let surface = Surface::new(points, vec![vec![0, 1, 2, 3]]);
+//! assert_eq!(surface.triangles().count(), 2);
+//! assert!((surface.area() - 2.0).abs() < 1e-12);
+//! ```
+
+use std::vec::Vec;
+
+use crate::{Float, Point3, triangle_area};
+use crate::mesh::{Cell, Mesh3};
+
+/// Points and polygonal faces of a surface.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Surface<T: Float> {
+    /// Node coordinates
+    pub points: Vec<Point3<T>>,
+    /// Faces as node indices, counter-clockwise seen from the front
+    pub faces: Vec<Vec<usize>>,
+}
+
+impl<T: Float> Surface<T> {
+    /// Surface from points and faces.
+    pub fn new(points: Vec<Point3<T>>, faces: Vec<Vec<usize>>) -> Self {
+        Surface { points, faces }
+    }
+
+    /// Surface cells (triangles, quads, polygons) of `mesh`; volume cells are skipped.
+    pub fn from_mesh(mesh: &Mesh3<T>) -> Self {
+        let faces = mesh
+            .cells
+            .iter()
+            .filter(|c| c.cell_type().dim() == 2)
+            .map(|c| c.nodes().to_vec())
+            .collect();
+        Surface { points: mesh.nodes.clone(), faces }
+    }
+
+    /// Mesh with one triangle, quad or polygon cell per face.
+    pub fn to_mesh(&self) -> Mesh3<T> {
+        let cells = self
+            .faces
+            .iter()
+            .map(|f| match f[..] {
+                [a, b, c] => Cell::Triangle([a, b, c]),
+                [a, b, c, d] => Cell::Quad([a, b, c, d]),
+                _ => Cell::Polygon(f.clone()),
+            })
+            .collect();
+        Mesh3::from_parts(self.points.clone(), cells)
+    }
+
+    /// Fan triangulation of every face, as node indices.
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.faces
+            .iter()
+            .flat_map(|f| (2..f.len()).map(move |k| [f[0], f[k - 1], f[k]]))
+    }
+
+    /// Total area of the fan triangles.
+    pub fn area(&self) -> T {
+        self.triangles().fold(T::zero(), |acc, [a, b, c]| {
+            acc + triangle_area(self.points[a], self.points[b], self.points[c])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec3;
+    use crate::queries::Ray;
+    use crate::utils::bvh::{Bvh, Split};
+
+    #[test]
+    fn mesh_roundtrip_and_raycast() {
+        let points = vec![
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.5, 1.5, 0.0),
+        ];
+        let surface = Surface::new(points, vec![vec![0, 1, 2], vec![0, 2, 4, 3]]);
+        let mesh = surface.to_mesh();
+        assert_eq!(mesh.cells[1], Cell::Quad([0, 2, 4, 3]));
+        assert_eq!(Surface::from_mesh(&mesh), surface);
+
+        let bvh = Bvh::from_mesh(&mesh, Split::Sah);
+        let ray = Ray { origin: Point3::new(0.5, 1.2, 1.0), dir: Vec3::new(0.0, 0.0, -1.0) };
+        let (cell, t) = bvh.raycast_mesh(&mesh, ray).unwrap();
+        assert_eq!(cell, 1);
+        assert!((t - 1.0).abs() < 1e-12);
+    }
+}
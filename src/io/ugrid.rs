@@ -0,0 +1,310 @@
+//! UGRID mesh topologies in NetCDF classic files.
+//!
+//! A UGRID file describes a mesh through a dummy "mesh topology" variable
+//! (`cf_role = "mesh_topology"`) whose attributes name the node coordinate
+//! variables (`mesh_node_x mesh_node_y`) and the `face_node_connectivity`
+//! variable. Faces with fewer nodes than the connectivity's width are padded
+//! with its `_FillValue`, and `start_index` gives 0- or 1-based numbering.
+//! [`Ugrid::from_netcdf`] turns such a topology into a [`Mesh2`] of
+//! triangles, quads and polygons; for layered meshes
+//! (`layer_interface_dimension`) the interface levels are kept and
+//! [`Ugrid::to_mesh3`] extrudes the mesh into wedges, hexahedra and
+//! polyhedra. Only a 1-D interface coordinate (z-levels) is interpreted;
+//! sigma or node-varying coordinates stay available in the [`NcFile`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point2, polygon_area, polygon_centroid};
+//! use mesh_geometry::io::netcdf::Version;
+//! use mesh_geometry::io::ugrid::{read_ugrid, write_ugrid, Ugrid};
+//! use mesh_geometry::mesh::{Cell, Mesh2};
+//!
+//! let mut mesh = Mesh2::new();
+//! for (x, y) in [(0.0_f64, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0), (3.0, 0.5)] {
+//!     mesh.add_node(Point2::new(x, y));
+//! }
+//! mesh.add_cell(Cell::Quad([0, 1, 2, 3]));
+//! mesh.add_cell(Cell::Triangle([1, 4, 2]));
+//!
+//! let mut out = Vec::new();
+//! write_ugrid(&mut out, &Ugrid::new("mesh2d", mesh), Version::Classic).unwrap();
+//! let grid = read_ugrid::<f64, _>(&out[..]).unwrap();
+//! let areas: Vec<f64> = grid.mesh.cell_points().map(|c| polygon_area(c.points())).collect();
+//! assert_eq!(areas, vec![2.0, 0.5]);
+//! assert_eq!(polygon_centroid(grid.mesh.cell_points_of(0).points()), Point2::new(1.0, 0.5));
+//! ```
+
+use std::io::{Read, Write};
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2, Point3};
+use crate::io::netcdf::{NcData, NcFile, Variable, Version};
+use crate::io::{IoError, Result};
+use crate::mesh::{Cell, Mesh2, Mesh3};
+
+/// A 2D (optionally layered) UGRID mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ugrid<T: Float> {
+    /// Name of the mesh topology variable
+    pub name: String,
+    /// Horizontal mesh
+    pub mesh: Mesh2<T>,
+    /// Elevation of each layer interface (positive up), for layered meshes
+    pub layer_interfaces: Option<Vec<T>>,
+}
+
+fn parse_err<V>(msg: impl Into<String>) -> Result<V> {
+    Err(IoError::Parse { line: 0, msg: msg.into() })
+}
+
+/// Text attribute `name` of `v`.
+fn text_attr<'a>(v: &'a Variable, name: &str) -> Option<&'a str> {
+    v.attribute(name).and_then(NcData::as_text)
+}
+
+impl<T: Float> Ugrid<T> {
+    /// Unlayered mesh named `name`.
+    pub fn new(name: &str, mesh: Mesh2<T>) -> Self {
+        Ugrid { name: name.into(), mesh, layer_interfaces: None }
+    }
+
+    /// Mesh topology `name` of `nc`, or its first 2D topology when `None`.
+    pub fn from_netcdf(nc: &NcFile, name: Option<&str>) -> Result<Self> {
+        let is_2d = |v: &&Variable| {
+            text_attr(v, "cf_role") == Some("mesh_topology")
+                && v.attribute("topology_dimension").map(NcData::to_f64) == Some(vec![2.0])
+        };
+        let topo = match name {
+            Some(name) => nc.variables.iter().filter(is_2d).find(|v| v.name == name),
+            None => nc.variables.iter().find(is_2d),
+        };
+        let Some(topo) = topo else {
+            return parse_err("no 2D mesh topology variable");
+        };
+        let var = |attr: &str| -> Result<&Variable> {
+            let Some(name) = text_attr(topo, attr) else {
+                return parse_err(format!("mesh '{}' has no {} attribute", topo.name, attr));
+            };
+            nc.variable(name.trim()).map_or_else(|| parse_err(format!("missing variable '{}'", name)), Ok)
+        };
+
+        // node coordinates
+        let Some(coords) = text_attr(topo, "node_coordinates") else {
+            return parse_err(format!("mesh '{}' has no node_coordinates attribute", topo.name));
+        };
+        let coords: Vec<&str> = coords.split_whitespace().collect();
+        let [x, y, ..] = coords[..] else {
+            return parse_err("node_coordinates needs two variables");
+        };
+        let lookup = |n: &str| nc.variable(n).map_or_else(|| parse_err(format!("missing variable '{}'", n)), Ok);
+        let (x, y) = (lookup(x)?.data.to_f64(), lookup(y)?.data.to_f64());
+        if x.len() != y.len() {
+            return parse_err("node coordinate variables differ in length");
+        }
+        let mut mesh = Mesh2::new();
+        for (&x, &y) in x.iter().zip(&y) {
+            mesh.add_node(Point2::new(T::from(x).unwrap(), T::from(y).unwrap()));
+        }
+
+        // face-node connectivity, possibly transposed, padded and 1-based
+        let conn = var("face_node_connectivity")?;
+        let [d0, d1] = conn.dims[..] else {
+            return parse_err(format!("'{}' must be two-dimensional", conn.name));
+        };
+        let face_dim = text_attr(topo, "face_dimension").and_then(|n| nc.dimension(n.trim()));
+        let transposed = face_dim == Some(d1) && face_dim != Some(d0);
+        let (faces, width) = (nc.dimensions[d0].len, nc.dimensions[d1].len);
+        let (faces, width) = if transposed { (width, faces) } else { (faces, width) };
+        let start = conn.attribute("start_index").map_or(0.0, |s| s.to_f64().first().copied().unwrap_or(0.0));
+        // the NetCDF default fill for `int`
+        let fill = conn.attribute("_FillValue").map_or(-2147483647.0, |f| f.to_f64().first().copied().unwrap_or(-1.0));
+        let values = conn.data.to_f64();
+        for f in 0..faces {
+            let nodes = (0..width)
+                .map(|k| values[if transposed { k * faces + f } else { f * width + k }])
+                .filter(|&v| v != fill && v >= start)
+                .map(|v| match (v - start) as usize {
+                    n if n < mesh.num_nodes() => Ok(n),
+                    n => parse_err(format!("face {} references missing node {}", f, n)),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            mesh.add_cell(match nodes[..] {
+                [a, b, c] => Cell::Triangle([a, b, c]),
+                [a, b, c, d] => Cell::Quad([a, b, c, d]),
+                _ if nodes.len() > 4 => Cell::Polygon(nodes),
+                _ => return parse_err(format!("face {} has fewer than 3 nodes", f)),
+            });
+        }
+
+        // z-level interfaces of a layered mesh
+        let layer_interfaces = match text_attr(topo, "layer_interface_dimension").and_then(|n| nc.dimension(n.trim())) {
+            Some(dim) => {
+                let Some(z) = nc.variables.iter().find(|v| v.dims == [dim]) else {
+                    return parse_err("layered mesh has no interface coordinate variable");
+                };
+                let sign = if text_attr(z, "positive") == Some("down") { -1.0 } else { 1.0 };
+                Some(z.data.to_f64().iter().map(|&v| T::from(sign * v).unwrap()).collect())
+            }
+            None => None,
+        };
+        Ok(Ugrid { name: topo.name.clone(), mesh, layer_interfaces })
+    }
+
+    /// NetCDF file describing this mesh, ready for data variables to be added.
+    pub fn to_netcdf(&self, version: Version) -> NcFile {
+        let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+        let name = &self.name;
+        let width = self.mesh.cells.iter().map(|c| c.nodes().len()).max().unwrap_or(3);
+        let mut nc = NcFile::new(version);
+        nc.set_attribute("Conventions", "CF-1.8 UGRID-1.0".into());
+        let node = nc.add_dimension(&format!("{}_nNodes", name), self.mesh.num_nodes());
+        let face = nc.add_dimension(&format!("{}_nFaces", name), self.mesh.num_cells());
+        let max = nc.add_dimension(&format!("{}_nMax_face_nodes", name), width);
+
+        let topo = nc.add_variable(name, &[], NcData::Int(vec![0]));
+        topo.set_attribute("cf_role", "mesh_topology".into())
+            .set_attribute("long_name", "Topology data of 2D mesh".into())
+            .set_attribute("topology_dimension", NcData::Int(vec![2]))
+            .set_attribute("node_coordinates", format!("{0}_node_x {0}_node_y", name).as_str().into())
+            .set_attribute("face_node_connectivity", format!("{}_face_nodes", name).as_str().into())
+            .set_attribute("face_dimension", format!("{}_nFaces", name).as_str().into());
+        if self.layer_interfaces.is_some() {
+            topo.set_attribute("layer_dimension", format!("{}_nLayers", name).as_str().into())
+                .set_attribute("layer_interface_dimension", format!("{}_nInterfaces", name).as_str().into());
+        }
+
+        for (axis, long_name) in [("x", "x-coordinate of mesh nodes"), ("y", "y-coordinate of mesh nodes")] {
+            let values = self.mesh.nodes.iter().map(|p| f(if axis == "x" { p.x } else { p.y })).collect();
+            nc.add_variable(&format!("{}_node_{}", name, axis), &[node], NcData::Double(values))
+                .set_attribute("long_name", long_name.into());
+        }
+        let mut conn = Vec::with_capacity(self.mesh.num_cells() * width);
+        for cell in &self.mesh.cells {
+            let nodes = cell.nodes();
+            conn.extend(nodes.iter().map(|&n| n as i32));
+            conn.extend(core::iter::repeat_n(-1, width - nodes.len()));
+        }
+        nc.add_variable(&format!("{}_face_nodes", name), &[face, max], NcData::Int(conn))
+            .set_attribute("cf_role", "face_node_connectivity".into())
+            .set_attribute("start_index", NcData::Int(vec![0]))
+            .set_attribute("_FillValue", NcData::Int(vec![-1]));
+
+        if let Some(z) = &self.layer_interfaces {
+            let z: Vec<f64> = z.iter().map(|&v| f(v)).collect();
+            let layers = nc.add_dimension(&format!("{}_nLayers", name), z.len().saturating_sub(1));
+            let interfaces = nc.add_dimension(&format!("{}_nInterfaces", name), z.len());
+            let mid = z.windows(2).map(|w| 0.5 * (w[0] + w[1])).collect();
+            nc.add_variable(&format!("{}_layer_z", name), &[layers], NcData::Double(mid))
+                .set_attribute("positive", "up".into());
+            nc.add_variable(&format!("{}_interface_z", name), &[interfaces], NcData::Double(z))
+                .set_attribute("positive", "up".into());
+        }
+        nc
+    }
+
+    /// Extrude a layered mesh: one cell per face and layer, layer by layer
+    /// (cell `layer * faces + face`), with nodes `interface * nodes + node`.
+    /// `None` for unlayered meshes.
+    pub fn to_mesh3(&self) -> Option<Mesh3<T>> {
+        let z = self.layer_interfaces.as_ref()?;
+        let np = self.mesh.num_nodes();
+        let mut mesh = Mesh3::new();
+        for &zk in z {
+            for p in &self.mesh.nodes {
+                mesh.add_node(Point3::new(p.x, p.y, zk));
+            }
+        }
+        for k in 0..z.len().saturating_sub(1) {
+            let (lo, hi) = if z[k] <= z[k + 1] { (k * np, (k + 1) * np) } else { ((k + 1) * np, k * np) };
+            for cell in &self.mesh.cells {
+                mesh.add_cell(match *cell {
+                    // the wedge's first triangle faces down, so it runs clockwise from above
+                    Cell::Triangle([a, b, c]) => Cell::Wedge([lo + a, lo + c, lo + b, hi + a, hi + c, hi + b]),
+                    Cell::Quad(q) => {
+                        Cell::Hexahedron([lo + q[0], lo + q[1], lo + q[2], lo + q[3], hi + q[0], hi + q[1], hi + q[2], hi + q[3]])
+                    }
+                    ref c => {
+                        let n = c.nodes();
+                        let mut faces = vec![n.iter().rev().map(|&i| lo + i).collect(), n.iter().map(|&i| hi + i).collect()];
+                        for (i, &a) in n.iter().enumerate() {
+                            let b = n[(i + 1) % n.len()];
+                            faces.push(vec![lo + a, lo + b, hi + b, hi + a]);
+                        }
+                        Cell::polyhedron(&faces)
+                    }
+                });
+            }
+        }
+        Some(mesh)
+    }
+}
+
+/// Read the first 2D mesh topology of a NetCDF classic file.
+pub fn read_ugrid<T: Float, R: Read>(reader: R) -> Result<Ugrid<T>> {
+    Ugrid::from_netcdf(&NcFile::read(reader)?, None)
+}
+
+/// Write `grid` alone as a NetCDF classic file. A mesh without nodes or
+/// cells has zero-length dimensions, which NetCDF classic cannot hold.
+pub fn write_ugrid<T: Float, W: Write>(w: W, grid: &Ugrid<T>, version: Version) -> Result<()> {
+    grid.to_netcdf(version).write(w)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::FvGeometry;
+
+    #[test]
+    fn one_based_transposed_connectivity() {
+        // as written by some models: (nMaxNodes, nFaces), 1-based, fill 0
+        let mut nc = NcFile::new(Version::Classic);
+        let node = nc.add_dimension("nNodes", 5);
+        let max = nc.add_dimension("nMaxNodes", 4);
+        let face = nc.add_dimension("nFaces", 2);
+        nc.add_variable("mesh", &[], NcData::Int(vec![0]))
+            .set_attribute("cf_role", "mesh_topology".into())
+            .set_attribute("topology_dimension", NcData::Int(vec![2]))
+            .set_attribute("node_coordinates", "lon lat".into())
+            .set_attribute("face_node_connectivity", "faces".into())
+            .set_attribute("face_dimension", "nFaces".into());
+        nc.add_variable("lon", &[node], NcData::Float(vec![0.0, 1.0, 1.0, 0.0, 2.0]));
+        nc.add_variable("lat", &[node], NcData::Float(vec![0.0, 0.0, 1.0, 1.0, 0.0]));
+        nc.add_variable("faces", &[max, face], NcData::Int(vec![1, 2, 2, 5, 3, 3, 4, 0]))
+            .set_attribute("start_index", NcData::Int(vec![1]))
+            .set_attribute("_FillValue", NcData::Int(vec![0]));
+
+        let grid = Ugrid::<f64>::from_netcdf(&nc, Some("mesh")).unwrap();
+        assert_eq!(grid.mesh.cells, vec![Cell::Quad([0, 1, 2, 3]), Cell::Triangle([1, 4, 2])]);
+        assert!(grid.to_mesh3().is_none());
+    }
+
+    #[test]
+    fn layered_roundtrip_and_extrusion() {
+        let mut mesh = Mesh2::new();
+        for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (2.0, 0.0), (2.5, 1.0), (1.5, 1.5)] {
+            mesh.add_node(Point2::new(x, y));
+        }
+        mesh.add_cell(Cell::Quad([0, 1, 2, 3]));
+        mesh.add_cell(Cell::Triangle([1, 4, 2]));
+        mesh.add_cell(Cell::Polygon(vec![2, 4, 5, 6, 3]));
+        let grid = Ugrid { name: "mesh2d".into(), mesh, layer_interfaces: Some(vec![0.0, -2.0, -5.0]) };
+
+        let mut out = Vec::new();
+        write_ugrid(&mut out, &grid, Version::Offset64).unwrap();
+        let back = read_ugrid::<f64, _>(&out[..]).unwrap();
+        assert_eq!(back, grid);
+
+        let solid = back.to_mesh3().unwrap();
+        assert_eq!(solid.num_cells(), 6);
+        let geo = FvGeometry::new(&solid).unwrap();
+        let areas = [1.0, 0.5, 1.375];
+        for (c, &v) in geo.cell_volumes.iter().enumerate() {
+            let thickness = if c < 3 { 2.0 } else { 3.0 };
+            assert!(v > 0.0);
+            assert!((v - thickness * areas[c % 3]).abs() < 1e-12, "cell {}", c);
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! VTK legacy (`.vtk`) and XML unstructured-grid (`.vtu`) writers.
+//!
+//! [`VtkWriter`] writes a [`Mesh3`] with any number of point and cell data
+//! arrays, scalar or 3-vector, e.g. `hexahedron_volume` results, quality
+//! metrics or `face_normal` vectors. Write a `Mesh2` through
+//! `mesh.to_mesh3(0.0)`.
+//!
+//! [`Encoding::Ascii`] writes plain text. [`Encoding::Binary`] writes
+//! big-endian raw binary in legacy files and inline base64 (with a `UInt64`
+//! byte-count header) in `.vtu` files. Values are written as `Float64`.
+//! Polyhedra are only representable in `.vtu`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, hexahedron_volume};
+//! use mesh_geometry::mesh::{Cell, CellPoints, Mesh3};
+//! use mesh_geometry::io::vtk::{Encoding, VtkWriter};
+//!
+//! let mut mesh = Mesh3::new();
+//! for k in 0..2 {
+//!     for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+//!         mesh.add_node(Point3::new(x, y, k as f64));
+//!     }
+//! }
+//! mesh.add_cell(Cell::Hexahedron([0, 1, 2, 3, 4, 5, 6, 7]));
+//! let volumes: Vec<f64> = mesh
+//!     .cell_points()
+//!     .map(|c| match c {
+//!         CellPoints::Hexahedron(v) => hexahedron_volume(v),
+//!         _ => 0.0,
+//!     })
+//!     .collect();
+//!
+//! let mut out = Vec::new();
+//! VtkWriter::new(&mesh)
+//!     .cell_scalars("volume", volumes)
+//!     .encoding(Encoding::Binary)
+//!     .write_vtu(&mut out)
+//!     .unwrap();
+//! assert!(String::from_utf8(out).unwrap().contains("Name=\"volume\""));
+//! ```
+
+use std::io::Write;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Vec3};
+pub use crate::io::Encoding;
+use crate::io::{base64_encode, IoError, Result};
+use crate::mesh::{Cell, Mesh3};
+
+/// Values of one data array, one entry per node or per cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldData<T: Float> {
+    /// One value per entry
+    Scalars(Vec<T>),
+    /// One 3-vector per entry
+    Vectors(Vec<Vec3<T>>),
+}
+
+/// A named data array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field<T: Float> {
+    /// Array name shown in ParaView
+    pub name: String,
+    /// Array values
+    pub data: FieldData<T>,
+}
+
+impl<T: Float> Field<T> {
+    fn len(&self) -> usize {
+        match &self.data {
+            FieldData::Scalars(v) => v.len(),
+            FieldData::Vectors(v) => v.len(),
+        }
+    }
+
+    fn components(&self) -> usize {
+        match self.data {
+            FieldData::Scalars(_) => 1,
+            FieldData::Vectors(_) => 3,
+        }
+    }
+
+    fn values(&self) -> Vec<f64> {
+        let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+        match &self.data {
+            FieldData::Scalars(v) => v.iter().map(|&x| f(x)).collect(),
+            FieldData::Vectors(v) => v.iter().flat_map(|p| [f(p.x), f(p.y), f(p.z)]).collect(),
+        }
+    }
+}
+
+/// VTK cell type code.
+fn vtk_type(cell: &Cell) -> u8 {
+    match cell {
+        Cell::Triangle(_) => 5,
+        Cell::Polygon(_) => 7,
+        Cell::Quad(_) => 9,
+        Cell::Tetrahedron(_) => 10,
+        Cell::Hexahedron(_) => 12,
+        Cell::Wedge(_) => 13,
+        Cell::Pyramid(_) => 14,
+        Cell::Polyhedron { .. } => 42,
+    }
+}
+
+/// Writer for one mesh and its data arrays.
+#[derive(Debug, Clone)]
+pub struct VtkWriter<'a, T: Float> {
+    mesh: &'a Mesh3<T>,
+    point_data: Vec<Field<T>>,
+    cell_data: Vec<Field<T>>,
+    encoding: Encoding,
+}
+
+impl<'a, T: Float> VtkWriter<'a, T> {
+    /// Writer for `mesh` with no data arrays, ASCII encoded.
+    pub fn new(mesh: &'a Mesh3<T>) -> Self {
+        VtkWriter { mesh, point_data: Vec::new(), cell_data: Vec::new(), encoding: Encoding::Ascii }
+    }
+
+    /// Select the encoding.
+    pub fn encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Add a per-node scalar array. Panics if its length is not the node count.
+    pub fn point_scalars(self, name: &str, values: Vec<T>) -> Self {
+        self.point_field(name, FieldData::Scalars(values))
+    }
+
+    /// Add a per-node vector array. Panics if its length is not the node count.
+    pub fn point_vectors(self, name: &str, values: Vec<Vec3<T>>) -> Self {
+        self.point_field(name, FieldData::Vectors(values))
+    }
+
+    /// Add a per-cell scalar array. Panics if its length is not the cell count.
+    pub fn cell_scalars(self, name: &str, values: Vec<T>) -> Self {
+        self.cell_field(name, FieldData::Scalars(values))
+    }
+
+    /// Add a per-cell vector array. Panics if its length is not the cell count.
+    pub fn cell_vectors(self, name: &str, values: Vec<Vec3<T>>) -> Self {
+        self.cell_field(name, FieldData::Vectors(values))
+    }
+
+    fn point_field(mut self, name: &str, data: FieldData<T>) -> Self {
+        let field = Field { name: name.into(), data };
+        assert_eq!(field.len(), self.mesh.num_nodes(), "point array '{}' has the wrong length", name);
+        self.point_data.push(field);
+        self
+    }
+
+    fn cell_field(mut self, name: &str, data: FieldData<T>) -> Self {
+        let field = Field { name: name.into(), data };
+        assert_eq!(field.len(), self.mesh.num_cells(), "cell array '{}' has the wrong length", name);
+        self.cell_data.push(field);
+        self
+    }
+
+    fn coords(&self) -> Vec<f64> {
+        let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+        self.mesh.nodes.iter().flat_map(|p| [f(p.x), f(p.y), f(p.z)]).collect()
+    }
+
+    /// Write a legacy `.vtk` file.
+    pub fn write_legacy<W: Write>(&self, mut w: W) -> Result<()> {
+        let mesh = self.mesh;
+        if mesh.cells.iter().any(|c| matches!(c, Cell::Polyhedron { .. })) {
+            return Err(IoError::Unsupported("polyhedra in legacy VTK files; write .vtu instead".into()));
+        }
+        let binary = self.encoding == Encoding::Binary;
+        writeln!(w, "# vtk DataFile Version 3.0")?;
+        writeln!(w, "mesh-geometry")?;
+        writeln!(w, "{}", if binary { "BINARY" } else { "ASCII" })?;
+        writeln!(w, "DATASET UNSTRUCTURED_GRID")?;
+
+        writeln!(w, "POINTS {} double", mesh.num_nodes())?;
+        legacy_f64(&mut w, &self.coords(), 3, binary)?;
+
+        let mut conn: Vec<i32> = Vec::new();
+        for cell in &mesh.cells {
+            conn.push(cell.nodes().len() as i32);
+            for &n in cell.nodes() {
+                conn.push(i32::try_from(n).map_err(|_| IoError::Unsupported("node index beyond i32 range".into()))?);
+            }
+        }
+        writeln!(w, "CELLS {} {}", mesh.num_cells(), conn.len())?;
+        if binary {
+            conn.iter().try_for_each(|v| w.write_all(&v.to_be_bytes()))?;
+            writeln!(w)?;
+        } else {
+            let mut rest = &conn[..];
+            while let Some((&n, tail)) = rest.split_first() {
+                let (ids, tail) = tail.split_at(n as usize);
+                let ids: Vec<String> = ids.iter().map(|i| i.to_string()).collect();
+                writeln!(w, "{} {}", n, ids.join(" "))?;
+                rest = tail;
+            }
+        }
+        writeln!(w, "CELL_TYPES {}", mesh.num_cells())?;
+        for cell in &mesh.cells {
+            if binary {
+                w.write_all(&(vtk_type(cell) as i32).to_be_bytes())?;
+            } else {
+                writeln!(w, "{}", vtk_type(cell))?;
+            }
+        }
+        if binary {
+            writeln!(w)?;
+        }
+
+        for (section, count, fields) in
+            [("CELL_DATA", mesh.num_cells(), &self.cell_data), ("POINT_DATA", mesh.num_nodes(), &self.point_data)]
+        {
+            if fields.is_empty() {
+                continue;
+            }
+            writeln!(w, "{} {}", section, count)?;
+            for field in fields {
+                let name = field.name.replace(char::is_whitespace, "_");
+                match field.data {
+                    FieldData::Scalars(_) => writeln!(w, "SCALARS {} double 1\nLOOKUP_TABLE default", name)?,
+                    FieldData::Vectors(_) => writeln!(w, "VECTORS {} double", name)?,
+                }
+                legacy_f64(&mut w, &field.values(), field.components(), binary)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write an XML `.vtu` file.
+    pub fn write_vtu<W: Write>(&self, mut w: W) -> Result<()> {
+        let mesh = self.mesh;
+        let enc = self.encoding;
+        writeln!(w, "<?xml version=\"1.0\"?>")?;
+        writeln!(
+            w,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt64\">"
+        )?;
+        writeln!(w, "<UnstructuredGrid>")?;
+        writeln!(w, "<Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">", mesh.num_nodes(), mesh.num_cells())?;
+
+        for (tag, fields) in [("PointData", &self.point_data), ("CellData", &self.cell_data)] {
+            writeln!(w, "<{}>", tag)?;
+            for field in fields {
+                vtu_array(&mut w, Some(&field.name), field.components(), Raw::F64(field.values()), enc)?;
+            }
+            writeln!(w, "</{}>", tag)?;
+        }
+
+        writeln!(w, "<Points>")?;
+        vtu_array(&mut w, None, 3, Raw::F64(self.coords()), enc)?;
+        writeln!(w, "</Points>")?;
+
+        let mut conn = Vec::new();
+        let mut offsets = Vec::new();
+        let mut faces = Vec::new();
+        let mut face_offsets = Vec::new();
+        for cell in &mesh.cells {
+            conn.extend(cell.nodes().iter().map(|&n| n as i64));
+            offsets.push(conn.len() as i64);
+            if let Cell::Polyhedron { .. } = cell {
+                let cell_faces = cell.faces();
+                faces.push(cell_faces.len() as i64);
+                for f in cell_faces {
+                    faces.push(f.len() as i64);
+                    faces.extend(f.iter().map(|&n| n as i64));
+                }
+                face_offsets.push(faces.len() as i64);
+            } else {
+                face_offsets.push(-1);
+            }
+        }
+        writeln!(w, "<Cells>")?;
+        vtu_array(&mut w, Some("connectivity"), 1, Raw::I64(conn), enc)?;
+        vtu_array(&mut w, Some("offsets"), 1, Raw::I64(offsets), enc)?;
+        vtu_array(&mut w, Some("types"), 1, Raw::U8(mesh.cells.iter().map(vtk_type).collect()), enc)?;
+        if !faces.is_empty() {
+            vtu_array(&mut w, Some("faces"), 1, Raw::I64(faces), enc)?;
+            vtu_array(&mut w, Some("faceoffsets"), 1, Raw::I64(face_offsets), enc)?;
+        }
+        writeln!(w, "</Cells>")?;
+        writeln!(w, "</Piece>")?;
+        writeln!(w, "</UnstructuredGrid>")?;
+        writeln!(w, "</VTKFile>")?;
+        Ok(())
+    }
+}
+
+/// Legacy float block: `per_line` values per text line, or big-endian binary.
+fn legacy_f64<W: Write>(w: &mut W, values: &[f64], per_line: usize, binary: bool) -> Result<()> {
+    if binary {
+        values.iter().try_for_each(|v| w.write_all(&v.to_be_bytes()))?;
+        writeln!(w)?;
+    } else {
+        for row in values.chunks(per_line) {
+            let row: Vec<String> = row.iter().map(|v| v.to_string()).collect();
+            writeln!(w, "{}", row.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Typed payload of a `.vtu` data array.
+enum Raw {
+    F64(Vec<f64>),
+    I64(Vec<i64>),
+    U8(Vec<u8>),
+}
+
+fn vtu_array<W: Write>(w: &mut W, name: Option<&str>, components: usize, raw: Raw, enc: Encoding) -> Result<()> {
+    let (ty, text, bytes): (&str, Vec<String>, Vec<u8>) = match &raw {
+        Raw::F64(v) => ("Float64", v.iter().map(|x| x.to_string()).collect(), v.iter().flat_map(|x| x.to_le_bytes()).collect()),
+        Raw::I64(v) => ("Int64", v.iter().map(|x| x.to_string()).collect(), v.iter().flat_map(|x| x.to_le_bytes()).collect()),
+        Raw::U8(v) => ("UInt8", v.iter().map(|x| x.to_string()).collect(), v.clone()),
+    };
+    write!(w, "<DataArray type=\"{}\"", ty)?;
+    if let Some(name) = name {
+        write!(w, " Name=\"{}\"", xml_escape(name))?;
+    }
+    if components > 1 {
+        write!(w, " NumberOfComponents=\"{}\"", components)?;
+    }
+    match enc {
+        Encoding::Ascii => {
+            writeln!(w, " format=\"ascii\">")?;
+            writeln!(w, "{}", text.join(" "))?;
+        }
+        Encoding::Binary => {
+            writeln!(w, " format=\"binary\">")?;
+            let mut block = (bytes.len() as u64).to_le_bytes().to_vec();
+            block.extend_from_slice(&bytes);
+            writeln!(w, "{}", base64_encode(&block))?;
+        }
+    }
+    writeln!(w, "</DataArray>")?;
+    Ok(())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Mesh3;
+    use crate::metrics::face_normal;
+    use crate::Point3;
+
+    fn two_cells() -> Mesh3<f64> {
+        let mut mesh = Mesh3::new();
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)] {
+            mesh.add_node(Point3::new(x, y, z));
+        }
+        mesh.add_cell(Cell::Quad([0, 1, 2, 3]));
+        mesh.add_cell(Cell::Tetrahedron([0, 1, 3, 4]));
+        mesh
+    }
+
+    #[test]
+    fn legacy_ascii_layout() {
+        let mesh = two_cells();
+        let n = face_normal(&[mesh.nodes[0], mesh.nodes[1], mesh.nodes[2], mesh.nodes[3]]);
+        let mut out = Vec::new();
+        VtkWriter::new(&mesh)
+            .cell_vectors("normal", vec![n, Vec3::new(0.0, 0.0, 0.0)])
+            .point_scalars("depth", vec![1.0, 2.0, 3.0, 4.0, 5.5])
+            .write_legacy(&mut out)
+            .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("POINTS 5 double\n0 0 0\n1 0 0\n"));
+        assert!(text.contains("CELLS 2 10\n4 0 1 2 3\n4 0 1 3 4\n"));
+        assert!(text.contains("CELL_TYPES 2\n9\n10\n"));
+        assert!(text.contains("CELL_DATA 2\nVECTORS normal double\n0 0 2\n0 0 0\n"));
+        assert!(text.contains("POINT_DATA 5\nSCALARS depth double 1\nLOOKUP_TABLE default\n1\n2\n3\n4\n5.5\n"));
+    }
+
+    #[test]
+    fn vtu_binary_block_and_polyhedra() {
+        let mut mesh = two_cells();
+        mesh.add_cell(Cell::polyhedron(&[vec![0, 1, 4], vec![1, 3, 4], vec![3, 0, 4], vec![0, 3, 1]]));
+        let mut out = Vec::new();
+        VtkWriter::new(&mesh).encoding(Encoding::Binary).write_vtu(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        // types: 9, 10, 42 as UInt8 behind an 8-byte length header
+        let types = base64_encode(&[3, 0, 0, 0, 0, 0, 0, 0, 9, 10, 42]);
+        assert!(text.contains(&format!("Name=\"types\" format=\"binary\">\n{}\n", types)));
+        assert!(text.contains("Name=\"faceoffsets\""));
+        assert!(VtkWriter::new(&mesh).write_legacy(Vec::new()).is_err());
+    }
+}
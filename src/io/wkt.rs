@@ -0,0 +1,234 @@
+//! Well-known text (WKT) geometries.
+//!
+//! [`parse_wkt`] reads `POINT`, `LINESTRING`, `POLYGON` and their `MULTI`
+//! variants, case-insensitively, with optional `Z`/`M`/`ZM` tags (extra
+//! ordinates are dropped) and `EMPTY` bodies. An `SRID=...;` prefix (EWKT)
+//! is skipped. [`format_wkt`] writes 2D WKT with closed rings.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::io::wkt::{format_wkt, parse_wkt};
+//!
+//! let domain = parse_wkt::<f64>("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (4 4, 4 6, 6 6, 6 4, 4 4))").unwrap();
+//! let poly = &domain.polygons()[0];
+//! assert_eq!(poly.exterior.len(), 4);
+//! assert_eq!(poly.area(), 96.0);
+//! assert!(poly.contains(Point2::new(2.0, 8.0)));
+//! assert_eq!(
+//!     format_wkt(&domain),
+//!     "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (4 4, 4 6, 6 6, 6 4, 4 4))"
+//! );
+//! ```
+
+use std::string::String;
+use std::vec::Vec;
+
+use crate::{Float, Point2};
+use crate::io::geometry::open_ring;
+use crate::io::{Geometry, IoError, Polygon, Result};
+
+/// Parse one WKT geometry.
+pub fn parse_wkt<T: Float>(text: &str) -> Result<Geometry<T>> {
+    let text = match text.trim_start().split_once(';') {
+        Some((srid, rest)) if srid.to_ascii_uppercase().starts_with("SRID=") => rest,
+        _ => text,
+    };
+    let mut p = Parser { text, pos: 0 };
+    let kind = p.word()?.to_ascii_uppercase();
+    let mut tag = p.peek_word().map(str::to_ascii_uppercase);
+    if matches!(tag.as_deref(), Some("Z" | "M" | "ZM")) {
+        p.word()?;
+        tag = p.peek_word().map(str::to_ascii_uppercase);
+    }
+    let empty = tag.as_deref() == Some("EMPTY");
+    if empty {
+        p.word()?;
+    }
+    let geometry = match kind.as_str() {
+        "POINT" if empty => return Err(IoError::Unsupported("POINT EMPTY".into())),
+        "POINT" => {
+            p.expect('(')?;
+            let pt = p.coord()?;
+            p.expect(')')?;
+            Geometry::Point(pt)
+        }
+        "LINESTRING" => Geometry::LineString(if empty { Vec::new() } else { p.coords()? }),
+        "POLYGON" => Geometry::Polygon(if empty { Polygon { exterior: Vec::new(), holes: Vec::new() } } else { p.polygon()? }),
+        "MULTIPOINT" => Geometry::MultiPoint(if empty {
+            Vec::new()
+        } else {
+            // both `MULTIPOINT (1 2, 3 4)` and `MULTIPOINT ((1 2), (3 4))`
+            p.list(|p| {
+                if p.eat('(') {
+                    let pt = p.coord()?;
+                    p.expect(')')?;
+                    Ok(pt)
+                } else {
+                    p.coord()
+                }
+            })?
+        }),
+        "MULTILINESTRING" => Geometry::MultiLineString(if empty { Vec::new() } else { p.list(Parser::coords)? }),
+        "MULTIPOLYGON" => Geometry::MultiPolygon(if empty { Vec::new() } else { p.list(Parser::polygon)? }),
+        "GEOMETRYCOLLECTION" => return Err(IoError::Unsupported("WKT GEOMETRYCOLLECTION".into())),
+        _ => return p.err(format!("unknown geometry type '{}'", kind)),
+    };
+    p.skip_space();
+    if p.pos < p.text.len() {
+        return p.err("unexpected text after geometry");
+    }
+    Ok(geometry)
+}
+
+/// Format a geometry as 2D WKT.
+pub fn format_wkt<T: Float>(geometry: &Geometry<T>) -> String {
+    let f = |x: T| x.to_f64().unwrap_or(f64::NAN);
+    let coord = |p: &Point2<T>| format!("{} {}", f(p.x), f(p.y));
+    let coords = |pts: &[Point2<T>]| pts.iter().map(coord).collect::<Vec<_>>().join(", ");
+    let ring = |pts: &[Point2<T>]| match pts.first() {
+        Some(first) => format!("({}, {})", coords(pts), coord(first)),
+        None => "()".into(),
+    };
+    let polygon = |p: &Polygon<T>| {
+        let rings: Vec<String> = core::iter::once(&p.exterior).chain(&p.holes).map(|r| ring(r)).collect();
+        format!("({})", rings.join(", "))
+    };
+    let list = |items: Vec<String>| format!("({})", items.join(", "));
+    match geometry {
+        Geometry::Point(p) => format!("POINT ({})", coord(p)),
+        Geometry::LineString(l) if l.is_empty() => "LINESTRING EMPTY".into(),
+        Geometry::LineString(l) => format!("LINESTRING ({})", coords(l)),
+        Geometry::Polygon(p) if p.exterior.is_empty() => "POLYGON EMPTY".into(),
+        Geometry::Polygon(p) => format!("POLYGON {}", polygon(p)),
+        Geometry::MultiPoint(m) if m.is_empty() => "MULTIPOINT EMPTY".into(),
+        Geometry::MultiPoint(m) => format!("MULTIPOINT {}", list(m.iter().map(|p| format!("({})", coord(p))).collect())),
+        Geometry::MultiLineString(m) if m.is_empty() => "MULTILINESTRING EMPTY".into(),
+        Geometry::MultiLineString(m) => {
+            format!("MULTILINESTRING {}", list(m.iter().map(|l| format!("({})", coords(l))).collect()))
+        }
+        Geometry::MultiPolygon(m) if m.is_empty() => "MULTIPOLYGON EMPTY".into(),
+        Geometry::MultiPolygon(m) => format!("MULTIPOLYGON {}", list(m.iter().map(polygon).collect())),
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn err<V>(&self, msg: impl Into<String>) -> Result<V> {
+        Err(IoError::Parse { line: 1, msg: format!("{} at column {}", msg.into(), self.pos + 1) })
+    }
+
+    fn skip_space(&mut self) {
+        let rest = &self.text[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek_word(&mut self) -> Option<&'a str> {
+        self.skip_space();
+        let rest = &self.text[self.pos..];
+        let end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        (end > 0).then(|| &rest[..end])
+    }
+
+    fn word(&mut self) -> Result<&'a str> {
+        match self.peek_word() {
+            Some(w) => {
+                self.pos += w.len();
+                Ok(w)
+            }
+            None => self.err("expected a geometry type"),
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_space();
+        if self.text[self.pos..].starts_with(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) { Ok(()) } else { self.err(format!("expected '{}'", c)) }
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        self.skip_space();
+        let rest = &self.text[self.pos..];
+        let end = rest.find(|c: char| c.is_whitespace() || c == ',' || c == ')').unwrap_or(rest.len());
+        match rest[..end].parse() {
+            Ok(v) if end > 0 => {
+                self.pos += end;
+                Ok(v)
+            }
+            _ => self.err("expected a number"),
+        }
+    }
+
+    /// `x y [z [m]]`
+    fn coord<T: Float>(&mut self) -> Result<Point2<T>> {
+        let x = self.number()?;
+        let y = self.number()?;
+        loop {
+            self.skip_space();
+            if self.text[self.pos..].starts_with([',', ')']) || self.pos == self.text.len() {
+                break;
+            }
+            self.number()?;
+        }
+        Ok(Point2::new(T::from(x).unwrap(), T::from(y).unwrap()))
+    }
+
+    /// `( item, item, ... )`
+    fn list<V>(&mut self, mut item: impl FnMut(&mut Self) -> Result<V>) -> Result<Vec<V>> {
+        self.expect('(')?;
+        let mut items = vec![item(self)?];
+        while self.eat(',') {
+            items.push(item(self)?);
+        }
+        self.expect(')')?;
+        Ok(items)
+    }
+
+    fn coords<T: Float>(&mut self) -> Result<Vec<Point2<T>>> {
+        self.list(Parser::coord)
+    }
+
+    fn polygon<T: Float>(&mut self) -> Result<Polygon<T>> {
+        let mut rings = self.list(Parser::coords)?.into_iter().map(open_ring);
+        let exterior = rings.next().unwrap_or_default();
+        Ok(Polygon { exterior, holes: rings.collect() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_variants_roundtrip() {
+        let cases = [
+            "MULTIPOINT ((1 2), (3.5 -4))",
+            "MULTILINESTRING ((0 0, 1 1, 2 0), (5 5, 6 6))",
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((2 2, 3 2, 3 3, 2 2), (2.2 2.1, 2.8 2.7, 2.8 2.1, 2.2 2.1)))",
+            "LINESTRING EMPTY",
+            "POINT (7 8)",
+        ];
+        for wkt in cases {
+            assert_eq!(format_wkt(&parse_wkt::<f64>(wkt).unwrap()), wkt);
+        }
+        let loose = parse_wkt::<f64>("SRID=4326;multipoint z (1 2 9, 3 4 9)").unwrap();
+        assert_eq!(loose, Geometry::MultiPoint(vec![Point2::new(1.0, 2.0), Point2::new(3.0, 4.0)]));
+        match parse_wkt::<f64>("LINESTRING (0 0, 1 x)") {
+            Err(IoError::Parse { msg, .. }) => assert!(msg.ends_with("column 20"), "{}", msg),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
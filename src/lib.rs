@@ -7,9 +7,12 @@
 //!
 //! ## Features
 //! - Core types: `Point2`, `Point3`, `Vec2`, `Vec3` with arithmetic, dot/cross, and conversion utilities.
-//! - Cell metrics: triangle/quad area, centroids, tetrahedron/hexahedron volume, face normals, projected area.
+//! - Cell metrics: triangle/quad area, centroids, tetrahedron/hexahedron volume, face normals, projected area, quality metrics, finite-volume face non-orthogonality/skewness.
 //! - Geometry queries: point-in-polygon, ray-triangle intersection, point-to-cell distance.
+//! - Mesh containers: `Mesh2`/`Mesh3` with mixed cell connectivity.
+//! - File I/O (`std`): VTK legacy and `.vtu` writers, Gmsh `.msh` reader, ADCIRC `fort.14` and SMS `.2dm` read/write, OpenFOAM `polyMesh` reader, STL/OBJ/PLY surfaces, UGRID meshes in NetCDF classic files, GeoJSON and WKT geometries, ESRI Shapefile reader, ESRI ASCII and GeoTIFF rasters sampled onto meshes.
 //! - Advanced utilities: Jacobians, AABB, 3D affine transforms.
+//! - Serde (`serde`): compact `[x, y, z]` arrays for points, vectors, transforms, boxes, rays and Jacobians, with a named-field form on request.
 //! - `no_std` compatible (default: `std` enabled).
 //! - Comprehensive documentation and examples.
 //!
@@ -35,6 +38,8 @@
 //! ## License
 //! MIT
 
+extern crate alloc;
+
 /// Prelude for ergonomic imports (core types, traits, and utilities).
 pub mod prelude;
 pub use prelude::*;
@@ -53,11 +58,12 @@ pub mod vec;
 pub mod metrics;
 pub use metrics::{
     triangle_area, quad_area,
-    triangle_centroid, quad_centroid, tetrahedron_centroid, hexahedron_centroid,
+    triangle_centroid, quad_centroid, tetrahedron_centroid, hexahedron_centroid, face_centroid,
     tetrahedron_volume, hexahedron_volume,
     face_normal, projected_area,
-    polygon_area, polygon_centroid,
+    polygon_area, polygon_signed_area, polygon_centroid,
     prism_volume, prism_centroid,
+    polyhedron_volume, polyhedron_centroid,
 };
 
 /// Geometry queries: point-in-polygon, ray-triangle, distance, etc.
@@ -66,6 +72,17 @@ pub mod queries;
 /// 3D affine transforms.
 pub mod transforms;
 
+/// Unstructured mesh containers.
+pub mod mesh;
+
+/// Mesh file readers and writers.
+#[cfg(feature = "std")]
+pub mod io;
+
 /// Advanced utilities: Jacobians, AABB, etc.
 pub mod utils;
 pub use utils::{jacobian, aabb};
+
+/// Serde support for the geometry types.
+#[cfg(feature = "serde")]
+pub mod serialization;
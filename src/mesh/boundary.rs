@@ -0,0 +1,291 @@
+//! Boundary extraction: ordered edge loops in 2D, face patches in 3D.
+//!
+//! [`Mesh2::boundary_loops`] chains the boundary edges of a 2D mesh into
+//! closed loops oriented with the mesh on their left, so outer boundaries run
+//! counter-clockwise and island holes clockwise. Each loop is classified by
+//! the sign of `polygon_signed_area`, and its `points` can be passed straight
+//! to `point_in_polygon` and `point_to_polygon_distance`.
+//!
+//! Where several boundary edges leave the same node (two regions touching at
+//! a point), the walk takes the edge that turns most sharply to the left,
+//! towards the mesh, which keeps every loop simple.
+//!
+//! [`Mesh3::boundary_patches`] groups the boundary faces of a volume mesh
+//! into patches connected through shared edges.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::queries::point_in_polygon;
+//! use mesh_geometry::mesh::{Cell, LoopKind, Mesh2};
+//!
+//! let mut mesh = Mesh2::new();
+//! for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+//!     mesh.add_node(Point2::new(x, y));
+//! }
+//! mesh.add_cell(Cell::Triangle([0, 1, 2]));
+//! mesh.add_cell(Cell::Triangle([0, 2, 3]));
+//!
+//! let loops = mesh.boundary_loops();
+//! assert_eq!(loops.len(), 1);
+//! assert_eq!(loops[0].kind, LoopKind::Outer);
+//! assert_eq!(loops[0].nodes, vec![0, 1, 2, 3]);
+//! assert!(point_in_polygon(Point2::new(0.5, 0.5), &loops[0].points));
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Float, Point2};
+use crate::mesh::{Mesh2, Mesh3};
+use crate::metrics::polygon_signed_area;
+
+/// Whether a boundary loop encloses the mesh or cuts a hole in it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoopKind {
+    /// Outer boundary, counter-clockwise (positive signed area)
+    Outer,
+    /// Island hole, clockwise (negative signed area)
+    Island,
+}
+
+/// A closed, ordered boundary loop of a 2D mesh, mesh on the left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundaryLoop<T: Float> {
+    /// Node indices in walking order; the last connects back to the first
+    pub nodes: Vec<usize>,
+    /// Node coordinates in the same order
+    pub points: Vec<Point2<T>>,
+    /// Signed area enclosed by the loop
+    pub signed_area: T,
+    /// Outer boundary or island
+    pub kind: LoopKind,
+}
+
+/// A set of boundary faces connected through shared edges.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryPatch {
+    /// Indices into the faces of [`Mesh::topology`](crate::mesh::Mesh::topology)
+    pub face_ids: Vec<usize>,
+    /// Face node indices, oriented outward
+    pub faces: Vec<Vec<usize>>,
+}
+
+/// Clockwise angle from direction `from` to direction `to`, in `(0, 2π]`.
+fn clockwise_angle<T: Float>(from: Point2<T>, to: Point2<T>) -> T {
+    let two_pi = T::from(2.0 * core::f64::consts::PI).unwrap();
+    let mut a = from.y.atan2(from.x) - to.y.atan2(to.x);
+    while a <= T::zero() {
+        a = a + two_pi;
+    }
+    while a > two_pi {
+        a = a - two_pi;
+    }
+    a
+}
+
+impl<T: Float> Mesh2<T> {
+    /// Ordered, oriented boundary loops, classified as outer or island.
+    ///
+    /// Chains that fail to close (only possible on invalid meshes) are
+    /// dropped.
+    pub fn boundary_loops(&self) -> Vec<BoundaryLoop<T>> {
+        let topo = self.topology();
+        let edges: Vec<[usize; 2]> = topo
+            .boundary_faces()
+            .filter(|&f| topo.faces[f].len() == 2)
+            .map(|f| [topo.faces[f][0], topo.faces[f][1]])
+            .collect();
+        let mut outgoing: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (e, &[a, _]) in edges.iter().enumerate() {
+            outgoing.entry(a).or_default().push(e);
+        }
+        let dir = |e: usize| -> Point2<T> {
+            let [a, b] = edges[e];
+            let (pa, pb) = (self.nodes[a], self.nodes[b]);
+            Point2::new(pb.x - pa.x, pb.y - pa.y)
+        };
+
+        let mut used = vec![false; edges.len()];
+        let mut loops = Vec::new();
+        for first in 0..edges.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+            let start = edges[first][0];
+            let mut nodes = vec![start];
+            let mut current = first;
+            let closed = loop {
+                let at = edges[current][1];
+                let d = dir(current);
+                let back = Point2::new(-d.x, -d.y);
+                let mut candidates: Vec<usize> =
+                    outgoing.get(&at).map_or_else(Vec::new, |v| v.iter().copied().filter(|&e| !used[e]).collect());
+                if at == start {
+                    candidates.push(first);
+                }
+                let next = candidates.into_iter().min_by(|&x, &y| {
+                    clockwise_angle(back, dir(x))
+                        .partial_cmp(&clockwise_angle(back, dir(y)))
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                });
+                match next {
+                    Some(e) if e == first => break true,
+                    Some(e) => {
+                        used[e] = true;
+                        nodes.push(at);
+                        current = e;
+                    }
+                    None => break false,
+                }
+            };
+            if !closed || nodes.len() < 3 {
+                continue;
+            }
+            let points: Vec<Point2<T>> = nodes.iter().map(|&n| self.nodes[n]).collect();
+            let signed_area = polygon_signed_area(&points);
+            let kind = if signed_area > T::zero() { LoopKind::Outer } else { LoopKind::Island };
+            loops.push(BoundaryLoop { nodes, points, signed_area, kind });
+        }
+        loops
+    }
+}
+
+impl<T: Float> Mesh3<T> {
+    /// Boundary faces grouped into patches connected through shared edges.
+    pub fn boundary_patches(&self) -> Vec<BoundaryPatch> {
+        let topo = self.topology();
+        let faces: Vec<usize> = topo.boundary_faces().collect();
+
+        // union-find over boundary faces, merged through shared edges
+        let mut parent: Vec<usize> = (0..faces.len()).collect();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        let mut edge_owner: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for (i, &f) in faces.iter().enumerate() {
+            let nodes = &topo.faces[f];
+            for k in 0..nodes.len() {
+                let (a, b) = (nodes[k], nodes[(k + 1) % nodes.len()]);
+                let key = (a.min(b), a.max(b));
+                match edge_owner.get(&key) {
+                    Some(&j) => {
+                        let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                        parent[ri.max(rj)] = ri.min(rj);
+                    }
+                    None => {
+                        edge_owner.insert(key, i);
+                    }
+                }
+            }
+        }
+
+        let mut patch_of: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut patches: Vec<BoundaryPatch> = Vec::new();
+        for (i, &f) in faces.iter().enumerate() {
+            let r = root(&mut parent, i);
+            let p = *patch_of.entry(r).or_insert_with(|| {
+                patches.push(BoundaryPatch { face_ids: Vec::new(), faces: Vec::new() });
+                patches.len() - 1
+            });
+            patches[p].face_ids.push(f);
+            patches[p].faces.push(topo.faces[f].clone());
+        }
+        patches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::Cell;
+    use crate::queries::{point_in_polygon, point_to_polygon_distance};
+    use crate::Point3;
+
+    #[test]
+    fn grid_with_hole_gives_outer_and_island() {
+        // 3×3 unit quads with the centre one removed
+        let mut mesh = Mesh2::new();
+        for j in 0..4 {
+            for i in 0..4 {
+                mesh.add_node(Point2::new(i as f64, j as f64));
+            }
+        }
+        for j in 0..3 {
+            for i in 0..3 {
+                if (i, j) != (1, 1) {
+                    let n = j * 4 + i;
+                    mesh.add_cell(Cell::Quad([n, n + 1, n + 5, n + 4]));
+                }
+            }
+        }
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 2);
+        let outer = loops.iter().find(|l| l.kind == LoopKind::Outer).unwrap();
+        let island = loops.iter().find(|l| l.kind == LoopKind::Island).unwrap();
+        assert_eq!(outer.nodes.len(), 12);
+        assert!((outer.signed_area - 9.0).abs() < 1e-12);
+        assert!((island.signed_area + 1.0).abs() < 1e-12);
+
+        let p = Point2::new(1.5, 1.5);
+        assert!(point_in_polygon(p, &outer.points));
+        assert!(point_in_polygon(p, &island.points));
+        assert!((point_to_polygon_distance(Point2::new(1.5, 1.2), &island.points)).abs() < 1e-12);
+        assert!((point_to_polygon_distance(Point2::new(-1.0, 1.5), &outer.points) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn touching_triangles_give_two_simple_loops() {
+        // two triangles sharing only node 2
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(2.0, 2.0),
+        ];
+        let mesh = Mesh2::from_parts(nodes, vec![Cell::Triangle([0, 1, 2]), Cell::Triangle([2, 3, 4])]);
+        let loops = mesh.boundary_loops();
+        assert_eq!(loops.len(), 2);
+        for l in &loops {
+            assert_eq!(l.nodes.len(), 3);
+            assert_eq!(l.kind, LoopKind::Outer);
+        }
+    }
+
+    #[test]
+    fn separate_hexes_form_separate_patches() {
+        let mut mesh = Mesh3::new();
+        for x0 in [0.0_f64, 1.0, 3.0] {
+            let base = mesh.num_nodes();
+            for k in 0..2 {
+                for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                    mesh.add_node(Point3::new(x0 + x, y, k as f64));
+                }
+            }
+            let n: [usize; 8] = core::array::from_fn(|i| base + i);
+            mesh.add_cell(Cell::Hexahedron(n));
+        }
+        // the first two hexes touch at x = 1 only through coincident nodes,
+        // so merge them by reusing the shared nodes
+        let shared = [(8, 1), (11, 2), (12, 5), (15, 6)];
+        if let Cell::Hexahedron(n) = &mut mesh.cells[1] {
+            for k in n.iter_mut() {
+                if let Some(&(_, s)) = shared.iter().find(|&&(old, _)| old == *k) {
+                    *k = s;
+                }
+            }
+        }
+        let patches = mesh.boundary_patches();
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches[0].faces.len(), 10);
+        assert_eq!(patches[1].faces.len(), 6);
+    }
+}
@@ -0,0 +1,265 @@
+//! Precomputed finite-volume geometry in struct-of-arrays form.
+//!
+//! [`FvGeometry`] evaluates, once per mesh, the quantities a cell-centred
+//! solver needs every step: cell volumes and centroids, face area vectors,
+//! areas and centroids, owner→neighbour distance vectors and owner
+//! interpolation weights. Face data follow the [`Topology`] face order, and
+//! area vectors point from owner to neighbour.
+//!
+//! Tetrahedra use `tetrahedron_volume`/`tetrahedron_centroid`; all other
+//! volume cells use the divergence-theorem `polyhedron_volume` and
+//! `polyhedron_centroid`, which stay consistent with the face area vectors
+//! for pyramids, wedges and warped hexahedra. On boundary faces the distance
+//! vector ends at the face centroid and the weight is 1. Surface cells
+//! (triangles, quads, polygons) have no volume, so a `Mesh3` holding any is
+//! rejected with [`FvGeometryError::SurfaceCell`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point3;
+//! use mesh_geometry::mesh::{Cell, FvGeometry, Mesh3};
+//!
+//! let mut mesh = Mesh3::new();
+//! for k in 0..2 {
+//!     for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+//!         mesh.add_node(Point3::new(x, y, k as f64));
+//!     }
+//! }
+//! mesh.add_cell(Cell::Hexahedron([0, 1, 2, 3, 4, 5, 6, 7]));
+//!
+//! let mut geo = FvGeometry::new(&mesh).unwrap();
+//! assert!((geo.cell_volumes[0] - 1.0).abs() < 1e-12);
+//!
+//! // lift the top face and refresh only what depends on it
+//! for n in 4..8 {
+//!     mesh.nodes[n].z = 2.0;
+//! }
+//! geo.update(&mesh, &[4, 5, 6, 7]);
+//! assert!((geo.cell_volumes[0] - 2.0).abs() < 1e-12);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Float, Point3, Vec3};
+use crate::mesh::{CellPoints, Mesh3, Topology};
+use crate::mesh::{PYRAMID_FACES, WEDGE_FACES, HEXAHEDRON_FACES};
+use crate::metrics::{
+    face_centroid, face_normal, polyhedron_centroid, polyhedron_volume, tetrahedron_centroid,
+    tetrahedron_volume,
+};
+
+/// Cell and face geometry of a volume mesh, stored as parallel arrays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FvGeometry<T: Float> {
+    /// Face connectivity the face arrays are indexed by
+    pub topology: Topology,
+    /// Volume of each cell
+    pub cell_volumes: Vec<T>,
+    /// Centroid of each cell
+    pub cell_centroids: Vec<Point3<T>>,
+    /// Area of each face
+    pub face_areas: Vec<T>,
+    /// Area vector of each face, from owner to neighbour
+    pub face_area_vectors: Vec<Vec3<T>>,
+    /// Centroid of each face
+    pub face_centroids: Vec<Point3<T>>,
+    /// Neighbour centroid (or face centroid on the boundary) minus owner centroid
+    pub face_deltas: Vec<Vec3<T>>,
+    /// Owner interpolation weight of each face
+    pub face_weights: Vec<T>,
+    /// Cells touching each node, for incremental updates
+    node_cells: Vec<Vec<usize>>,
+}
+
+/// Reasons a mesh has no finite-volume geometry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FvGeometryError {
+    /// The cell is a surface cell (triangle, quad or polygon)
+    SurfaceCell(usize),
+}
+
+impl fmt::Display for FvGeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FvGeometryError::SurfaceCell(c) => write!(f, "cell {} is a surface cell, not a volume cell", c),
+        }
+    }
+}
+
+/// Volume and centroid of one volume cell; `new` has rejected surface cells.
+fn cell_geometry<T: Float>(mesh: &Mesh3<T>, c: usize) -> (T, Point3<T>) {
+    match mesh.cell_points_of(c) {
+        CellPoints::Tetrahedron([a, b, cc, d]) => {
+            (tetrahedron_volume(a, b, cc, d), tetrahedron_centroid(a, b, cc, d))
+        }
+        CellPoints::Pyramid(v) => (polyhedron_volume(&v, &PYRAMID_FACES), polyhedron_centroid(&v, &PYRAMID_FACES)),
+        CellPoints::Wedge(v) => (polyhedron_volume(&v, &WEDGE_FACES), polyhedron_centroid(&v, &WEDGE_FACES)),
+        CellPoints::Hexahedron(v) => {
+            (polyhedron_volume(&v, &HEXAHEDRON_FACES), polyhedron_centroid(&v, &HEXAHEDRON_FACES))
+        }
+        CellPoints::Polyhedron(v, f) => (polyhedron_volume(&v, f), polyhedron_centroid(&v, f)),
+        _ => unreachable!("surface cell {} in FvGeometry", c),
+    }
+}
+
+impl<T: Float> FvGeometry<T> {
+    /// Compute topology and all cell and face geometry for `mesh`, which
+    /// must hold only volume cells.
+    pub fn new(mesh: &Mesh3<T>) -> Result<Self, FvGeometryError> {
+        if let Some(c) = mesh.cells.iter().position(|cell| cell.cell_type().dim() != 3) {
+            return Err(FvGeometryError::SurfaceCell(c));
+        }
+        let topology = mesh.topology();
+        let mut node_cells = vec![Vec::new(); mesh.num_nodes()];
+        for (c, cell) in mesh.cells.iter().enumerate() {
+            for &n in cell.nodes() {
+                node_cells[n].push(c);
+            }
+        }
+        let zero = Vec3::new(T::zero(), T::zero(), T::zero());
+        let origin = Point3::new(T::zero(), T::zero(), T::zero());
+        let nf = topology.num_faces();
+        let nc = mesh.num_cells();
+        let mut geo = FvGeometry {
+            topology,
+            cell_volumes: vec![T::zero(); nc],
+            cell_centroids: vec![origin; nc],
+            face_areas: vec![T::zero(); nf],
+            face_area_vectors: vec![zero; nf],
+            face_centroids: vec![origin; nf],
+            face_deltas: vec![zero; nf],
+            face_weights: vec![T::one(); nf],
+            node_cells,
+        };
+        for c in 0..nc {
+            geo.update_cell(mesh, c);
+        }
+        for f in 0..nf {
+            geo.update_face(mesh, f);
+        }
+        Ok(geo)
+    }
+
+    /// Recompute everything that depends on the nodes in `moved`: the cells
+    /// containing them and every face of those cells.
+    pub fn update(&mut self, mesh: &Mesh3<T>, moved: &[usize]) {
+        let mut cells: Vec<usize> = moved.iter().flat_map(|&n| self.node_cells[n].iter().copied()).collect();
+        cells.sort_unstable();
+        cells.dedup();
+        let mut faces: Vec<usize> = cells
+            .iter()
+            .flat_map(|&c| self.topology.cell_faces[c].iter().copied())
+            .collect();
+        faces.sort_unstable();
+        faces.dedup();
+        for &c in &cells {
+            self.update_cell(mesh, c);
+        }
+        for &f in &faces {
+            self.update_face(mesh, f);
+        }
+    }
+
+    fn update_cell(&mut self, mesh: &Mesh3<T>, c: usize) {
+        let (vol, cen) = cell_geometry(mesh, c);
+        self.cell_volumes[c] = vol;
+        self.cell_centroids[c] = cen;
+    }
+
+    fn update_face(&mut self, mesh: &Mesh3<T>, f: usize) {
+        let pts = self.topology.face_points_of(f, &mesh.nodes);
+        let s = face_normal(&pts) * T::from(0.5).unwrap();
+        let cf = face_centroid(&pts);
+        let p = self.cell_centroids[self.topology.owner[f]];
+        let (delta, weight) = match self.topology.neighbour[f] {
+            Some(nb) => {
+                let d = self.cell_centroids[nb] - p;
+                (d, T::one() - s.dot(cf - p) / s.dot(d))
+            }
+            None => (cf - p, T::one()),
+        };
+        self.face_area_vectors[f] = s;
+        self.face_areas[f] = s.magnitude();
+        self.face_centroids[f] = cf;
+        self.face_deltas[f] = delta;
+        self.face_weights[f] = weight;
+    }
+
+    /// Number of cells
+    pub fn num_cells(&self) -> usize {
+        self.cell_volumes.len()
+    }
+
+    /// Number of faces
+    pub fn num_faces(&self) -> usize {
+        self.face_areas.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Cell, Mesh3};
+    use crate::Point3;
+
+    /// A unit-height block: one hex on the left, two wedges on the right.
+    fn mixed_block() -> Mesh3<f64> {
+        let mut mesh = Mesh3::new();
+        for k in 0..2 {
+            for (x, y) in [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.add_node(Point3::new(x, y, k as f64));
+            }
+        }
+        mesh.add_cell(Cell::Hexahedron([0, 1, 4, 5, 6, 7, 10, 11]));
+        // wedges need (0,1,2) facing down, i.e. clockwise seen from above
+        mesh.add_cell(Cell::Wedge([1, 4, 2, 7, 10, 8]));
+        mesh.add_cell(Cell::Wedge([2, 4, 3, 8, 10, 9]));
+        mesh
+    }
+
+    #[test]
+    fn mixed_block_closes() {
+        let mesh = mixed_block();
+        let geo = FvGeometry::new(&mesh).unwrap();
+        let total: f64 = geo.cell_volumes.iter().sum();
+        assert!((total - 2.0).abs() < 1e-12);
+        assert!((geo.cell_volumes[1] - 0.5).abs() < 1e-12);
+        // each cell's outward area vectors sum to zero
+        for c in 0..geo.num_cells() {
+            let mut sum = Vec3::new(0.0, 0.0, 0.0);
+            for &f in &geo.topology.cell_faces[c] {
+                let s = geo.face_area_vectors[f];
+                sum = if geo.topology.owner[f] == c { sum + s } else { sum - s };
+            }
+            assert!(sum.magnitude() < 1e-12);
+        }
+        // hex | wedge face at x = 1: owner weight from centroid distances
+        let f = geo
+            .topology
+            .internal_faces()
+            .find(|&f| (geo.face_centroids[f].x - 1.0).abs() < 1e-12)
+            .unwrap();
+        let wedge_cx = (1.0 + 1.0 + 2.0) / 3.0;
+        assert!((geo.face_weights[f] - (wedge_cx - 1.0) / (wedge_cx - 0.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn incremental_update_matches_rebuild() {
+        let mut mesh = mixed_block();
+        let mut geo = FvGeometry::new(&mesh).unwrap();
+        mesh.nodes[8].z = 1.4;
+        mesh.nodes[2].x = 2.2;
+        geo.update(&mesh, &[8, 2]);
+        assert_eq!(geo, FvGeometry::new(&mesh).unwrap());
+    }
+
+    #[test]
+    fn surface_cells_are_rejected() {
+        let mut mesh = mixed_block();
+        mesh.add_cell(Cell::Triangle([0, 1, 5]));
+        assert_eq!(FvGeometry::new(&mesh), Err(FvGeometryError::SurfaceCell(3)));
+    }
+}
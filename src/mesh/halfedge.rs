@@ -0,0 +1,685 @@
+//! Half-edge mesh for 2D triangulations and polygon meshes.
+//!
+//! Every edge is stored as two opposite half-edges. Boundary edges get an
+//! explicit half-edge with no face, linked into boundary loops, so `twin`
+//! is always defined and vertex stars are complete cycles. Faces are
+//! traversed counter-clockwise through `next`; a vertex's outgoing edges are
+//! visited with `next(twin(h))`, and a boundary vertex stores its boundary
+//! half-edge as its outgoing edge.
+//!
+//! Positions are `Point2`, so `polygon_area`, `polygon_centroid` and the
+//! other planar metrics run directly on [`HalfEdgeMesh::face_points`].
+//! Triangle edges can be flipped, split and collapsed in place.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point2, polygon_area};
+//! use mesh_geometry::mesh::HalfEdgeMesh;
+//!
+//! let pts = vec![
+//!     Point2::new(0.0_f64, 0.0),
+//!     Point2::new(1.0, 0.0),
+//!     Point2::new(1.0, 1.0),
+//!     Point2::new(0.0, 1.0),
+//! ];
+//! let mut he = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2], [0, 2, 3]]).unwrap();
+//! assert_eq!(he.boundary_loops()[0].len(), 4);
+//!
+//! let diag = he.find_half_edge(0, 2).unwrap();
+//! assert!(he.flip_edge(diag));
+//! let area: f64 = (0..he.num_faces()).map(|f| polygon_area(&he.face_points(f))).sum();
+//! assert!((area - 1.0).abs() < 1e-12);
+//! ```
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Float, Point2, polygon_signed_area};
+use crate::mesh::Mesh2;
+
+/// One directed half of an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HalfEdge {
+    /// Vertex the half-edge starts at
+    pub origin: usize,
+    /// Opposite half-edge
+    pub twin: usize,
+    /// Next half-edge around the face (or boundary loop)
+    pub next: usize,
+    /// Previous half-edge around the face (or boundary loop)
+    pub prev: usize,
+    /// Face to the left, `None` for boundary half-edges
+    pub face: Option<usize>,
+}
+
+/// Reasons a face list cannot be turned into a half-edge mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HalfEdgeError {
+    /// The directed edge `(from, to)` is used by more than one face, either
+    /// because three faces share the edge or two neighbours disagree on orientation
+    NonManifoldEdge(usize, usize),
+    /// The vertex joins more than one boundary fan
+    NonManifoldVertex(usize),
+    /// A face refers to a vertex index with no position
+    VertexOutOfRange(usize),
+    /// The face has fewer than three vertices or repeats one
+    DegenerateFace(usize),
+}
+
+impl fmt::Display for HalfEdgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HalfEdgeError::NonManifoldEdge(a, b) => write!(f, "edge ({}, {}) is non-manifold or inconsistently oriented", a, b),
+            HalfEdgeError::NonManifoldVertex(v) => write!(f, "vertex {} is non-manifold", v),
+            HalfEdgeError::VertexOutOfRange(v) => write!(f, "vertex {} is out of range", v),
+            HalfEdgeError::DegenerateFace(face) => write!(f, "face {} has fewer than three distinct vertices", face),
+        }
+    }
+}
+
+/// Half-edge mesh over `Point2` positions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HalfEdgeMesh<T: Float> {
+    /// Vertex positions
+    pub positions: Vec<Point2<T>>,
+    /// Half-edge records
+    pub half_edges: Vec<HalfEdge>,
+    /// One outgoing half-edge per vertex (the boundary one on the boundary)
+    pub vertex_edge: Vec<Option<usize>>,
+    /// One half-edge per face
+    pub face_edge: Vec<usize>,
+}
+
+impl<T: Float> HalfEdgeMesh<T> {
+    /// Build from counter-clockwise triangles.
+    pub fn from_triangles(positions: Vec<Point2<T>>, tris: &[[usize; 3]]) -> Result<Self, HalfEdgeError> {
+        Self::from_polygons(positions, tris)
+    }
+
+    /// Build from a [`Mesh2`]'s cells (all 2D cell types are polygons).
+    pub fn from_mesh(mesh: &Mesh2<T>) -> Result<Self, HalfEdgeError> {
+        let faces: Vec<&[usize]> = mesh.cells.iter().map(|c| c.nodes()).collect();
+        Self::from_polygons(mesh.nodes.clone(), &faces)
+    }
+
+    /// Build from counter-clockwise polygons given as vertex index lists.
+    pub fn from_polygons<F: AsRef<[usize]>>(positions: Vec<Point2<T>>, faces: &[F]) -> Result<Self, HalfEdgeError> {
+        let none = usize::MAX;
+        let mut hes: Vec<HalfEdge> = Vec::new();
+        let mut face_edge = Vec::with_capacity(faces.len());
+        let mut directed: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+        for (f, face) in faces.iter().enumerate() {
+            let face = face.as_ref();
+            if let Some(&v) = face.iter().find(|&&v| v >= positions.len()) {
+                return Err(HalfEdgeError::VertexOutOfRange(v));
+            }
+            let k = face.len();
+            if k < 3 || (1..k).any(|i| face[..i].contains(&face[i])) {
+                return Err(HalfEdgeError::DegenerateFace(f));
+            }
+            let base = hes.len();
+            for i in 0..k {
+                let (a, b) = (face[i], face[(i + 1) % k]);
+                if directed.insert((a, b), base + i).is_some() {
+                    return Err(HalfEdgeError::NonManifoldEdge(a, b));
+                }
+                hes.push(HalfEdge {
+                    origin: a,
+                    twin: none,
+                    next: base + (i + 1) % k,
+                    prev: base + (i + k - 1) % k,
+                    face: Some(f),
+                });
+            }
+            face_edge.push(base);
+        }
+        // pair up twins, creating boundary half-edges where none exists
+        let mut boundary_from: BTreeMap<usize, usize> = BTreeMap::new();
+        let interior = hes.len();
+        for h in 0..interior {
+            if hes[h].twin != none {
+                continue;
+            }
+            let (a, b) = (hes[h].origin, hes[hes[h].next].origin);
+            let t = match directed.get(&(b, a)) {
+                Some(&t) => t,
+                None => {
+                    hes.push(HalfEdge { origin: b, twin: h, next: none, prev: none, face: None });
+                    if boundary_from.insert(b, hes.len() - 1).is_some() {
+                        return Err(HalfEdgeError::NonManifoldVertex(b));
+                    }
+                    hes.len() - 1
+                }
+            };
+            hes[h].twin = t;
+            hes[t].twin = h;
+        }
+        // link boundary loops: a boundary half-edge continues from its end vertex
+        for b in interior..hes.len() {
+            let end = hes[hes[b].twin].origin;
+            let n = boundary_from[&end];
+            hes[b].next = n;
+            hes[n].prev = b;
+        }
+        let mut vertex_edge = vec![None; positions.len()];
+        for (h, he) in hes.iter().enumerate() {
+            if vertex_edge[he.origin].is_none() || he.face.is_none() {
+                vertex_edge[he.origin] = Some(h);
+            }
+        }
+        Ok(HalfEdgeMesh { positions, half_edges: hes, vertex_edge, face_edge })
+    }
+
+    /// Number of vertices
+    pub fn num_vertices(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Number of faces
+    pub fn num_faces(&self) -> usize {
+        self.face_edge.len()
+    }
+
+    /// Start vertex of half-edge `h`
+    pub fn origin(&self, h: usize) -> usize {
+        self.half_edges[h].origin
+    }
+
+    /// End vertex of half-edge `h`
+    pub fn dest(&self, h: usize) -> usize {
+        self.half_edges[self.half_edges[h].twin].origin
+    }
+
+    /// Opposite half-edge of `h`
+    pub fn twin(&self, h: usize) -> usize {
+        self.half_edges[h].twin
+    }
+
+    /// Next half-edge around `h`'s face
+    pub fn next(&self, h: usize) -> usize {
+        self.half_edges[h].next
+    }
+
+    /// Previous half-edge around `h`'s face
+    pub fn prev(&self, h: usize) -> usize {
+        self.half_edges[h].prev
+    }
+
+    /// Face left of `h`, `None` on the boundary
+    pub fn face(&self, h: usize) -> Option<usize> {
+        self.half_edges[h].face
+    }
+
+    /// Half-edge from `a` to `b`, if the edge exists.
+    pub fn find_half_edge(&self, a: usize, b: usize) -> Option<usize> {
+        self.outgoing(a).find(|&h| self.dest(h) == b)
+    }
+
+    /// Whether `v` lies on the boundary
+    pub fn is_boundary_vertex(&self, v: usize) -> bool {
+        self.vertex_edge[v].is_some_and(|h| self.face(h).is_none())
+    }
+
+    /// Half-edges of face `f`, counter-clockwise.
+    pub fn face_half_edges(&self, f: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.face_edge[f];
+        let mut cur = Some(start);
+        core::iter::from_fn(move || {
+            let h = cur?;
+            let n = self.next(h);
+            cur = if n == start { None } else { Some(n) };
+            Some(h)
+        })
+    }
+
+    /// Vertices of face `f`, counter-clockwise.
+    pub fn face_vertices(&self, f: usize) -> impl Iterator<Item = usize> + '_ {
+        self.face_half_edges(f).map(move |h| self.origin(h))
+    }
+
+    /// Positions of face `f`'s vertices, ready for `polygon_area` etc.
+    pub fn face_points(&self, f: usize) -> Vec<Point2<T>> {
+        self.face_vertices(f).map(|v| self.positions[v]).collect()
+    }
+
+    /// Outgoing half-edges of vertex `v` (its star), starting from `vertex_edge[v]`.
+    pub fn outgoing(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        let start = self.vertex_edge[v];
+        let mut cur = start;
+        core::iter::from_fn(move || {
+            let h = cur?;
+            let n = self.next(self.twin(h));
+            cur = if Some(n) == start { None } else { Some(n) };
+            Some(h)
+        })
+    }
+
+    /// Vertices adjacent to `v`.
+    pub fn vertex_neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing(v).map(move |h| self.dest(h))
+    }
+
+    /// Faces around `v`.
+    pub fn vertex_faces(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing(v).filter_map(move |h| self.face(h))
+    }
+
+    /// Boundary loops as vertex lists. Each loop runs with the exterior on its
+    /// left, i.e. clockwise for the outer boundary and counter-clockwise around holes.
+    pub fn boundary_loops(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.half_edges.len()];
+        let mut loops = Vec::new();
+        for h in 0..self.half_edges.len() {
+            if seen[h] || self.face(h).is_some() {
+                continue;
+            }
+            let mut lp = Vec::new();
+            let mut cur = h;
+            while !seen[cur] {
+                seen[cur] = true;
+                lp.push(self.origin(cur));
+                cur = self.next(cur);
+            }
+            loops.push(lp);
+        }
+        loops
+    }
+
+    /// Whether face `f` is a triangle.
+    fn is_triangle(&self, f: Option<usize>) -> bool {
+        f.is_some_and(|f| self.face_half_edges(f).count() == 3)
+    }
+
+    fn link(&mut self, a: usize, b: usize) {
+        self.half_edges[a].next = b;
+        self.half_edges[b].prev = a;
+    }
+
+    /// Whether `p, q, r` turn strictly counter-clockwise.
+    fn is_ccw(&self, p: usize, q: usize, r: usize) -> bool {
+        polygon_signed_area(&[self.positions[p], self.positions[q], self.positions[r]]) > T::zero()
+    }
+
+    /// Flip the interior edge of `h` between two triangles. Returns `false`
+    /// (leaving the mesh untouched) if either side is not a triangle, the
+    /// opposite corners coincide or are already joined by an edge, or the
+    /// quadrilateral around the edge is not strictly convex.
+    pub fn flip_edge(&mut self, h: usize) -> bool {
+        let t = self.twin(h);
+        let (f0, f1) = (self.face(h), self.face(t));
+        if !self.is_triangle(f0) || !self.is_triangle(f1) {
+            return false;
+        }
+        // h: a→b, h1: b→c, h2: c→a;  t: b→a, t1: a→d, t2: d→b
+        let (h1, h2) = (self.next(h), self.prev(h));
+        let (t1, t2) = (self.next(t), self.prev(t));
+        let (a, b) = (self.origin(h), self.origin(t));
+        let (c, d) = (self.origin(h2), self.origin(t2));
+        if c == d || self.find_half_edge(c, d).is_some() {
+            return false;
+        }
+        // the quad a, d, b, c (counter-clockwise) must be strictly convex
+        if ![(a, d, b), (d, b, c), (b, c, a), (c, a, d)].iter().all(|&(p, q, r)| self.is_ccw(p, q, r)) {
+            return false;
+        }
+        // new faces (d, c, a) and (c, d, b)
+        self.half_edges[h].origin = d;
+        self.half_edges[t].origin = c;
+        self.link(h, h2);
+        self.link(h2, t1);
+        self.link(t1, h);
+        self.link(t, t2);
+        self.link(t2, h1);
+        self.link(h1, t);
+        self.half_edges[t1].face = f0;
+        self.half_edges[h1].face = f1;
+        self.face_edge[f0.unwrap()] = h;
+        self.face_edge[f1.unwrap()] = t;
+        if self.vertex_edge[a] == Some(h) {
+            self.vertex_edge[a] = Some(t1);
+        }
+        if self.vertex_edge[b] == Some(t) {
+            self.vertex_edge[b] = Some(h1);
+        }
+        true
+    }
+
+    /// Split the edge of `h` at `p`, connecting the new vertex to the
+    /// opposite corner of each adjacent triangle. Returns the new vertex, or
+    /// `None` (leaving the mesh untouched) if an adjacent face is not a triangle.
+    pub fn split_edge(&mut self, h: usize, p: Point2<T>) -> Option<usize> {
+        let t = self.twin(h);
+        for f in [self.face(h), self.face(t)] {
+            if f.is_some() && !self.is_triangle(f) {
+                return None;
+            }
+        }
+        let m = self.positions.len();
+        self.positions.push(p);
+        // h: a→m, hn: m→b, t: b→m, tn: m→a
+        let (hn, tn) = (self.half_edges.len(), self.half_edges.len() + 1);
+        let (h_next, t_next) = (self.next(h), self.next(t));
+        let (fh, ft) = (self.face(h), self.face(t));
+        self.half_edges.push(HalfEdge { origin: m, twin: t, next: h_next, prev: h, face: fh });
+        self.half_edges.push(HalfEdge { origin: m, twin: h, next: t_next, prev: t, face: ft });
+        self.half_edges[h].twin = tn;
+        self.half_edges[t].twin = hn;
+        self.link(h, hn);
+        self.link(hn, h_next);
+        self.link(t, tn);
+        self.link(tn, t_next);
+        self.vertex_edge.push(Some(if fh.is_none() { hn } else { tn }));
+        // cut each adjacent (now four-sided) face from m to its far corner
+        for (first, second) in [(h, hn), (t, tn)] {
+            let Some(f) = self.face(first) else { continue };
+            let far = self.next(second);
+            let back = self.next(far);
+            let c = self.origin(back);
+            let (e, et) = (self.half_edges.len(), self.half_edges.len() + 1);
+            let nf = self.face_edge.len();
+            self.half_edges.push(HalfEdge { origin: m, twin: et, next: back, prev: first, face: Some(f) });
+            self.half_edges.push(HalfEdge { origin: c, twin: e, next: second, prev: far, face: Some(nf) });
+            self.link(first, e);
+            self.link(e, back);
+            self.link(far, et);
+            self.link(et, second);
+            self.half_edges[second].face = Some(nf);
+            self.half_edges[far].face = Some(nf);
+            self.face_edge[f] = first;
+            self.face_edge.push(second);
+        }
+        Some(m)
+    }
+
+    /// Collapse the edge of `h` into one vertex at `p`, removing `dest(h)`
+    /// and the triangles on the edge. Freed slots are refilled from the end
+    /// of `positions`, `half_edges` and `face_edge`, so the last vertex, face
+    /// and half-edges are renumbered. Returns the merged vertex, or `None`
+    /// (leaving the mesh untouched) if an adjacent face is not a triangle,
+    /// the link condition fails, or a face around the edge would be inverted.
+    pub fn collapse_edge(&mut self, h: usize, p: Point2<T>) -> Option<usize> {
+        let t = self.twin(h);
+        let (a, b) = (self.origin(h), self.dest(h));
+        let sides: Vec<usize> = [h, t].into_iter().filter(|&e| self.face(e).is_some()).collect();
+        if sides.iter().any(|&e| !self.is_triangle(self.face(e))) {
+            return None;
+        }
+        // link condition: the common neighbours of a and b are exactly the
+        // corners opposite the edge, and an interior edge may not join two
+        // boundary vertices
+        let na: BTreeSet<usize> = self.vertex_neighbors(a).collect();
+        let common: BTreeSet<usize> = self.vertex_neighbors(b).filter(|v| na.contains(v)).collect();
+        let opposite: BTreeSet<usize> = sides.iter().map(|&e| self.origin(self.prev(e))).collect();
+        if common != opposite || (sides.len() == 2 && self.is_boundary_vertex(a) && self.is_boundary_vertex(b)) {
+            return None;
+        }
+        // a triangle whose other two edges are both boundary would leave a dangling edge
+        if sides.iter().any(|&e| self.face(self.twin(self.next(e))).is_none() && self.face(self.twin(self.prev(e))).is_none()) {
+            return None;
+        }
+        // the remaining faces around a and b must stay counter-clockwise
+        let removed: Vec<Option<usize>> = sides.iter().map(|&e| self.face(e)).collect();
+        for f in self.vertex_faces(a).chain(self.vertex_faces(b)) {
+            if removed.contains(&Some(f)) {
+                continue;
+            }
+            let moved: Vec<Point2<T>> =
+                self.face_vertices(f).map(|v| if v == a || v == b { p } else { self.positions[v] }).collect();
+            if polygon_signed_area(&moved) <= T::zero() {
+                return None;
+            }
+        }
+
+        let star_b: Vec<usize> = self.outgoing(b).collect();
+        let mut dead_edges = vec![h, t];
+        let mut dead_faces = Vec::new();
+        let mut keep = Vec::new();
+        // a surviving half-edge out of a: the one after h on h's side
+        let mut from_a = if self.face(h).is_some() { self.twin(self.prev(h)) } else { self.next(h) };
+        for e in [h, t] {
+            match self.face(e) {
+                Some(f) => {
+                    // e: x→y, e1: y→c, e2: c→x; the outer twins of e1 and e2 become one edge
+                    let (e1, e2) = (self.next(e), self.prev(e));
+                    let (o1, o2) = (self.twin(e1), self.twin(e2));
+                    self.half_edges[o1].twin = o2;
+                    self.half_edges[o2].twin = o1;
+                    dead_edges.extend([e1, e2]);
+                    dead_faces.push(f);
+                    keep.push((self.origin(e2), o1));
+                }
+                None => {
+                    let (before, after) = (self.prev(e), self.next(e));
+                    self.link(before, after);
+                    if e == h {
+                        from_a = after;
+                    }
+                }
+            }
+        }
+        for e in star_b {
+            self.half_edges[e].origin = a;
+        }
+        self.positions[a] = p;
+        keep.push((a, from_a));
+        for (v, start) in keep {
+            self.reset_vertex_edge(v, start);
+        }
+
+        dead_edges.sort_unstable();
+        for &e in dead_edges.iter().rev() {
+            self.remove_half_edge(e);
+        }
+        dead_faces.sort_unstable();
+        for &f in dead_faces.iter().rev() {
+            let last = self.face_edge.len() - 1;
+            self.face_edge.swap_remove(f);
+            if f != last {
+                let moved: Vec<usize> = self.face_half_edges(f).collect();
+                for e in moved {
+                    self.half_edges[e].face = Some(f);
+                }
+            }
+        }
+        let last = self.positions.len() - 1;
+        self.positions.swap_remove(b);
+        self.vertex_edge.swap_remove(b);
+        if b != last {
+            let moved: Vec<usize> = self.outgoing(b).collect();
+            for e in moved {
+                self.half_edges[e].origin = b;
+            }
+        }
+        Some(if a == last { b } else { a })
+    }
+
+    /// Point `vertex_edge[v]` at an outgoing half-edge of its star (found
+    /// from `start`), preferring the boundary one.
+    fn reset_vertex_edge(&mut self, v: usize, start: usize) {
+        let mut cur = start;
+        loop {
+            if self.face(cur).is_none() {
+                break;
+            }
+            cur = self.next(self.twin(cur));
+            if cur == start {
+                break;
+            }
+        }
+        self.vertex_edge[v] = Some(cur);
+    }
+
+    /// Drop half-edge `e`, which nothing links to any more, by moving the
+    /// last half-edge into its slot.
+    fn remove_half_edge(&mut self, e: usize) {
+        let last = self.half_edges.len() - 1;
+        self.half_edges.swap_remove(e);
+        if e == last {
+            return;
+        }
+        let HalfEdge { origin, twin, next, prev, face } = self.half_edges[e];
+        self.half_edges[twin].twin = e;
+        self.half_edges[next].prev = e;
+        self.half_edges[prev].next = e;
+        if self.vertex_edge[origin] == Some(last) {
+            self.vertex_edge[origin] = Some(e);
+        }
+        if let Some(f) = face.filter(|&f| self.face_edge[f] == last) {
+            self.face_edge[f] = e;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::polygon_area;
+    use crate::Point2;
+
+    fn square() -> HalfEdgeMesh<f64> {
+        let pts = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 1.0),
+        ];
+        HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2], [0, 2, 3]]).unwrap()
+    }
+
+    fn total_area(he: &HalfEdgeMesh<f64>) -> f64 {
+        (0..he.num_faces()).map(|f| polygon_area(&he.face_points(f))).sum()
+    }
+
+    #[test]
+    fn stars_and_boundary() {
+        let he = square();
+        let mut n: Vec<_> = he.vertex_neighbors(0).collect();
+        n.sort();
+        assert_eq!(n, vec![1, 2, 3]);
+        assert_eq!(he.vertex_faces(2).count(), 2);
+        assert!(he.is_boundary_vertex(0));
+        assert_eq!(he.boundary_loops().len(), 1);
+        // the diagonal's twin is an interior half-edge
+        let d = he.find_half_edge(2, 0).unwrap();
+        assert!(he.face(d).is_some() && he.face(he.twin(d)).is_some());
+    }
+
+    #[test]
+    fn flip_and_split_keep_area_and_links() {
+        let mut he = square();
+        let d = he.find_half_edge(0, 2).unwrap();
+        assert!(he.flip_edge(d));
+        assert!(he.find_half_edge(0, 2).is_none());
+        assert!(he.find_half_edge(1, 3).is_some() || he.find_half_edge(3, 1).is_some());
+        let b = he.find_half_edge(0, 1).unwrap();
+        let m = he.split_edge(b, Point2::new(0.5, 0.0)).unwrap();
+        let d2 = he.find_half_edge(1, 3).or(he.find_half_edge(3, 1)).unwrap();
+        let c = he.split_edge(d2, Point2::new(0.5, 0.5)).unwrap();
+        assert_eq!(he.num_faces(), 5);
+        assert!((total_area(&he) - 1.0).abs() < 1e-12);
+        assert!(he.is_boundary_vertex(m) && !he.is_boundary_vertex(c));
+        assert_eq!(he.vertex_faces(c).count(), 4);
+        for (h, e) in he.half_edges.iter().enumerate() {
+            assert_eq!(he.twin(e.twin), h);
+            assert_eq!(he.prev(e.next), h);
+        }
+        assert_eq!(he.boundary_loops()[0].len(), 5);
+    }
+
+    /// 3×3 grid of nodes `j * 3 + i` at `(i, j)`, two triangles per square.
+    fn grid() -> HalfEdgeMesh<f64> {
+        let pts = (0..9).map(|n| Point2::new((n % 3) as f64, (n / 3) as f64)).collect();
+        let mut tris = Vec::new();
+        for n in [0, 1, 3, 4] {
+            tris.push([n, n + 1, n + 4]);
+            tris.push([n, n + 4, n + 3]);
+        }
+        HalfEdgeMesh::from_triangles(pts, &tris).unwrap()
+    }
+
+    fn assert_consistent(he: &HalfEdgeMesh<f64>) {
+        for (h, e) in he.half_edges.iter().enumerate() {
+            assert_eq!(he.twin(e.twin), h);
+            assert_eq!(he.prev(e.next), h);
+            assert_eq!(he.origin(e.next), he.dest(h));
+            assert_eq!(he.face(e.next), e.face);
+        }
+        for v in 0..he.num_vertices() {
+            assert!(he.outgoing(v).all(|h| he.origin(h) == v));
+        }
+    }
+
+    #[test]
+    fn flip_rejects_concave_and_existing_edges() {
+        // quad 0, 3, 1, 2 is reflex at node 1
+        let pts = vec![Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(1.0, 1.0), Point2::new(4.0, -1.0)];
+        let mut he = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2], [1, 0, 3]]).unwrap();
+        let before = he.clone();
+        assert!(!he.flip_edge(he.find_half_edge(0, 1).unwrap()));
+        assert_eq!(he, before);
+        // fan around node 3: flipping 0–3 would duplicate edge 1–2
+        let pts = vec![Point2::new(0.0, 0.0), Point2::new(2.0, 0.0), Point2::new(1.0, 2.0), Point2::new(1.0, 0.5)];
+        let mut he = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 3], [1, 2, 3], [2, 0, 3]]).unwrap();
+        let before = he.clone();
+        assert!(!he.flip_edge(he.find_half_edge(0, 3).unwrap()));
+        assert_eq!(he, before);
+    }
+
+    #[test]
+    fn collapse_interior_and_boundary_edges() {
+        // interior edge 1–4: the centre node merges into boundary node 1
+        let mut he = grid();
+        let v = he.collapse_edge(he.find_half_edge(1, 4).unwrap(), Point2::new(1.0, 0.0)).unwrap();
+        assert_eq!(v, 1);
+        assert_eq!((he.num_vertices(), he.num_faces()), (8, 6));
+        assert_consistent(&he);
+        assert!((total_area(&he) - 4.0).abs() < 1e-12);
+        assert_eq!(he.boundary_loops()[0].len(), 8);
+        assert_eq!(he.vertex_faces(v).count(), 5);
+
+        // boundary edge 0–1 keeps node 0 at the corner
+        let mut he = grid();
+        let v = he.collapse_edge(he.find_half_edge(0, 1).unwrap(), Point2::new(0.0, 0.0)).unwrap();
+        assert_eq!((v, he.num_vertices(), he.num_faces()), (0, 8, 7));
+        assert_consistent(&he);
+        assert!((total_area(&he) - 4.0).abs() < 1e-12);
+        assert_eq!(he.boundary_loops()[0].len(), 7);
+        assert!(he.is_boundary_vertex(v));
+    }
+
+    #[test]
+    fn collapse_rejects_pinches_and_inversions() {
+        let mut he = grid();
+        let before = he.clone();
+        // interior edge between two boundary nodes would pinch the domain
+        assert_eq!(he.collapse_edge(he.find_half_edge(1, 5).unwrap(), Point2::new(1.5, 0.5)), None);
+        // moving the merged node past the far side inverts triangles
+        assert_eq!(he.collapse_edge(he.find_half_edge(1, 4).unwrap(), Point2::new(1.0, 2.5)), None);
+        assert_eq!(he, before);
+        // a lone triangle has no collapsible edge
+        let pts = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(0.0, 1.0)];
+        let mut tri = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2]]).unwrap();
+        assert_eq!(tri.collapse_edge(0, Point2::new(0.5, 0.0)), None);
+    }
+
+    #[test]
+    fn rejects_non_manifold_edge() {
+        let pts = vec![Point2::new(0.0_f64, 0.0); 5];
+        let err = HalfEdgeMesh::from_triangles(pts, &[[0, 1, 2], [0, 1, 3]]).unwrap_err();
+        assert_eq!(err, HalfEdgeError::NonManifoldEdge(0, 1));
+    }
+
+    #[test]
+    fn rejects_bad_indices_and_degenerate_faces() {
+        let pts = vec![Point2::new(0.0_f64, 0.0); 4];
+        let err = HalfEdgeMesh::from_triangles(pts.clone(), &[[0, 1, 2], [1, 4, 2]]).unwrap_err();
+        assert_eq!(err, HalfEdgeError::VertexOutOfRange(4));
+        let faces: [&[usize]; 3] = [&[0, 1, 2], &[2, 3], &[]];
+        assert_eq!(HalfEdgeMesh::from_polygons(pts.clone(), &faces).unwrap_err(), HalfEdgeError::DegenerateFace(1));
+        let err = HalfEdgeMesh::from_polygons(pts, &[vec![0, 1, 2, 1]]).unwrap_err();
+        assert_eq!(err, HalfEdgeError::DegenerateFace(0));
+    }
+}
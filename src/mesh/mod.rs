@@ -0,0 +1,445 @@
+//! Unstructured mesh containers with mixed-cell connectivity.
+//!
+//! [`Mesh2`] and [`Mesh3`] hold node coordinates plus a list of [`Cell`]s
+//! referencing nodes by index. Node ordering within each cell follows the
+//! VTK conventions:
+//!
+//! - `Triangle`, `Quad`, `Polygon`: counter-clockwise around the cell;
+//! - `Tetrahedron`: `(0,1,2)` counter-clockwise when seen from node 3;
+//! - `Pyramid`: base quad `(0,1,2,3)` counter-clockwise when seen from apex 4;
+//! - `Wedge`: triangle `(0,1,2)` facing away from triangle `(3,4,5)`, with
+//!   node `i + 3` above node `i`;
+//! - `Hexahedron`: bottom `(0,1,2,3)` counter-clockwise when seen from the
+//!   top `(4,5,6,7)`, node `i + 4` above node `i` (as in `hexahedron_volume`);
+//! - `Polyhedron`: faces counter-clockwise when seen from outside.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, triangle_area};
+//! use mesh_geometry::mesh::{Cell, CellPoints, Mesh3};
+//!
+//! let mut mesh = Mesh3::new();
+//! for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+//!     mesh.add_node(Point3::new(x, y, 0.0));
+//! }
+//! mesh.add_cell(Cell::Triangle([0, 1, 2]));
+//! mesh.add_cell(Cell::Triangle([0, 2, 3]));
+//!
+//! let total: f64 = mesh
+//!     .cell_points()
+//!     .map(|cell| match cell {
+//!         CellPoints::Triangle([a, b, c]) => triangle_area(a, b, c),
+//!         _ => 0.0,
+//!     })
+//!     .sum();
+//! assert!((total - 1.0).abs() < 1e-12);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Float, Point2, Point3, PointN};
+use crate::utils::weld::weld_vertices;
+
+/// Face/edge adjacency for finite-volume connectivity.
+pub mod topology;
+pub use topology::Topology;
+
+/// Precomputed finite-volume geometry.
+pub mod fv_geometry;
+pub use fv_geometry::{FvGeometry, FvGeometryError};
+
+/// Half-edge data structure for 2D triangulations.
+pub mod halfedge;
+pub use halfedge::{HalfEdge, HalfEdgeError, HalfEdgeMesh};
+
+/// Mesh validity checks.
+pub mod validity;
+pub use validity::{FaceIssue, ValidityReport};
+
+/// Boundary loops and patches.
+pub mod boundary;
+pub use boundary::{BoundaryLoop, BoundaryPatch, LoopKind};
+
+/// Outward faces of a tetrahedron, as local node positions.
+pub const TETRAHEDRON_FACES: [&[usize]; 4] = [&[0, 1, 3], &[1, 2, 3], &[2, 0, 3], &[0, 2, 1]];
+
+/// Outward faces of a pyramid, as local node positions.
+pub const PYRAMID_FACES: [&[usize]; 5] = [&[0, 3, 2, 1], &[0, 1, 4], &[1, 2, 4], &[2, 3, 4], &[3, 0, 4]];
+
+/// Outward faces of a wedge, as local node positions.
+pub const WEDGE_FACES: [&[usize]; 5] = [&[0, 1, 2], &[3, 5, 4], &[0, 3, 4, 1], &[1, 4, 5, 2], &[2, 5, 3, 0]];
+
+/// Outward faces of a hexahedron, as local node positions.
+pub const HEXAHEDRON_FACES: [&[usize]; 6] = [
+    &[0, 4, 7, 3],
+    &[1, 2, 6, 5],
+    &[0, 1, 5, 4],
+    &[3, 7, 6, 2],
+    &[0, 3, 2, 1],
+    &[4, 5, 6, 7],
+];
+
+/// Cell shape, independent of connectivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellType {
+    /// 3-node triangle
+    Triangle,
+    /// 4-node quadrilateral
+    Quad,
+    /// Polygon with any number of nodes
+    Polygon,
+    /// 4-node tetrahedron
+    Tetrahedron,
+    /// 5-node pyramid
+    Pyramid,
+    /// 6-node wedge (triangular prism)
+    Wedge,
+    /// 8-node hexahedron
+    Hexahedron,
+    /// Polyhedron given by its faces
+    Polyhedron,
+}
+
+impl CellType {
+    /// Topological dimension: 2 for surface cells, 3 for volume cells.
+    pub fn dim(self) -> usize {
+        match self {
+            CellType::Triangle | CellType::Quad | CellType::Polygon => 2,
+            _ => 3,
+        }
+    }
+}
+
+/// One mesh cell, as indices into the mesh's node array.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// Triangle nodes
+    Triangle([usize; 3]),
+    /// Quadrilateral nodes
+    Quad([usize; 4]),
+    /// Polygon nodes in order
+    Polygon(Vec<usize>),
+    /// Tetrahedron nodes
+    Tetrahedron([usize; 4]),
+    /// Pyramid nodes, base quad first then apex
+    Pyramid([usize; 5]),
+    /// Wedge nodes, bottom triangle first then top
+    Wedge([usize; 6]),
+    /// Hexahedron nodes, bottom quad first then top
+    Hexahedron([usize; 8]),
+    /// Polyhedron: its distinct `nodes`, and `faces` as positions in `nodes`
+    Polyhedron {
+        /// Distinct node indices of the cell
+        nodes: Vec<usize>,
+        /// Faces as local indices into `nodes`, outward oriented
+        faces: Vec<Vec<usize>>,
+    },
+}
+
+impl Cell {
+    /// Build a polyhedron from faces given as global node indices.
+    pub fn polyhedron(faces: &[Vec<usize>]) -> Cell {
+        let mut nodes: Vec<usize> = Vec::new();
+        let local = faces
+            .iter()
+            .map(|f| {
+                f.iter()
+                    .map(|&n| match nodes.iter().position(|&m| m == n) {
+                        Some(i) => i,
+                        None => {
+                            nodes.push(n);
+                            nodes.len() - 1
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Cell::Polyhedron { nodes, faces: local }
+    }
+
+    /// Shape of this cell.
+    pub fn cell_type(&self) -> CellType {
+        match self {
+            Cell::Triangle(_) => CellType::Triangle,
+            Cell::Quad(_) => CellType::Quad,
+            Cell::Polygon(_) => CellType::Polygon,
+            Cell::Tetrahedron(_) => CellType::Tetrahedron,
+            Cell::Pyramid(_) => CellType::Pyramid,
+            Cell::Wedge(_) => CellType::Wedge,
+            Cell::Hexahedron(_) => CellType::Hexahedron,
+            Cell::Polyhedron { .. } => CellType::Polyhedron,
+        }
+    }
+
+    /// Boundary facets as global node indices, oriented outward: the faces of
+    /// a volume cell, or the edges of a surface cell (with the cell on the left).
+    pub fn faces(&self) -> Vec<Vec<usize>> {
+        let pick = |n: &[usize], lists: &[&[usize]]| -> Vec<Vec<usize>> {
+            lists.iter().map(|f| f.iter().map(|&i| n[i]).collect()).collect()
+        };
+        match self {
+            Cell::Triangle(_) | Cell::Quad(_) | Cell::Polygon(_) => {
+                let n = self.nodes();
+                (0..n.len()).map(|i| vec![n[i], n[(i + 1) % n.len()]]).collect()
+            }
+            Cell::Tetrahedron(n) => pick(n, &TETRAHEDRON_FACES),
+            Cell::Pyramid(n) => pick(n, &PYRAMID_FACES),
+            Cell::Wedge(n) => pick(n, &WEDGE_FACES),
+            Cell::Hexahedron(n) => pick(n, &HEXAHEDRON_FACES),
+            Cell::Polyhedron { nodes, faces } => faces
+                .iter()
+                .map(|f| f.iter().map(|&i| nodes[i]).collect())
+                .collect(),
+        }
+    }
+
+    /// Node indices of the cell (distinct nodes for polyhedra).
+    pub fn nodes(&self) -> &[usize] {
+        match self {
+            Cell::Triangle(n) => n,
+            Cell::Quad(n) => n,
+            Cell::Polygon(n) => n,
+            Cell::Tetrahedron(n) => n,
+            Cell::Pyramid(n) => n,
+            Cell::Wedge(n) => n,
+            Cell::Hexahedron(n) => n,
+            Cell::Polyhedron { nodes, .. } => nodes,
+        }
+    }
+
+    /// Mutable node indices of the cell, for renumbering.
+    pub fn nodes_mut(&mut self) -> &mut [usize] {
+        match self {
+            Cell::Triangle(n) => n,
+            Cell::Quad(n) => n,
+            Cell::Polygon(n) => n,
+            Cell::Tetrahedron(n) => n,
+            Cell::Pyramid(n) => n,
+            Cell::Wedge(n) => n,
+            Cell::Hexahedron(n) => n,
+            Cell::Polyhedron { nodes, .. } => nodes,
+        }
+    }
+}
+
+/// Coordinates of one cell's nodes, shaped for the `metrics` functions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellPoints<'a, P> {
+    /// Triangle corners
+    Triangle([P; 3]),
+    /// Quadrilateral corners
+    Quad([P; 4]),
+    /// Polygon corners in order
+    Polygon(Vec<P>),
+    /// Tetrahedron corners
+    Tetrahedron([P; 4]),
+    /// Pyramid corners
+    Pyramid([P; 5]),
+    /// Wedge corners
+    Wedge([P; 6]),
+    /// Hexahedron corners
+    Hexahedron([P; 8]),
+    /// Polyhedron vertices and faces as indices into them
+    Polyhedron(Vec<P>, &'a [Vec<usize>]),
+}
+
+impl<P> CellPoints<'_, P> {
+    /// All node coordinates of the cell.
+    pub fn points(&self) -> &[P] {
+        match self {
+            CellPoints::Triangle(p) => p,
+            CellPoints::Quad(p) => p,
+            CellPoints::Polygon(p) => p,
+            CellPoints::Tetrahedron(p) => p,
+            CellPoints::Pyramid(p) => p,
+            CellPoints::Wedge(p) => p,
+            CellPoints::Hexahedron(p) => p,
+            CellPoints::Polyhedron(p, _) => p,
+        }
+    }
+}
+
+/// Unstructured mesh over node type `P`; see [`Mesh2`] and [`Mesh3`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mesh<P> {
+    /// Node coordinates
+    pub nodes: Vec<P>,
+    /// Cell connectivity
+    pub cells: Vec<Cell>,
+}
+
+/// 2D unstructured mesh (triangles, quads, polygons).
+pub type Mesh2<T> = Mesh<Point2<T>>;
+
+/// 3D unstructured mesh (any cell type, including surface cells).
+pub type Mesh3<T> = Mesh<Point3<T>>;
+
+impl<P: Copy> Default for Mesh<P> {
+    fn default() -> Self {
+        Mesh { nodes: Vec::new(), cells: Vec::new() }
+    }
+}
+
+impl<P: Copy> Mesh<P> {
+    /// Empty mesh
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct from nodes and cells
+    pub fn from_parts(nodes: Vec<P>, cells: Vec<Cell>) -> Self {
+        Mesh { nodes, cells }
+    }
+
+    /// Append a node, returning its index
+    pub fn add_node(&mut self, p: P) -> usize {
+        self.nodes.push(p);
+        self.nodes.len() - 1
+    }
+
+    /// Append a cell, returning its index
+    pub fn add_cell(&mut self, cell: Cell) -> usize {
+        self.cells.push(cell);
+        self.cells.len() - 1
+    }
+
+    /// Number of nodes
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of cells
+    pub fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Coordinates of cell `i`'s nodes.
+    pub fn cell_points_of(&self, i: usize) -> CellPoints<'_, P> {
+        let at = |n: &[usize]| -> Vec<P> { n.iter().map(|&k| self.nodes[k]).collect() };
+        let nd = &self.nodes;
+        match &self.cells[i] {
+            Cell::Triangle(n) => CellPoints::Triangle(n.map(|k| nd[k])),
+            Cell::Quad(n) => CellPoints::Quad(n.map(|k| nd[k])),
+            Cell::Polygon(n) => CellPoints::Polygon(at(n)),
+            Cell::Tetrahedron(n) => CellPoints::Tetrahedron(n.map(|k| nd[k])),
+            Cell::Pyramid(n) => CellPoints::Pyramid(n.map(|k| nd[k])),
+            Cell::Wedge(n) => CellPoints::Wedge(n.map(|k| nd[k])),
+            Cell::Hexahedron(n) => CellPoints::Hexahedron(n.map(|k| nd[k])),
+            Cell::Polyhedron { nodes, faces } => CellPoints::Polyhedron(at(nodes), faces),
+        }
+    }
+
+    /// Build face/edge adjacency for the mesh's cells.
+    pub fn topology(&self) -> Topology {
+        Topology::from_cells(&self.cells)
+    }
+
+    /// Iterate over the node coordinates of every cell, in cell order.
+    pub fn cell_points(&self) -> impl Iterator<Item = CellPoints<'_, P>> + '_ {
+        (0..self.cells.len()).map(move |i| self.cell_points_of(i))
+    }
+}
+
+impl<T: Float, P: PointN<Scalar = T>> Mesh<P> {
+    /// Merge nodes closer than `tol` (see [`weld_vertices`]) and renumber
+    /// the cells; returns the old→new node map.
+    pub fn weld_nodes(&mut self, tol: T) -> Vec<usize> {
+        let weld = weld_vertices(&self.nodes, tol);
+        for cell in &mut self.cells {
+            for n in cell.nodes_mut() {
+                *n = weld.remap[*n];
+            }
+        }
+        self.nodes = weld.points;
+        weld.remap
+    }
+}
+
+impl<T: Float> Mesh2<T> {
+    /// Lift to a planar [`Mesh3`] at height `z`, for the `Point3` metrics.
+    pub fn to_mesh3(&self, z: T) -> Mesh3<T> {
+        Mesh {
+            nodes: self.nodes.iter().map(|p| Point3::new(p.x, p.y, z)).collect(),
+            cells: self.cells.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{hexahedron_volume, polygon_area, polyhedron_volume};
+    use crate::{Point2, Point3};
+
+    #[test]
+    fn mixed_2d_cells() {
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 1.0),
+        ];
+        let cells = vec![
+            Cell::Quad([0, 1, 4, 5]),
+            Cell::Triangle([1, 2, 3]),
+            Cell::Triangle([1, 3, 4]),
+        ];
+        let mesh = Mesh2::from_parts(nodes, cells);
+        let area: f64 = mesh.cell_points().map(|c| polygon_area(c.points())).sum();
+        assert!((area - 2.0).abs() < 1e-12);
+        assert_eq!(mesh.cells[1].cell_type().dim(), 2);
+    }
+
+    #[test]
+    fn weld_stitches_two_triangles() {
+        // two triangles built separately, sharing the diagonal up to roundoff
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0 + 1e-14, 1.0),
+            Point2::new(0.0, 1.0),
+            Point2::new(0.0, 1e-14),
+        ];
+        let mut mesh = Mesh2::from_parts(nodes, vec![Cell::Triangle([0, 1, 2]), Cell::Triangle([5, 3, 4])]);
+        let remap = mesh.weld_nodes(1e-10);
+        assert_eq!(remap, vec![0, 1, 2, 2, 3, 0]);
+        assert_eq!(mesh.num_nodes(), 4);
+        assert_eq!(mesh.cells[1], Cell::Triangle([0, 2, 3]));
+        assert_eq!(mesh.topology().internal_faces().count(), 1);
+    }
+
+    #[test]
+    fn hex_and_polyhedron_cells() {
+        let mut mesh = Mesh3::new();
+        for k in 0..2 {
+            for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.add_node(Point3::new(x, y, k as f64));
+            }
+        }
+        mesh.add_cell(Cell::Hexahedron([0, 1, 2, 3, 4, 5, 6, 7]));
+        let faces = vec![
+            vec![0, 3, 2, 1],
+            vec![4, 5, 6, 7],
+            vec![0, 1, 5, 4],
+            vec![1, 2, 6, 5],
+            vec![2, 3, 7, 6],
+            vec![3, 0, 4, 7],
+        ];
+        mesh.add_cell(Cell::polyhedron(&faces));
+        let vols: Vec<f64> = mesh
+            .cell_points()
+            .map(|c| match c {
+                CellPoints::Hexahedron(v) => hexahedron_volume(v),
+                CellPoints::Polyhedron(v, f) => polyhedron_volume(&v, f),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert!((vols[0] - 1.0).abs() < 1e-12 && (vols[1] - 1.0).abs() < 1e-12);
+        assert_eq!(mesh.cells[1].nodes().len(), 8);
+    }
+}
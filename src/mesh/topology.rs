@@ -0,0 +1,167 @@
+//! Face/edge adjacency for finite-volume connectivity.
+//!
+//! [`Topology::from_cells`] turns cell–node connectivity into the classic
+//! FVM layout: a list of unique faces (edges for surface cells), each with an
+//! owner cell and an optional neighbour, plus per-cell face lists. Faces are
+//! stored as seen from their owner, i.e. oriented outward from the owner, so
+//! `face_normal` of a face points from owner to neighbour.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, face_normal};
+//! use mesh_geometry::mesh::{Cell, Mesh3};
+//!
+//! let mut mesh = Mesh3::new();
+//! for p in [(0.0_f64, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0), (1.0, 1.0, 1.0)] {
+//!     mesh.add_node(Point3::new(p.0, p.1, p.2));
+//! }
+//! mesh.add_cell(Cell::Tetrahedron([0, 1, 2, 3]));
+//! mesh.add_cell(Cell::Tetrahedron([1, 2, 3, 4]));
+//!
+//! let topo = mesh.topology();
+//! assert_eq!(topo.num_faces(), 7);
+//! assert_eq!(topo.internal_faces().count(), 1);
+//! for pts in topo.face_points(&mesh.nodes) {
+//!     assert!(face_normal(&pts).magnitude() > 0.0);
+//! }
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::mesh::Cell;
+
+/// Unique faces with owner/neighbour cells and per-cell face lists.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Topology {
+    /// Face node indices, oriented outward from the owner
+    pub faces: Vec<Vec<usize>>,
+    /// Owner cell of each face (the first cell that referenced it)
+    pub owner: Vec<usize>,
+    /// Neighbour cell of each face, `None` on the boundary
+    pub neighbour: Vec<Option<usize>>,
+    /// Faces of each cell, in the cell's local face order
+    pub cell_faces: Vec<Vec<usize>>,
+    /// Faces referenced by more than two cells
+    pub non_manifold: Vec<usize>,
+}
+
+impl Topology {
+    /// Build adjacency from a list of cells.
+    pub fn from_cells(cells: &[Cell]) -> Self {
+        let mut topo = Topology::default();
+        let mut lookup: BTreeMap<Vec<usize>, usize> = BTreeMap::new();
+        let mut uses: Vec<usize> = Vec::new();
+        for (c, cell) in cells.iter().enumerate() {
+            let mut local = Vec::new();
+            for face in cell.faces() {
+                let mut key = face.clone();
+                key.sort_unstable();
+                let f = *lookup.entry(key).or_insert_with(|| {
+                    topo.faces.push(face);
+                    topo.owner.push(c);
+                    topo.neighbour.push(None);
+                    uses.push(0);
+                    topo.faces.len() - 1
+                });
+                uses[f] += 1;
+                match uses[f] {
+                    1 => {}
+                    2 => topo.neighbour[f] = Some(c),
+                    3 => topo.non_manifold.push(f),
+                    _ => {}
+                }
+                local.push(f);
+            }
+            topo.cell_faces.push(local);
+        }
+        topo
+    }
+
+    /// Number of unique faces
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Indices of faces with both an owner and a neighbour.
+    pub fn internal_faces(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.faces.len()).filter(move |&f| self.neighbour[f].is_some())
+    }
+
+    /// Indices of faces with an owner only.
+    pub fn boundary_faces(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.faces.len()).filter(move |&f| self.neighbour[f].is_none())
+    }
+
+    /// Coordinates of face `f`'s nodes, in owner orientation.
+    pub fn face_points_of<P: Copy>(&self, f: usize, nodes: &[P]) -> Vec<P> {
+        self.faces[f].iter().map(|&n| nodes[n]).collect()
+    }
+
+    /// Iterate over the node coordinates of every face, in face order.
+    pub fn face_points<'a, P: Copy>(&'a self, nodes: &'a [P]) -> impl Iterator<Item = Vec<P>> + 'a {
+        (0..self.faces.len()).map(move |f| self.face_points_of(f, nodes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Cell, Mesh2, Mesh3};
+    use crate::metrics::face_normal;
+    use crate::{Point2, Point3, Vec3};
+
+    #[test]
+    fn quad_strip_edges() {
+        // two unit quads side by side
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 1.0),
+        ];
+        let mesh = Mesh2::from_parts(nodes, vec![Cell::Quad([0, 1, 4, 5]), Cell::Quad([1, 2, 3, 4])]);
+        let topo = mesh.topology();
+        assert_eq!(topo.num_faces(), 7);
+        assert_eq!(topo.boundary_faces().count(), 6);
+        let f = topo.internal_faces().next().unwrap();
+        assert_eq!((topo.owner[f], topo.neighbour[f]), (0, Some(1)));
+        // owner is on the left of its edge, so the edge runs upward at x = 1
+        let e = topo.face_points_of(f, &mesh.nodes);
+        assert!(e[1].y > e[0].y);
+        assert_eq!(topo.cell_faces[1].len(), 4);
+    }
+
+    #[test]
+    fn hex_pair_normals_point_to_neighbour() {
+        let mut mesh = Mesh3::new();
+        for k in 0..2 {
+            for (x, y) in [(0.0_f64, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.add_node(Point3::new(x, y, k as f64));
+            }
+        }
+        mesh.add_cell(Cell::Hexahedron([0, 1, 4, 5, 6, 7, 10, 11]));
+        mesh.add_cell(Cell::Hexahedron([1, 2, 3, 4, 7, 8, 9, 10]));
+        let topo = mesh.topology();
+        assert_eq!(topo.num_faces(), 11);
+        let f = topo.internal_faces().next().unwrap();
+        assert!(face_normal(&topo.face_points_of(f, &mesh.nodes)).x > 0.0);
+        // boundary faces close the two-cell block
+        let total = topo
+            .boundary_faces()
+            .map(|b| face_normal(&topo.face_points_of(b, &mesh.nodes)))
+            .fold(Vec3::new(0.0, 0.0, 0.0), |acc, n| acc + n);
+        assert!(total.magnitude() < 1e-12);
+    }
+
+    #[test]
+    fn three_triangles_on_one_edge_are_non_manifold() {
+        let cells = [Cell::Triangle([0, 1, 2]), Cell::Triangle([1, 0, 3]), Cell::Triangle([0, 1, 4])];
+        let topo = Topology::from_cells(&cells);
+        assert_eq!(topo.non_manifold.len(), 1);
+        assert_eq!(topo.faces[topo.non_manifold[0]].len(), 2);
+    }
+}
@@ -0,0 +1,513 @@
+//! Mesh validity checks.
+//!
+//! [`Mesh::validate`](crate::mesh::Mesh::validate) runs every check in one
+//! pass and returns a [`ValidityReport`] listing offending cells, nodes and
+//! faces:
+//!
+//! - inverted cells (negative signed area/volume) and degenerate cells;
+//! - zero-area faces (zero-length edges in 2D);
+//! - duplicate and orphan nodes;
+//! - non-manifold faces (edges in 2D) shared by more than two cells;
+//! - faces two neighbours traverse in the same direction;
+//! - polyhedra whose faces do not close;
+//! - boundary edges (2D) or faces (3D) that cross each other.
+//! - cells referring to missing nodes, which the other checks skip.
+//!
+//! Signed measures come from `face_normal`: the fan normal of a 2D cell
+//! gives its signed area, and `Σ n_f · x_f / 6` over outward faces gives a
+//! volume cell's signed volume. Surface cells in a `Mesh3` have no sign of
+//! their own: within each edge-connected patch, the cells whose orientation
+//! covers less of the patch's area are the inverted ones. Lengths are
+//! compared against `tol`, areas against `tol²` and volumes against `tol³`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::mesh::{Cell, Mesh2};
+//!
+//! let nodes = vec![
+//!     Point2::new(0.0_f64, 0.0),
+//!     Point2::new(1.0, 0.0),
+//!     Point2::new(0.0, 1.0),
+//!     Point2::new(5.0, 5.0),
+//! ];
+//! // clockwise triangle, and node 3 is unused
+//! let mesh = Mesh2::from_parts(nodes, vec![Cell::Triangle([0, 2, 1])]);
+//! let report = mesh.validate(1e-9);
+//! assert_eq!(report.inverted_cells, vec![0]);
+//! assert_eq!(report.orphan_nodes, vec![3]);
+//! assert!(!report.is_valid());
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{Float, Point2, Point3, PointN};
+use crate::mesh::{Cell, Mesh, Topology};
+use crate::metrics::{face_normal, projected_area};
+use crate::queries::{ray_intersects_triangle, Ray};
+use crate::utils::weld::PointGrid;
+
+/// A face (edge in 2D) flagged by a check, with the cells that use it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaceIssue {
+    /// Face node indices, as seen from the first cell
+    pub nodes: Vec<usize>,
+    /// Cells referencing the face
+    pub cells: Vec<usize>,
+}
+
+/// Everything [`Mesh::validate`] found wrong; empty lists mean the check passed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ValidityReport {
+    /// Cells with negative signed area/volume
+    pub inverted_cells: Vec<usize>,
+    /// Cells whose area/volume magnitude is within tolerance of zero
+    pub degenerate_cells: Vec<usize>,
+    /// Faces (edges in 2D) with zero area (length)
+    pub zero_area_faces: Vec<FaceIssue>,
+    /// Pairs of distinct nodes closer than the tolerance, lower index first
+    pub duplicate_nodes: Vec<(usize, usize)>,
+    /// Nodes not used by any cell
+    pub orphan_nodes: Vec<usize>,
+    /// Faces (edges in 2D) shared by more than two cells
+    pub non_manifold_faces: Vec<FaceIssue>,
+    /// Internal faces both neighbours traverse in the same direction
+    pub inconsistent_orientation: Vec<FaceIssue>,
+    /// Polyhedral cells whose faces do not form a closed surface
+    pub unclosed_cells: Vec<usize>,
+    /// Pairs of boundary faces (edges in 2D) that intersect away from shared nodes
+    pub boundary_intersections: Vec<(FaceIssue, FaceIssue)>,
+    /// Cells referring to a node index past the end of `nodes` (or, for
+    /// polyhedra, a local index past their node list); the other checks skip them
+    pub invalid_node_refs: Vec<usize>,
+}
+
+impl ValidityReport {
+    /// Whether no check found a problem.
+    pub fn is_valid(&self) -> bool {
+        self.inverted_cells.is_empty()
+            && self.degenerate_cells.is_empty()
+            && self.zero_area_faces.is_empty()
+            && self.duplicate_nodes.is_empty()
+            && self.orphan_nodes.is_empty()
+            && self.non_manifold_faces.is_empty()
+            && self.inconsistent_orientation.is_empty()
+            && self.unclosed_cells.is_empty()
+            && self.boundary_intersections.is_empty()
+            && self.invalid_node_refs.is_empty()
+    }
+}
+
+/// Dimension-specific geometry behind the checks.
+pub trait ValidityGeometry: PointN {
+    /// Signed area (2D cells) or volume (3D cells) of `cell`.
+    fn signed_measure(nodes: &[Self], cell: &Cell) -> Self::Scalar;
+
+    /// Area of a face, or length of an edge in 2D.
+    fn face_measure(pts: &[Self]) -> Self::Scalar;
+
+    /// Whether two boundary faces that share no node intersect.
+    fn faces_cross(a: &[Self], b: &[Self]) -> bool;
+
+    /// Cells to report as inverted although `signed_measure` cannot tell;
+    /// none by default.
+    fn flipped_cells(_nodes: &[Self], cells: &[Cell], _topo: &Topology) -> Vec<bool> {
+        vec![false; cells.len()]
+    }
+}
+
+impl<T: Float> ValidityGeometry for Point2<T> {
+    fn signed_measure(nodes: &[Self], cell: &Cell) -> T {
+        let pts: Vec<_> = cell.nodes().iter().map(|&n| Point3::new(nodes[n].x, nodes[n].y, T::zero())).collect();
+        face_normal(&pts).z * T::from(0.5).unwrap()
+    }
+
+    fn face_measure(pts: &[Self]) -> T {
+        (pts[1] - pts[0]).magnitude()
+    }
+
+    fn faces_cross(a: &[Self], b: &[Self]) -> bool {
+        let orient = |p: Point2<T>, q: Point2<T>, r: Point2<T>| (q - p).cross(r - p);
+        let (p, q, r, s) = (a[0], a[1], b[0], b[1]);
+        let (d1, d2) = (orient(p, q, r), orient(p, q, s));
+        let (d3, d4) = (orient(r, s, p), orient(r, s, q));
+        if d1 == T::zero() && d2 == T::zero() {
+            // collinear: the projections onto p→q must overlap
+            let dir = q - p;
+            let (t0, t1) = ((r - p).dot(dir), (s - p).dot(dir));
+            return t0.min(t1) <= dir.dot(dir) && t0.max(t1) >= T::zero();
+        }
+        d1 * d2 <= T::zero() && d3 * d4 <= T::zero()
+    }
+}
+
+impl<T: Float> ValidityGeometry for Point3<T> {
+    fn signed_measure(nodes: &[Self], cell: &Cell) -> T {
+        if cell.cell_type().dim() == 2 {
+            let pts: Vec<_> = cell.nodes().iter().map(|&n| nodes[n]).collect();
+            return projected_area(&pts);
+        }
+        let o = nodes[cell.nodes()[0]];
+        let sum = cell.faces().iter().fold(T::zero(), |acc, f| {
+            let pts: Vec<_> = f.iter().map(|&n| nodes[n]).collect();
+            acc + face_normal(&pts).dot(pts[0] - o)
+        });
+        sum / T::from(6.0).unwrap()
+    }
+
+    fn face_measure(pts: &[Self]) -> T {
+        if pts.len() == 2 {
+            (pts[1] - pts[0]).magnitude()
+        } else {
+            projected_area(pts)
+        }
+    }
+
+    fn faces_cross(a: &[Self], b: &[Self]) -> bool {
+        // two fanned polygons cross when an edge of one pierces a triangle of the other
+        let pierces = |edges: &[Self], tris: &[Self]| {
+            (0..edges.len()).any(|i| {
+                let (p, q) = (edges[i], edges[(i + 1) % edges.len()]);
+                let ray = Ray { origin: p, dir: q - p };
+                (1..tris.len() - 1).any(|k| {
+                    ray_intersects_triangle(ray, tris[0], tris[k], tris[k + 1])
+                        .is_some_and(|(t, _, _)| t <= T::one())
+                })
+            })
+        };
+        a.len() > 2 && b.len() > 2 && (pierces(a, b) || pierces(b, a))
+    }
+
+    fn flipped_cells(nodes: &[Self], cells: &[Cell], topo: &Topology) -> Vec<bool> {
+        let surface = |c: usize| cells[c].cell_type().dim() == 2;
+        let mut against: Vec<Option<bool>> = vec![None; cells.len()];
+        let mut flipped = vec![false; cells.len()];
+        for start in 0..cells.len() {
+            if !surface(start) || against[start].is_some() {
+                continue;
+            }
+            // walk the edge-connected patch, noting which cells run against `start`;
+            // neighbours agree when they traverse their shared edge in opposite directions
+            against[start] = Some(false);
+            let mut patch = vec![start];
+            let mut k = 0;
+            while k < patch.len() {
+                let c = patch[k];
+                k += 1;
+                let edges = cells[c].faces();
+                for (local, &f) in topo.cell_faces[c].iter().enumerate() {
+                    let Some(nb) = topo.neighbour[f] else { continue };
+                    let other = if topo.owner[f] == c { nb } else { topo.owner[f] };
+                    if other == c || !surface(other) || against[other].is_some() {
+                        continue;
+                    }
+                    let j = topo.cell_faces[other].iter().position(|&g| g == f).unwrap();
+                    let flip = same_direction(&edges[local], &cells[other].faces()[j]);
+                    against[other] = Some(against[c].unwrap() ^ flip);
+                    patch.push(other);
+                }
+            }
+            // the orientation covering less area is the inverted one (ties keep `start`'s)
+            let (mut area_with, mut area_against) = (T::zero(), T::zero());
+            for &c in &patch {
+                let a = Self::signed_measure(nodes, &cells[c]);
+                if against[c] == Some(true) {
+                    area_against = area_against + a;
+                } else {
+                    area_with = area_with + a;
+                }
+            }
+            let minority = area_against <= area_with;
+            for &c in &patch {
+                flipped[c] = against[c] == Some(minority);
+            }
+        }
+        flipped
+    }
+}
+
+impl<T: Float, P: ValidityGeometry<Scalar = T>> Mesh<P> {
+    /// Run every validity check; `tol` is the length tolerance.
+    pub fn validate(&self, tol: T) -> ValidityReport {
+        let bad_ref = |cell: &Cell| {
+            cell.nodes().iter().any(|&n| n >= self.nodes.len())
+                || matches!(cell, Cell::Polyhedron { nodes, faces } if faces.iter().flatten().any(|&i| i >= nodes.len()))
+        };
+        let (invalid, keep): (Vec<usize>, Vec<usize>) = (0..self.cells.len()).partition(|&c| bad_ref(&self.cells[c]));
+        if invalid.is_empty() {
+            return self.check(tol);
+        }
+        // check the remaining cells, then map their indices back
+        let rest = Mesh { nodes: self.nodes.clone(), cells: keep.iter().map(|&c| self.cells[c].clone()).collect() };
+        let mut report = rest.check(tol);
+        let remap = |cells: &mut Vec<usize>| cells.iter_mut().for_each(|c| *c = keep[*c]);
+        remap(&mut report.inverted_cells);
+        remap(&mut report.degenerate_cells);
+        remap(&mut report.unclosed_cells);
+        let issues = report.zero_area_faces.iter_mut().chain(&mut report.non_manifold_faces);
+        let issues = issues.chain(&mut report.inconsistent_orientation);
+        let pairs = report.boundary_intersections.iter_mut().flat_map(|(a, b)| [a, b]);
+        issues.chain(pairs).for_each(|issue| remap(&mut issue.cells));
+        // nodes of skipped cells are still in use
+        report.orphan_nodes.retain(|n| !invalid.iter().any(|&c| self.cells[c].nodes().contains(n)));
+        report.invalid_node_refs = invalid;
+        report
+    }
+
+    fn check(&self, tol: T) -> ValidityReport {
+        let mut report = ValidityReport::default();
+        let topo = self.topology();
+        let dim = P::DIM;
+        let tol_measure = |d: usize| (0..d).fold(T::one(), |acc, _| acc * tol);
+
+        // cells: sign and closure
+        let flipped = P::flipped_cells(&self.nodes, &self.cells, &topo);
+        let mut used = vec![false; self.nodes.len()];
+        for (c, cell) in self.cells.iter().enumerate() {
+            for &n in cell.nodes() {
+                used[n] = true;
+            }
+            if matches!(cell, Cell::Polyhedron { .. }) && !is_closed(&cell.faces()) {
+                // an open cell has no meaningful volume
+                report.unclosed_cells.push(c);
+                continue;
+            }
+            let m = P::signed_measure(&self.nodes, cell);
+            let m = if flipped[c] { -m } else { m };
+            if m.abs() <= tol_measure(cell.cell_type().dim()) {
+                report.degenerate_cells.push(c);
+            } else if m < T::zero() {
+                report.inverted_cells.push(c);
+            }
+        }
+        report.orphan_nodes = (0..used.len()).filter(|&n| !used[n]).collect();
+        report.duplicate_nodes = duplicate_nodes(&self.nodes, tol);
+
+        // faces: measure, manifoldness, orientation
+        let issue = |f: usize| FaceIssue { nodes: topo.faces[f].clone(), cells: face_cells(&topo, f) };
+        for f in 0..topo.num_faces() {
+            let pts = topo.face_points_of(f, &self.nodes);
+            if P::face_measure(&pts) <= tol_measure(pts.len().min(dim) - 1) {
+                report.zero_area_faces.push(issue(f));
+            }
+            if let Some(nb) = topo.neighbour[f] {
+                let k = topo.cell_faces[nb].iter().position(|&g| g == f).unwrap();
+                let theirs = &self.cells[nb].faces()[k];
+                if same_direction(&topo.faces[f], theirs) {
+                    report.inconsistent_orientation.push(issue(f));
+                }
+            }
+        }
+        report.non_manifold_faces = topo.non_manifold.iter().map(|&f| issue(f)).collect();
+
+        // boundary self-intersection, pruned by a sweep over x-extents
+        let boundary: Vec<usize> = topo.boundary_faces().collect();
+        let mut spans: Vec<(T, T, usize)> = boundary
+            .iter()
+            .map(|&f| {
+                let xs = topo.faces[f].iter().map(|&n| self.nodes[n].coord(0));
+                let init = (T::infinity(), T::neg_infinity());
+                let (lo, hi) = xs.fold(init, |(lo, hi), x| (lo.min(x), hi.max(x)));
+                (lo, hi, f)
+            })
+            .collect();
+        spans.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        for i in 0..spans.len() {
+            let (_, hi, f) = spans[i];
+            for &(lo2, _, g) in &spans[i + 1..] {
+                if lo2 > hi {
+                    break;
+                }
+                if topo.faces[f].iter().any(|n| topo.faces[g].contains(n)) {
+                    continue;
+                }
+                let (a, b) = (topo.face_points_of(f, &self.nodes), topo.face_points_of(g, &self.nodes));
+                if P::faces_cross(&a, &b) {
+                    report.boundary_intersections.push((issue(f.min(g)), issue(f.max(g))));
+                }
+            }
+        }
+        report
+    }
+}
+
+/// All cells whose face lists reference face `f`.
+fn face_cells(topo: &Topology, f: usize) -> Vec<usize> {
+    (0..topo.cell_faces.len()).filter(|&c| topo.cell_faces[c].contains(&f)).collect()
+}
+
+/// Whether face `b` runs the same way round as face `a` (same node set).
+fn same_direction(a: &[usize], b: &[usize]) -> bool {
+    if a.len() == 2 {
+        return a[0] == b[0];
+    }
+    let k = b.iter().position(|&n| n == a[0]).unwrap();
+    b[(k + 1) % b.len()] == a[1]
+}
+
+/// Whether every directed edge of `faces` is matched by exactly one reverse edge.
+fn is_closed(faces: &[Vec<usize>]) -> bool {
+    let mut count: BTreeMap<(usize, usize), i32> = BTreeMap::new();
+    for f in faces {
+        for i in 0..f.len() {
+            let (a, b) = (f[i], f[(i + 1) % f.len()]);
+            *count.entry((a, b)).or_insert(0) += 1;
+        }
+    }
+    count.iter().all(|(&(a, b), &n)| n == 1 && count.get(&(b, a)) == Some(&1))
+}
+
+/// Pairs of nodes within `tol` of each other, found through a hash grid
+/// with cell size `tol` (exact matches when `tol` is not positive).
+fn duplicate_nodes<T: Float, P: PointN<Scalar = T>>(nodes: &[P], tol: T) -> Vec<(usize, usize)> {
+    let mut out = Vec::new();
+    if tol > T::zero() {
+        let mut grid = PointGrid::new(tol);
+        for (b, p) in nodes.iter().enumerate() {
+            out.extend(grid.near(p).filter(|&a| nodes[a].distance_squared(p) <= tol * tol).map(|a| (a, b)));
+            grid.insert(p, b);
+        }
+    } else {
+        let cmp = |a: &usize, b: &usize| {
+            (0..P::DIM)
+                .map(|axis| nodes[*a].coord(axis).partial_cmp(&nodes[*b].coord(axis)).unwrap_or(Ordering::Equal))
+                .find(|o| o.is_ne())
+                .unwrap_or(Ordering::Equal)
+        };
+        let mut order: Vec<usize> = (0..nodes.len()).collect();
+        order.sort_by(cmp);
+        for run in order.chunk_by(|a, b| cmp(a, b).is_eq()) {
+            for (i, &a) in run.iter().enumerate() {
+                out.extend(run[i + 1..].iter().map(|&b| (a.min(b), a.max(b))));
+            }
+        }
+    }
+    out.sort_unstable();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidityGeometry;
+    use crate::mesh::{Cell, Mesh2, Mesh3};
+    use crate::{Point2, Point3};
+
+    #[test]
+    fn clean_2d_mesh_is_valid() {
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 1.0),
+        ];
+        let mesh = Mesh2::from_parts(nodes, vec![Cell::Triangle([0, 1, 2]), Cell::Triangle([0, 2, 3])]);
+        let r = mesh.validate(1e-9);
+        assert!(r.is_valid(), "{:?}", r);
+    }
+
+    #[test]
+    fn flags_2d_problems() {
+        let nodes = vec![
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(0.0, 1.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(3.0, 0.5),
+            Point2::new(-1.0, 0.5),
+            Point2::new(-1.0, 0.6),
+        ];
+        let cells = vec![
+            Cell::Triangle([0, 1, 2]),
+            // same orientation as cell 0 along edge 0-2
+            Cell::Triangle([2, 0, 3]),
+            // a sliver poking through the square from outside
+            Cell::Triangle([5, 7, 6]),
+        ];
+        let r = Mesh2::from_parts(nodes, cells).validate(1e-9);
+        assert_eq!(r.inverted_cells, vec![1]);
+        assert_eq!(r.duplicate_nodes, vec![(2, 4)]);
+        assert_eq!(r.orphan_nodes, vec![4]);
+        assert_eq!(r.inconsistent_orientation.len(), 1);
+        assert_eq!(r.inconsistent_orientation[0].cells, vec![0, 1]);
+        assert!(!r.boundary_intersections.is_empty());
+    }
+
+    #[test]
+    fn collinear_overlaps_and_grid_duplicates() {
+        // collinear boundary edges meet only when their spans overlap
+        let seg = |x0: f64, x1: f64| [Point2::new(x0, 0.0), Point2::new(x1, 0.0)];
+        assert!(Point2::faces_cross(&seg(0.0, 2.0), &seg(3.0, 1.0)));
+        assert!(Point2::faces_cross(&seg(0.0, 2.0), &seg(2.0, 4.0)));
+        assert!(!Point2::faces_cross(&seg(0.0, 2.0), &seg(2.5, 4.0)));
+
+        // a structured grid where every column shares an x value, plus one copy
+        let mut nodes: Vec<_> = (0..1600).map(|n| Point2::new((n / 40) as f64, (n % 40) as f64)).collect();
+        nodes.push(Point2::new(0.0, 5.0 + 1e-12));
+        let mesh = Mesh2::from_parts(nodes, Vec::new());
+        assert_eq!(mesh.validate(1e-9).duplicate_nodes, vec![(5, 1600)]);
+        assert_eq!(mesh.validate(0.0).duplicate_nodes, vec![]);
+    }
+
+    #[test]
+    fn out_of_range_nodes_are_reported() {
+        let nodes = vec![Point2::new(0.0_f64, 0.0), Point2::new(1.0, 0.0), Point2::new(0.0, 1.0), Point2::new(5.0, 5.0)];
+        let cells = vec![Cell::Triangle([3, 1, 9]), Cell::Triangle([0, 2, 1])];
+        let r = Mesh2::from_parts(nodes, cells).validate(1e-9);
+        assert_eq!(r.invalid_node_refs, vec![0]);
+        assert_eq!(r.inverted_cells, vec![1]);
+        assert!(r.orphan_nodes.is_empty());
+        assert!(!r.is_valid());
+    }
+
+    #[test]
+    fn flipped_surface_cell_in_3d() {
+        let nodes = vec![
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.2),
+            Point3::new(0.0, 1.0, 0.2),
+            Point3::new(2.0, 0.0, 0.5),
+            Point3::new(2.0, 1.0, 0.7),
+        ];
+        let cells = vec![
+            Cell::Triangle([0, 1, 2]),
+            Cell::Triangle([0, 2, 3]),
+            Cell::Triangle([1, 4, 5]),
+            // reversed relative to the other three
+            Cell::Triangle([1, 2, 5]),
+        ];
+        let mut mesh = Mesh3::from_parts(nodes, cells);
+        let r = mesh.validate(1e-9);
+        assert_eq!(r.inverted_cells, vec![3]);
+        mesh.cells.truncate(3);
+        assert!(mesh.validate(1e-9).inverted_cells.is_empty());
+    }
+
+    #[test]
+    fn flags_3d_problems() {
+        let nodes = vec![
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(0.5, 0.5, 0.0),
+        ];
+        let open_box = Cell::polyhedron(&[vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2]]);
+        let cells = vec![
+            Cell::Tetrahedron([0, 1, 2, 3]),
+            Cell::Tetrahedron([0, 2, 1, 3]),
+            Cell::Tetrahedron([0, 1, 4, 2]),
+            open_box,
+        ];
+        let r = Mesh3::from_parts(nodes, cells).validate(1e-9);
+        assert_eq!(r.inverted_cells, vec![1]);
+        assert_eq!(r.degenerate_cells, vec![2]);
+        assert_eq!(r.unclosed_cells, vec![3]);
+        assert!(!r.non_manifold_faces.is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+//! Triangle and tetrahedron centers: circumcenter, incenter, orthocenter.
+//!
+//! Unstructured C-grid models (Delaunay–Voronoi staggering) place velocity
+//! points at triangle circumcenters, which is only orthogonal when the
+//! circumcenter lies inside the cell. The circumcenter functions therefore
+//! return a [`Circumcenter`] carrying an `inside` flag decided from the signs
+//! of its barycentric weights rather than from the computed center.
+//!
+//! Functions suffixed `_2d` take `Point2`; the others take `Point3`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point2, metrics::centers::triangle_circumcenter_2d};
+//! let cc = triangle_circumcenter_2d(
+//!     Point2::new(0.0_f64, 0.0),
+//!     Point2::new(2.0, 0.0),
+//!     Point2::new(0.0, 2.0),
+//! );
+//! assert_eq!(cc.center, Point2::new(1.0, 1.0));
+//! // right triangle: the circumcenter sits on the hypotenuse
+//! assert!(!cc.inside);
+//! ```
+
+use crate::{Float, Point2, Point3, Vec3};
+use crate::metrics::triangle_area;
+
+/// Circumscribed circle or sphere of a cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circumcenter<P, T: Float> {
+    /// Circumcenter
+    pub center: P,
+    /// Circumradius
+    pub radius: T,
+    /// `true` when the center lies strictly inside the cell
+    pub inside: bool,
+}
+
+/// Whether the triangle circumcenter lies strictly inside, from the
+/// barycentric weights `l_a²(l_b² + l_c² − l_a²)` etc. (squared edge lengths
+/// opposite each vertex).
+fn triangle_circumcenter_inside<T: Float>(la2: T, lb2: T, lc2: T) -> bool {
+    la2 * (lb2 + lc2 - la2) > T::zero()
+        && lb2 * (lc2 + la2 - lb2) > T::zero()
+        && lc2 * (la2 + lb2 - lc2) > T::zero()
+}
+
+/// Circumcenter and circumradius of 2D triangle (A, B, C).
+pub fn triangle_circumcenter_2d<T: Float>(
+    a: Point2<T>,
+    b: Point2<T>,
+    c: Point2<T>,
+) -> Circumcenter<Point2<T>, T> {
+    let (ab, ac) = (b - a, c - a);
+    let (ab2, ac2) = (ab.dot(ab), ac.dot(ac));
+    let d = T::from(2.0).unwrap() * ab.cross(ac);
+    let ux = (ac.y * ab2 - ab.y * ac2) / d;
+    let uy = (ab.x * ac2 - ac.x * ab2) / d;
+    let center = Point2::new(a.x + ux, a.y + uy);
+    let bc = c - b;
+    Circumcenter {
+        center,
+        radius: (ux * ux + uy * uy).sqrt(),
+        inside: triangle_circumcenter_inside(bc.dot(bc), ac2, ab2),
+    }
+}
+
+/// Circumcenter and circumradius of triangle (A, B, C) in 3D.
+pub fn triangle_circumcenter<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+) -> Circumcenter<Point3<T>, T> {
+    let (ab, ac) = (b - a, c - a);
+    let (ab2, ac2) = (ab.dot(ab), ac.dot(ac));
+    let n = ab.cross(ac);
+    let u = (n.cross(ab) * ac2 + ac.cross(n) * ab2) * (T::one() / (T::from(2.0).unwrap() * n.dot(n)));
+    let bc = c - b;
+    Circumcenter {
+        center: a + u,
+        radius: u.magnitude(),
+        inside: triangle_circumcenter_inside(bc.dot(bc), ac2, ab2),
+    }
+}
+
+/// Incenter and inradius of 2D triangle (A, B, C).
+pub fn triangle_incenter_2d<T: Float>(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> (Point2<T>, T) {
+    let (la, lb, lc) = ((c - b).magnitude(), (a - c).magnitude(), (b - a).magnitude());
+    let p = la + lb + lc;
+    let center = Point2::new(
+        (a.x * la + b.x * lb + c.x * lc) / p,
+        (a.y * la + b.y * lb + c.y * lc) / p,
+    );
+    (center, (b - a).cross(c - a).abs() / p)
+}
+
+/// Incenter and inradius of triangle (A, B, C) in 3D.
+pub fn triangle_incenter<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> (Point3<T>, T) {
+    let (la, lb, lc) = ((c - b).magnitude(), (a - c).magnitude(), (b - a).magnitude());
+    let p = la + lb + lc;
+    let center = Point3::new(
+        (a.x * la + b.x * lb + c.x * lc) / p,
+        (a.y * la + b.y * lb + c.y * lc) / p,
+        (a.z * la + b.z * lb + c.z * lc) / p,
+    );
+    (center, T::from(2.0).unwrap() * triangle_area(a, b, c) / p)
+}
+
+/// Orthocenter of 2D triangle (A, B, C), via the Euler line `H = A + B + C − 2O`.
+pub fn triangle_orthocenter_2d<T: Float>(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> Point2<T> {
+    let o = triangle_circumcenter_2d(a, b, c).center;
+    let two = T::from(2.0).unwrap();
+    Point2::new(a.x + b.x + c.x - two * o.x, a.y + b.y + c.y - two * o.y)
+}
+
+/// Orthocenter of triangle (A, B, C) in 3D, via the Euler line `H = A + B + C − 2O`.
+pub fn triangle_orthocenter<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> Point3<T> {
+    let o = triangle_circumcenter(a, b, c).center;
+    let two = T::from(2.0).unwrap();
+    Point3::new(
+        a.x + b.x + c.x - two * o.x,
+        a.y + b.y + c.y - two * o.y,
+        a.z + b.z + c.z - two * o.z,
+    )
+}
+
+/// Six times the signed volume of tetrahedron (A, B, C, D).
+fn signed_volume6<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>, d: Point3<T>) -> T {
+    (b - a).cross(c - a).dot(d - a)
+}
+
+/// Barycentric weight of vertex `o + u` for the circumcenter of the tet
+/// spanned by edges `u`, `v`, `w` from `o`, up to a positive factor, in
+/// terms of edge dot products only.
+fn tetrahedron_circumcenter_weight<T: Float>(u: Vec3<T>, v: Vec3<T>, w: Vec3<T>) -> T {
+    let (uu, vv, ww) = (u.dot(u), v.dot(v), w.dot(w));
+    let (uv, vw, wu) = (u.dot(v), v.dot(w), w.dot(u));
+    // (v×w)·(v×w), (w×u)·(v×w) and (u×v)·(v×w) by the Lagrange identity
+    (vv * ww - vw * vw) * uu + (vw * wu - ww * uv) * vv + (uv * vw - wu * vv) * ww
+}
+
+/// Circumsphere of tetrahedron (A, B, C, D).
+pub fn tetrahedron_circumsphere<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> Circumcenter<Point3<T>, T> {
+    let (u, v, w) = (b - a, c - a, d - a);
+    let det = u.dot(v.cross(w));
+    let num = v.cross(w) * u.dot(u) + w.cross(u) * v.dot(v) + u.cross(v) * w.dot(w);
+    let off = num * (T::one() / (T::from(2.0).unwrap() * det));
+    let weights = [
+        tetrahedron_circumcenter_weight(a - b, c - b, d - b),
+        tetrahedron_circumcenter_weight(u, v, w),
+        tetrahedron_circumcenter_weight(v, w, u),
+        tetrahedron_circumcenter_weight(w, u, v),
+    ];
+    let inside = weights.iter().all(|&x| x > T::zero());
+    Circumcenter { center: a + off, radius: off.magnitude(), inside }
+}
+
+/// Insphere center and radius of tetrahedron (A, B, C, D): vertices weighted
+/// by the area of the opposite face, `r = 3V / ΣA`.
+pub fn tetrahedron_insphere<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> (Point3<T>, T) {
+    let (wa, wb, wc, wd) = (
+        triangle_area(b, c, d),
+        triangle_area(a, c, d),
+        triangle_area(a, b, d),
+        triangle_area(a, b, c),
+    );
+    let sum = wa + wb + wc + wd;
+    let center = Point3::new(
+        (a.x * wa + b.x * wb + c.x * wc + d.x * wd) / sum,
+        (a.y * wa + b.y * wb + c.y * wc + d.y * wd) / sum,
+        (a.z * wa + b.z * wb + c.z * wc + d.z * wd) / sum,
+    );
+    let vol = signed_volume6(a, b, c, d).abs() / T::from(6.0).unwrap();
+    (center, T::from(3.0).unwrap() * vol / sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point2, Point3};
+
+    #[test]
+    fn acute_vs_obtuse_circumcenter_flag() {
+        let acute = triangle_circumcenter_2d(
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(1.0, 1.5),
+        );
+        assert!(acute.inside);
+        let obtuse = triangle_circumcenter_2d(
+            Point2::new(0.0_f64, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(2.0, 0.5),
+        );
+        assert!(!obtuse.inside);
+        assert!(obtuse.center.y < 0.0);
+        // 3D version agrees with the planar one
+        let cc3 = triangle_circumcenter(
+            Point3::new(0.0_f64, 0.0, 1.0),
+            Point3::new(4.0, 0.0, 1.0),
+            Point3::new(2.0, 0.5, 1.0),
+        );
+        assert!((cc3.center.y - obtuse.center.y).abs() < 1e-12);
+        assert!((cc3.radius - obtuse.radius).abs() < 1e-12);
+        assert!(!cc3.inside);
+    }
+
+    #[test]
+    fn triangle_incenter_and_orthocenter() {
+        let (a, b, c) = (Point2::new(0.0_f64, 0.0), Point2::new(3.0, 0.0), Point2::new(0.0, 4.0));
+        let (ic, r) = triangle_incenter_2d(a, b, c);
+        // 3-4-5 triangle: r = (3 + 4 - 5) / 2 = 1
+        assert!((r - 1.0).abs() < 1e-12);
+        assert!((ic.x - 1.0).abs() < 1e-12 && (ic.y - 1.0).abs() < 1e-12);
+        // right angle at A: orthocenter is A
+        let h = triangle_orthocenter_2d(a, b, c);
+        assert!(h.x.abs() < 1e-12 && h.y.abs() < 1e-12);
+    }
+
+    #[test]
+    fn tet_spheres() {
+        let a = Point3::new(0.0_f64, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+        let d = Point3::new(0.0, 0.0, 1.0);
+        let cs = tetrahedron_circumsphere(a, b, c, d);
+        assert!((cs.center.x - 0.5).abs() < 1e-12);
+        assert!((cs.radius - 0.75_f64.sqrt()).abs() < 1e-12);
+        // the corner tet's circumcenter lies on its hypotenuse face's far side
+        assert!(!cs.inside);
+        let (ic, r) = tetrahedron_insphere(a, b, c, d);
+        let expected = 1.0 / (3.0 + 3.0_f64.sqrt());
+        assert!((r - expected).abs() < 1e-12);
+        assert!((ic.x - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tet_circumcenter_on_a_face_is_not_inside() {
+        // right angle at A in face ABC: the circumcenter is the hypotenuse
+        // midpoint (3, 4, 0), straight below D
+        let a = Point3::new(0.0_f64, 0.0, 0.0);
+        let b = Point3::new(6.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 8.0, 0.0);
+        let d = Point3::new(3.0, 4.0, 5.0);
+        let cs = tetrahedron_circumsphere(a, b, c, d);
+        assert!((cs.center - Point3::new(3.0, 4.0, 0.0)).magnitude() < 1e-12);
+        assert!(!cs.inside);
+        let [p, q, r, s] = [[1.0, 1.0, 1.0], [1.0, -1.0, -1.0], [-1.0, 1.0, -1.0], [-1.0, -1.0, 1.0]]
+            .map(|[x, y, z]| Point3::new(x, y, z));
+        assert!(tetrahedron_circumsphere(p, q, r, s).inside);
+    }
+}
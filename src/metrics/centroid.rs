@@ -1,4 +1,7 @@
-use crate::{Float, Point3};
+use alloc::vec::Vec;
+
+use crate::{Float, Point2, Point3};
+use crate::metrics::{face_normal, polygon_centroid};
 
 /// Centroid of triangle = (A + B + C) / 3.
 pub fn triangle_centroid<T: Float>(
@@ -56,6 +59,39 @@ pub fn hexahedron_centroid<T: Float>(verts: [Point3<T>;8]) -> Point3<T> {
     Point3::new(sum.x*inv8, sum.y*inv8, sum.z*inv8)
 }
 
+/// Centroid of a planar face in 3D with vertices in order.
+/// Triangles and quads use `triangle_centroid`/`quad_centroid`; larger
+/// polygons are projected onto the coordinate plane most aligned with the
+/// face normal, where `polygon_centroid` is exact, and lifted back.
+pub fn face_centroid<T: Float>(verts: &[Point3<T>]) -> Point3<T> {
+    match verts.len() {
+        3 => triangle_centroid(verts[0], verts[1], verts[2]),
+        4 => quad_centroid(verts[0], verts[1], verts[2], verts[3]),
+        n => {
+            assert!(n >= 3, "face_centroid requires ≥3 vertices");
+            let nrm = face_normal(verts);
+            let (ax, ay, az) = (nrm.x.abs(), nrm.y.abs(), nrm.z.abs());
+            let p0 = verts[0];
+            if az >= ax && az >= ay {
+                let proj: Vec<_> = verts.iter().map(|p| Point2::new(p.x, p.y)).collect();
+                let c = polygon_centroid(&proj);
+                let z = p0.z - (nrm.x * (c.x - p0.x) + nrm.y * (c.y - p0.y)) / nrm.z;
+                Point3::new(c.x, c.y, z)
+            } else if ay >= ax {
+                let proj: Vec<_> = verts.iter().map(|p| Point2::new(p.z, p.x)).collect();
+                let c = polygon_centroid(&proj);
+                let y = p0.y - (nrm.z * (c.x - p0.z) + nrm.x * (c.y - p0.x)) / nrm.y;
+                Point3::new(c.y, y, c.x)
+            } else {
+                let proj: Vec<_> = verts.iter().map(|p| Point2::new(p.y, p.z)).collect();
+                let c = polygon_centroid(&proj);
+                let x = p0.x - (nrm.y * (c.x - p0.y) + nrm.z * (c.y - p0.z)) / nrm.x;
+                Point3::new(x, c.x, c.y)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,4 +105,22 @@ mod tests {
         let cen = triangle_centroid(a,b,c);
         assert_eq!(cen, Point3::new(1.0/3.0,1.0/3.0,0.0));
     }
+
+    #[test]
+    fn pentagon_face_centroid_tilted() {
+        // unit square with a roof apex, lying in the plane x = 2
+        let verts = [
+            Point3::new(2.0_f64, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 0.0),
+            Point3::new(2.0, 1.0, 1.0),
+            Point3::new(2.0, 0.5, 1.5),
+            Point3::new(2.0, 0.0, 1.0),
+        ];
+        let c = face_centroid(&verts);
+        // square (area 1, z=0.5) + triangle (area 0.25, z=7/6)
+        let zc = (0.5 + 0.25 * 7.0 / 6.0) / 1.25;
+        assert!((c.x - 2.0).abs() < 1e-12);
+        assert!((c.y - 0.5).abs() < 1e-12);
+        assert!((c.z - zc).abs() < 1e-12);
+    }
 }
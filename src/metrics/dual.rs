@@ -0,0 +1,164 @@
+//! Median-dual control volumes for vertex-centred finite volumes.
+//!
+//! Each corner of a triangle (tetrahedron) owns the part of the cell closer
+//! to it along the medians: the quadrilateral (hexahedron) bounded by edge
+//! midpoints, face centroids and the cell centroid. This module returns the
+//! corner sub-volumes and, for every edge `(i, j)`, the area vector of the
+//! dual face separating the control volumes of `i` and `j`, oriented from
+//! `i` towards `j`.
+//!
+//! Edges are ordered `(0,1), (1,2), (2,0)` for triangles and
+//! `(0,1), (0,2), (0,3), (1,2), (1,3), (2,3)` for tetrahedra.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, metrics::dual::triangle_median_dual};
+//! let d = triangle_median_dual(
+//!     Point3::new(0.0_f64, 0.0, 0.0),
+//!     Point3::new(1.0, 0.0, 0.0),
+//!     Point3::new(0.0, 1.0, 0.0),
+//! );
+//! assert_eq!(d.areas.iter().sum::<f64>(), 0.5);
+//! ```
+
+use crate::{Float, Point3, Vec3};
+use crate::metrics::{
+    face_normal, tetrahedron_centroid, tetrahedron_volume, triangle_area, triangle_centroid,
+};
+
+/// Triangle edges as vertex pairs.
+pub const TRIANGLE_EDGES: [(usize, usize); 3] = [(0, 1), (1, 2), (2, 0)];
+
+/// Tetrahedron edges as vertex pairs.
+pub const TETRAHEDRON_EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+
+/// Median-dual decomposition of a triangle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleDual<T: Float> {
+    /// Sub-control-volume area owned by each corner
+    pub areas: [T; 3],
+    /// Dual-face area vector per edge, in the triangle plane, from `i` to `j`
+    pub dual_faces: [Vec3<T>; 3],
+}
+
+/// Median-dual decomposition of a tetrahedron.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TetrahedronDual<T: Float> {
+    /// Sub-control-volume volume owned by each corner
+    pub volumes: [T; 4],
+    /// Dual-face area vector per edge, from `i` to `j`
+    pub dual_faces: [Vec3<T>; 6],
+}
+
+fn midpoint<T: Float>(a: Point3<T>, b: Point3<T>) -> Point3<T> {
+    a + (b - a) * T::from(0.5).unwrap()
+}
+
+/// Flip `v` so that it points along `dir`.
+fn orient<T: Float>(v: Vec3<T>, dir: Vec3<T>) -> Vec3<T> {
+    if v.dot(dir) < T::zero() { v * -T::one() } else { v }
+}
+
+/// Split `total` into `N` equal shares that sum exactly to `total` when
+/// added in order: the last share is `total` minus the sum of the others,
+/// which is exact since that sum lies within a factor of two of `total`.
+fn equal_shares<T: Float, const N: usize>(total: T) -> [T; N] {
+    let share = total / T::from(N).unwrap();
+    let mut out = [share; N];
+    let others = (0..N - 1).fold(T::zero(), |acc, _| acc + share);
+    out[N - 1] = total - others;
+    out
+}
+
+/// Median-dual areas and dual-face vectors of triangle (A, B, C).
+/// Works for triangles embedded in 3D; 2D meshes use `z = 0`.
+pub fn triangle_median_dual<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> TriangleDual<T> {
+    let v = [a, b, c];
+    let cen = triangle_centroid(a, b, c);
+    let n = face_normal(&v);
+    let unit = n * (T::one() / n.magnitude());
+    let mut dual_faces = [Vec3::new(T::zero(), T::zero(), T::zero()); 3];
+    for (k, &(i, j)) in TRIANGLE_EDGES.iter().enumerate() {
+        let m = midpoint(v[i], v[j]);
+        dual_faces[k] = orient((cen - m).cross(unit), v[j] - v[i]);
+    }
+    TriangleDual { areas: equal_shares(triangle_area(a, b, c)), dual_faces }
+}
+
+/// Median-dual volumes and dual-face area vectors of tetrahedron (A, B, C, D).
+pub fn tetrahedron_median_dual<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> TetrahedronDual<T> {
+    let v = [a, b, c, d];
+    let cen = tetrahedron_centroid(a, b, c, d);
+    let half = T::from(0.5).unwrap();
+    let mut dual_faces = [Vec3::new(T::zero(), T::zero(), T::zero()); 6];
+    for (k, &(i, j)) in TETRAHEDRON_EDGES.iter().enumerate() {
+        let m = midpoint(v[i], v[j]);
+        let e = v[j] - v[i];
+        // the two faces sharing edge (i, j) hold the other two vertices
+        let mut sum = Vec3::new(T::zero(), T::zero(), T::zero());
+        for o in (0..4).filter(|&o| o != i && o != j) {
+            let fc = triangle_centroid(v[i], v[j], v[o]);
+            sum = sum + orient(face_normal(&[m, fc, cen]), e) * half;
+        }
+        dual_faces[k] = sum;
+    }
+    TetrahedronDual { volumes: equal_shares(tetrahedron_volume(a, b, c, d)), dual_faces }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point3, Vec3};
+
+    fn small(v: Vec3<f64>) -> bool {
+        v.magnitude() < 1e-12
+    }
+
+    #[test]
+    fn triangle_corner_control_volume_closes() {
+        let (a, b, c) = (Point3::new(0.2_f64, 0.1, 0.0), Point3::new(3.0, 0.4, 0.0), Point3::new(1.1, 2.5, 0.0));
+        let d = triangle_median_dual(a, b, c);
+        assert_eq!(d.areas.iter().sum::<f64>(), triangle_area(a, b, c));
+        // corner A: dual faces out of A plus outward normals of the half edges
+        let z = Vec3::new(0.0, 0.0, 1.0);
+        let half_ab = (b - a).cross(z) * 0.5;
+        let half_ca = (a - c).cross(z) * 0.5;
+        assert!(small(d.dual_faces[0] - d.dual_faces[2] + half_ab + half_ca));
+    }
+
+    #[test]
+    fn tet_corner_control_volume_closes() {
+        let v = [
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.1, 0.0),
+            Point3::new(0.2, 1.3, 0.1),
+            Point3::new(0.3, 0.2, 0.9),
+        ];
+        let d = tetrahedron_median_dual(v[0], v[1], v[2], v[3]);
+        assert_eq!(d.volumes.iter().sum::<f64>(), tetrahedron_volume(v[0], v[1], v[2], v[3]));
+        // corner 0: outgoing dual faces (edges 01, 02, 03) plus a third of the
+        // outward area vectors of its three faces must vanish
+        let third = 1.0 / 3.0;
+        let outward = (face_normal(&[v[0], v[2], v[1]])
+            + face_normal(&[v[0], v[1], v[3]])
+            + face_normal(&[v[0], v[3], v[2]]))
+            * (0.5 * third);
+        assert!(small(d.dual_faces[0] + d.dual_faces[1] + d.dual_faces[2] + outward));
+    }
+
+    #[test]
+    fn shares_sum_exactly() {
+        let mut total = 0.1_f64;
+        for _ in 0..1000 {
+            total = total * 1.37 % 97.0 + 1e-3;
+            assert_eq!(equal_shares::<f64, 3>(total).iter().sum::<f64>(), total);
+            assert_eq!(equal_shares::<f64, 4>(total).iter().sum::<f64>(), total);
+        }
+    }
+}
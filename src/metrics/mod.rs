@@ -1,4 +1,4 @@
-//! Cell metrics: area, centroid, volume, normals, etc.
+//! Cell metrics: area, centroid, volume, normals, quality, etc.
 
 /// Centroid and center-of-mass calculations.
 pub mod centroid;
@@ -8,11 +8,25 @@ pub mod area;
 pub mod normals;
 pub mod polygon;
 pub mod prism;
+/// Element quality metrics for triangles and tetrahedra.
+pub mod quality;
+/// Circumcenters, incenters and orthocenters of triangles and tetrahedra.
+pub mod centers;
+/// Volume and centroid of general polyhedral cells.
+pub mod polyhedron;
+/// Second moments of area and volume inertia tensors.
+pub mod moments;
+/// Median-dual control volumes for vertex-centred schemes.
+pub mod dual;
+/// Finite-volume face metrics: non-orthogonality, skewness, weights.
+pub mod fv;
 
 // Re-export for convenience:
 pub use area::{triangle_area, quad_area};
-pub use centroid::{triangle_centroid, quad_centroid, tetrahedron_centroid, hexahedron_centroid};
+pub use centroid::{triangle_centroid, quad_centroid, tetrahedron_centroid, hexahedron_centroid, face_centroid};
 pub use volume::{tetrahedron_volume, hexahedron_volume};
 pub use normals::{face_normal, projected_area};
-pub use polygon::{polygon_area, polygon_centroid};
+pub use polygon::{polygon_area, polygon_signed_area, polygon_centroid};
 pub use prism::{prism_volume, prism_centroid};
+pub use fv::{face_fv_metrics, FaceFvMetrics};
+pub use polyhedron::{polyhedron_volume, polyhedron_centroid};
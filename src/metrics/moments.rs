@@ -0,0 +1,184 @@
+//! Second moments of area and volume inertia tensors for cells.
+//!
+//! Like `polygon_centroid`, everything here is an exact boundary integral:
+//! polygon moments come from Green's theorem over the edges, volume moments
+//! from the divergence theorem over the faces (see
+//! [`polyhedron`](crate::metrics::polyhedron)). Moments are taken about the
+//! cell centroid with unit density.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point2, metrics::moments::polygon_second_moments};
+//! let rect = [
+//!     Point2::new(0.0_f64, 0.0),
+//!     Point2::new(2.0, 0.0),
+//!     Point2::new(2.0, 1.0),
+//!     Point2::new(0.0, 1.0),
+//! ];
+//! let m = polygon_second_moments(&rect);
+//! // b h³ / 12 and h b³ / 12
+//! assert!((m.ixx - 2.0 / 12.0).abs() < 1e-12);
+//! assert!((m.iyy - 8.0 / 12.0).abs() < 1e-12);
+//! ```
+
+use crate::{Float, Point2, Point3};
+use crate::metrics::polygon_area;
+use crate::metrics::polyhedron::{inertia_from_covariance, VolumeMoments};
+
+/// Second moments of area about the centroid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaMoments<T: Float> {
+    /// ∫ (y − cy)² dA, the moment about the centroidal x-axis
+    pub ixx: T,
+    /// ∫ (x − cx)² dA, the moment about the centroidal y-axis
+    pub iyy: T,
+    /// ∫ (x − cx)(y − cy) dA, the product of area
+    pub ixy: T,
+}
+
+/// Second moments of area of a simple polygon about its centroid.
+/// Vertices ordered CCW or CW, first and last need not repeat.
+pub fn polygon_second_moments<T: Float>(verts: &[Point2<T>]) -> AreaMoments<T> {
+    let n = verts.len();
+    assert!(n >= 3, "polygon_second_moments requires ≥3 vertices");
+    // work relative to the first vertex to limit cancellation
+    let o = verts[0];
+    let (mut a2, mut sx, mut sy) = (T::zero(), T::zero(), T::zero());
+    let (mut sxx, mut syy, mut sxy) = (T::zero(), T::zero(), T::zero());
+    for i in 0..n {
+        let (xi, yi) = (verts[i].x - o.x, verts[i].y - o.y);
+        let (xj, yj) = (verts[(i + 1) % n].x - o.x, verts[(i + 1) % n].y - o.y);
+        let cross = xi * yj - xj * yi;
+        a2 = a2 + cross;
+        sx = sx + (xi + xj) * cross;
+        sy = sy + (yi + yj) * cross;
+        sxx = sxx + (xi * xi + xi * xj + xj * xj) * cross;
+        syy = syy + (yi * yi + yi * yj + yj * yj) * cross;
+        let two = T::from(2.0).unwrap();
+        sxy = sxy + (xi * yj + two * xi * yi + two * xj * yj + xj * yi) * cross;
+    }
+    let a = a2 * T::from(0.5).unwrap();
+    let (cx, cy) = (sx / (T::from(6.0).unwrap() * a), sy / (T::from(6.0).unwrap() * a));
+    let twelve = T::from(12.0).unwrap();
+    // parallel-axis shift; dividing by sign(a) handles CW input
+    let s = a.signum();
+    AreaMoments {
+        ixx: (syy / twelve - a * cy * cy) * s,
+        iyy: (sxx / twelve - a * cx * cx) * s,
+        ixy: (sxy / T::from(24.0).unwrap() - a * cx * cy) * s,
+    }
+}
+
+/// Unit-density inertia tensor of tetrahedron (A, B, C, D) about its centroid.
+pub fn tetrahedron_inertia<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> [[T; 3]; 3] {
+    let mut m = VolumeMoments::new(a);
+    m.add_tet(b, c, d);
+    m.inertia()
+}
+
+/// Unit-density inertia tensor of a hexahedron about its centroid, with the
+/// vertex ordering of `hexahedron_volume` (bottom 0-3, top 4-7).
+pub fn hexahedron_inertia<T: Float>(verts: [Point3<T>; 8]) -> [[T; 3]; 3] {
+    let faces = [
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+        [0, 1, 5, 4],
+        [1, 2, 6, 5],
+        [2, 3, 7, 6],
+        [3, 0, 4, 7],
+    ];
+    polyhedron_inertia(&verts, &faces)
+}
+
+/// Unit-density inertia tensor of a straight prism (base polygon extruded by
+/// `height` along z) about its centroid.
+pub fn prism_inertia<T: Float>(base: &[Point2<T>], height: T) -> [[T; 3]; 3] {
+    let am = polygon_second_moments(base);
+    let area = polygon_area(base);
+    let h = height.abs();
+    let cov = [
+        [am.iyy * h, am.ixy * h, T::zero()],
+        [am.ixy * h, am.ixx * h, T::zero()],
+        [T::zero(), T::zero(), area * h * h * h / T::from(12.0).unwrap()],
+    ];
+    inertia_from_covariance(cov)
+}
+
+/// Unit-density inertia tensor of a closed polyhedron about its centroid.
+/// Faces are index lists into `verts`, consistently oriented.
+pub fn polyhedron_inertia<T: Float, F: AsRef<[usize]>>(verts: &[Point3<T>], faces: &[F]) -> [[T; 3]; 3] {
+    VolumeMoments::of_polyhedron(verts, faces).inertia()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point2, Point3};
+
+    fn assert_tensor(got: [[f64; 3]; 3], want: [[f64; 3]; 3]) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((got[i][j] - want[i][j]).abs() < 1e-12, "[{}][{}]: {} vs {}", i, j, got[i][j], want[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_moments_cw_and_ccw_agree() {
+        // right triangle b=3, h=6: Ixx = b h³/36, Iyy = h b³/36, Ixy = -b² h² / 72
+        let ccw = [Point2::new(1.0_f64, 1.0), Point2::new(4.0, 1.0), Point2::new(1.0, 7.0)];
+        let cw = [ccw[0], ccw[2], ccw[1]];
+        for tri in [ccw, cw] {
+            let m = polygon_second_moments(&tri);
+            assert!((m.ixx - 3.0 * 216.0 / 36.0).abs() < 1e-12);
+            assert!((m.iyy - 6.0 * 27.0 / 36.0).abs() < 1e-12);
+            assert!((m.ixy + 9.0 * 36.0 / 72.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn box_inertia_hex_and_prism() {
+        // a × b × c box: Ixx = V (b² + c²) / 12, ...
+        let (a, b, c) = (2.0_f64, 1.0, 3.0);
+        let v = a * b * c;
+        let want = [
+            [v * (b * b + c * c) / 12.0, 0.0, 0.0],
+            [0.0, v * (a * a + c * c) / 12.0, 0.0],
+            [0.0, 0.0, v * (a * a + b * b) / 12.0],
+        ];
+        let hex = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(a, 0.0, 0.0),
+            Point3::new(a, b, 0.0),
+            Point3::new(0.0, b, 0.0),
+            Point3::new(0.0, 0.0, c),
+            Point3::new(a, 0.0, c),
+            Point3::new(a, b, c),
+            Point3::new(0.0, b, c),
+        ];
+        assert_tensor(hexahedron_inertia(hex), want);
+        let base = [Point2::new(0.0, 0.0), Point2::new(a, 0.0), Point2::new(a, b), Point2::new(0.0, b)];
+        assert_tensor(prism_inertia(&base, c), want);
+    }
+
+    #[test]
+    fn tet_inertia_matches_polyhedron() {
+        let verts = [
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(0.5, 1.0, 0.0),
+            Point3::new(0.3, 0.2, 1.5),
+        ];
+        let faces = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+        assert_tensor(
+            tetrahedron_inertia(verts[0], verts[1], verts[2], verts[3]),
+            polyhedron_inertia(&verts, &faces),
+        );
+    }
+}
@@ -4,7 +4,8 @@ use crate::{Float, Point3, Vec3};
 
 /// Compute un-normalized face normal for planar polygon verts in order.
 /// For triangles, returns the cross product of two edges.
-/// For n>3, returns the sum of cross(edges[i], edges[i+1]).
+/// For n>3, returns the sum of the fan cross products about `verts[0]`, so the
+/// magnitude is twice the polygon area in every case.
 pub fn face_normal<T: Float>(verts: &[Point3<T>]) -> Vec3<T> {
     match verts.len() {
         3 => {
@@ -14,10 +15,9 @@ pub fn face_normal<T: Float>(verts: &[Point3<T>]) -> Vec3<T> {
         }
         n if n > 3 => {
             let mut nrm = Vec3::new(T::zero(), T::zero(), T::zero());
-            for i in 0..verts.len() {
-                let a = verts[i];
-                let b = verts[(i+1) % verts.len()];
-                nrm = nrm + (b - a).cross(verts[(i+2)%verts.len()] - a);
+            let o = verts[0];
+            for i in 1..verts.len() - 1 {
+                nrm = nrm + (verts[i] - o).cross(verts[i + 1] - o);
             }
             nrm
         }
@@ -48,4 +48,26 @@ mod tests {
         }
         assert!((area - 0.5).abs() < 1e-8);
     }
+
+    #[test]
+    fn projected_area_quad_and_pentagon() {
+        let quad = [
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        assert!((projected_area(&quad) - 2.0).abs() < 1e-12);
+        // square with a roof apex, area 1.25, in the plane x = 0
+        let pent = [
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 1.0),
+            Point3::new(0.0, 0.5, 1.5),
+            Point3::new(0.0, 0.0, 1.0),
+        ];
+        let n = face_normal(&pent);
+        assert!((n.x - 2.5).abs() < 1e-12);
+        assert!((projected_area(&pent) - 1.25).abs() < 1e-12);
+    }
 }
@@ -19,18 +19,24 @@
 
 use crate::{Float, Point2};
 
-/// Signed area of an arbitrary planar polygon (shoelace formula).
+/// Area of an arbitrary planar polygon (shoelace formula).
 /// Vertices ordered CCW or CW, first and last need not repeat.
 pub fn polygon_area<T: Float>(verts: &[Point2<T>]) -> T {
+    assert!(verts.len() >= 3, "polygon_area requires ≥3 vertices");
+    polygon_signed_area(verts).abs()
+}
+
+/// Signed area of a planar polygon: positive for CCW, negative for CW.
+pub fn polygon_signed_area<T: Float>(verts: &[Point2<T>]) -> T {
     let n = verts.len();
-    assert!(n >= 3, "polygon_area requires ≥3 vertices");
+    assert!(n >= 3, "polygon_signed_area requires ≥3 vertices");
     let mut sum = T::zero();
     for i in 0..n {
         let a = verts[i];
         let b = verts[(i + 1) % n];
         sum = sum + (a.x * b.y - b.x * a.y);
     }
-    (sum * T::from(0.5).unwrap()).abs()
+    sum * T::from(0.5).unwrap()
 }
 
 /// Centroid of a planar polygon: (Cx, Cy) = (1/(6A)) Σ (xi + xi+1)(xi yi+1 − xi+1 yi)
@@ -0,0 +1,171 @@
+//! Polyhedron metrics: volume and centroid of general polyhedral cells.
+//!
+//! A polyhedron is given as a vertex slice plus faces, each face a list of
+//! indices into the vertex slice ordered counter-clockwise when seen from
+//! outside. Using the divergence theorem, every face is fanned into
+//! triangles around its vertex average and each triangle closes a signed
+//! tetrahedron with a reference point, so the result is exact for planar
+//! faces and well defined for warped ones.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, metrics::polyhedron::polyhedron_volume};
+//! let verts = [
+//!     Point3::new(0.0_f64, 0.0, 0.0),
+//!     Point3::new(1.0, 0.0, 0.0),
+//!     Point3::new(0.0, 1.0, 0.0),
+//!     Point3::new(0.0, 0.0, 1.0),
+//! ];
+//! let faces = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+//! assert!((polyhedron_volume(&verts, &faces) - 1.0 / 6.0).abs() < 1e-12);
+//! ```
+
+use crate::{Float, Point3, Vec3};
+
+/// Zeroth, first and second volume moments relative to a reference point.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct VolumeMoments<T: Float> {
+    /// Reference point the moments are taken about
+    pub origin: Point3<T>,
+    /// Signed volume
+    pub volume: T,
+    /// ∫ r dV
+    pub first: Vec3<T>,
+    /// ∫ r rᵀ dV
+    pub second: [[T; 3]; 3],
+}
+
+impl<T: Float> VolumeMoments<T> {
+    pub(crate) fn new(origin: Point3<T>) -> Self {
+        VolumeMoments {
+            origin,
+            volume: T::zero(),
+            first: Vec3::new(T::zero(), T::zero(), T::zero()),
+            second: [[T::zero(); 3]; 3],
+        }
+    }
+
+    /// Add the signed tetrahedron (origin, A, B, C), exactly.
+    pub(crate) fn add_tet(&mut self, a: Point3<T>, b: Point3<T>, c: Point3<T>) {
+        let (p1, p2, p3) = (a - self.origin, b - self.origin, c - self.origin);
+        let vol = p1.cross(p2).dot(p3) / T::from(6.0).unwrap();
+        let s = p1 + p2 + p3;
+        self.volume = self.volume + vol;
+        self.first = self.first + s * (vol * T::from(0.25).unwrap());
+        // covariance of a tet: V/20 (Σ pᵢpᵢᵀ + s sᵀ), with p₀ = 0
+        let k = vol / T::from(20.0).unwrap();
+        let (p1, p2, p3, s) = ([p1.x, p1.y, p1.z], [p2.x, p2.y, p2.z], [p3.x, p3.y, p3.z], [s.x, s.y, s.z]);
+        for i in 0..3 {
+            for j in 0..3 {
+                let sum = p1[i] * p1[j] + p2[i] * p2[j] + p3[i] * p3[j] + s[i] * s[j];
+                self.second[i][j] = self.second[i][j] + k * sum;
+            }
+        }
+    }
+
+    /// Add one face, fanned around its vertex average.
+    pub(crate) fn add_face(&mut self, verts: &[Point3<T>], face: &[usize]) {
+        if face.len() == 3 {
+            self.add_tet(verts[face[0]], verts[face[1]], verts[face[2]]);
+            return;
+        }
+        let inv = T::one() / T::from(face.len()).unwrap();
+        let mut c = Vec3::new(T::zero(), T::zero(), T::zero());
+        for &i in face {
+            c = c + (verts[i] - self.origin);
+        }
+        let fc = self.origin + c * inv;
+        for k in 0..face.len() {
+            let a = verts[face[k]];
+            let b = verts[face[(k + 1) % face.len()]];
+            self.add_tet(fc, a, b);
+        }
+    }
+
+    /// Moments of a closed polyhedron, taken about its vertex average.
+    pub(crate) fn of_polyhedron<F: AsRef<[usize]>>(verts: &[Point3<T>], faces: &[F]) -> Self {
+        let inv = T::one() / T::from(verts.len()).unwrap();
+        let mut c = Vec3::new(T::zero(), T::zero(), T::zero());
+        for &p in verts {
+            c = c + (p - verts[0]);
+        }
+        let mut m = VolumeMoments::new(verts[0] + c * inv);
+        for f in faces {
+            m.add_face(verts, f.as_ref());
+        }
+        m
+    }
+
+    /// Centroid of the accumulated volume.
+    pub(crate) fn centroid(&self) -> Point3<T> {
+        self.origin + self.first * (T::one() / self.volume)
+    }
+
+    /// Unit-density inertia tensor about the centroid, `tr(C) I − C`.
+    pub(crate) fn inertia(&self) -> [[T; 3]; 3] {
+        let c = self.first * (T::one() / self.volume);
+        let c = [c.x, c.y, c.z];
+        let mut cov = [[T::zero(); 3]; 3];
+        for i in 0..3 {
+            for j in 0..3 {
+                // parallel-axis shift to the centroid; sign makes inward-oriented input positive
+                cov[i][j] = (self.second[i][j] - self.volume * c[i] * c[j]) * self.volume.signum();
+            }
+        }
+        inertia_from_covariance(cov)
+    }
+}
+
+/// Inertia tensor `tr(C) I − C` from the covariance `C = ∫ r rᵀ dV`.
+pub(crate) fn inertia_from_covariance<T: Float>(cov: [[T; 3]; 3]) -> [[T; 3]; 3] {
+    let tr = cov[0][0] + cov[1][1] + cov[2][2];
+    let mut out = [[T::zero(); 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            out[i][j] = if i == j { tr - cov[i][j] } else { -cov[i][j] };
+        }
+    }
+    out
+}
+
+/// Volume of a closed polyhedron with outward-oriented faces.
+pub fn polyhedron_volume<T: Float, F: AsRef<[usize]>>(verts: &[Point3<T>], faces: &[F]) -> T {
+    VolumeMoments::of_polyhedron(verts, faces).volume.abs()
+}
+
+/// Centroid of a closed polyhedron (face orientation must be consistent).
+pub fn polyhedron_centroid<T: Float, F: AsRef<[usize]>>(verts: &[Point3<T>], faces: &[F]) -> Point3<T> {
+    VolumeMoments::of_polyhedron(verts, faces).centroid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point3;
+
+    #[test]
+    fn unit_cube_volume_and_centroid() {
+        let verts = [
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(1.0, 0.0, 1.0),
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(0.0, 1.0, 1.0),
+        ];
+        let faces = [
+            [0, 3, 2, 1],
+            [4, 5, 6, 7],
+            [0, 1, 5, 4],
+            [1, 2, 6, 5],
+            [2, 3, 7, 6],
+            [3, 0, 4, 7],
+        ];
+        assert!((polyhedron_volume(&verts, &faces) - 1.0).abs() < 1e-12);
+        let c = polyhedron_centroid(&verts, &faces);
+        assert!((c.x - 0.5).abs() < 1e-12 && (c.y - 0.5).abs() < 1e-12 && (c.z - 0.5).abs() < 1e-12);
+    }
+}
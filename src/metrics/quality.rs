@@ -0,0 +1,272 @@
+//! Element quality metrics for triangles and tetrahedra.
+//!
+//! Definitions follow the usual conventions of mesh QA tools (Verdict,
+//! Gmsh, Fluent), normalised so that the equilateral triangle and the regular
+//! tetrahedron score 1 (or 0 for skewness). Angles are returned in degrees.
+//!
+//! | metric                     | ideal | range      |
+//! |----------------------------|-------|------------|
+//! | `*_aspect_ratio`           | 1     | [1, ∞)     |
+//! | `*_radius_ratio`           | 1     | [0, 1]     |
+//! | `*_edge_ratio`             | 1     | [1, ∞)     |
+//! | `*_equiangle_skewness`     | 0     | [0, 1]     |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, metrics::quality::triangle_radius_ratio};
+//! let a = Point3::new(0.0_f64, 0.0, 0.0);
+//! let b = Point3::new(1.0, 0.0, 0.0);
+//! let c = Point3::new(0.5, 3.0_f64.sqrt() / 2.0, 0.0);
+//! assert!((triangle_radius_ratio(a, b, c) - 1.0).abs() < 1e-12);
+//! ```
+
+use crate::{Float, Point3, Vec3};
+use crate::metrics::triangle_area;
+use crate::metrics::centers::{
+    triangle_circumcenter, triangle_incenter, tetrahedron_circumsphere, tetrahedron_insphere,
+};
+
+/// Angle between two vectors, in degrees.
+fn angle_between<T: Float>(u: Vec3<T>, v: Vec3<T>) -> T {
+    let cos = u.dot(v) / (u.magnitude() * v.magnitude());
+    cos.max(-T::one()).min(T::one()).acos().to_degrees()
+}
+
+/// Equiangle skewness of a set of angles against the ideal angle `theta_e`.
+fn equiangle_skewness<T: Float>(min: T, max: T, theta_e: T) -> T {
+    let straight = T::from(180.0).unwrap();
+    ((max - theta_e) / (straight - theta_e)).max((theta_e - min) / theta_e)
+}
+
+/// Interior angles of triangle (A, B, C) at A, B and C, in degrees.
+pub fn triangle_angles<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> [T; 3] {
+    [
+        angle_between(b - a, c - a),
+        angle_between(a - b, c - b),
+        angle_between(a - c, b - c),
+    ]
+}
+
+/// Smallest interior angle of triangle (A, B, C), in degrees.
+pub fn triangle_min_angle<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    let [x, y, z] = triangle_angles(a, b, c);
+    x.min(y).min(z)
+}
+
+/// Largest interior angle of triangle (A, B, C), in degrees.
+pub fn triangle_max_angle<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    let [x, y, z] = triangle_angles(a, b, c);
+    x.max(y).max(z)
+}
+
+/// Aspect ratio `l_max (l_ab + l_bc + l_ca) / (4√3 A)` (Verdict definition).
+pub fn triangle_aspect_ratio<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    let (l0, l1, l2) = ((b - a).magnitude(), (c - b).magnitude(), (a - c).magnitude());
+    let lmax = l0.max(l1).max(l2);
+    let four_root3 = T::from(4.0).unwrap() * T::from(3.0).unwrap().sqrt();
+    lmax * (l0 + l1 + l2) / (four_root3 * triangle_area(a, b, c))
+}
+
+/// Normalised radius ratio `2 r / R` (inradius over circumradius).
+pub fn triangle_radius_ratio<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    let (_, r) = triangle_incenter(a, b, c);
+    T::from(2.0).unwrap() * r / triangle_circumcenter(a, b, c).radius
+}
+
+/// Ratio of the longest to the shortest edge.
+pub fn triangle_edge_ratio<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    let (l0, l1, l2) = ((b - a).magnitude(), (c - b).magnitude(), (a - c).magnitude());
+    l0.max(l1).max(l2) / l0.min(l1).min(l2)
+}
+
+/// Equiangle skewness `max((θmax − 60)/120, (60 − θmin)/60)`.
+pub fn triangle_equiangle_skewness<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>) -> T {
+    equiangle_skewness(
+        triangle_min_angle(a, b, c),
+        triangle_max_angle(a, b, c),
+        T::from(60.0).unwrap(),
+    )
+}
+
+/// Edges of tetrahedron (A, B, C, D) in the order AB, AC, AD, BC, BD, CD.
+fn tet_edges<T: Float>(a: Point3<T>, b: Point3<T>, c: Point3<T>, d: Point3<T>) -> [T; 6] {
+    [
+        (b - a).magnitude(),
+        (c - a).magnitude(),
+        (d - a).magnitude(),
+        (c - b).magnitude(),
+        (d - b).magnitude(),
+        (d - c).magnitude(),
+    ]
+}
+
+/// Interior dihedral angle along edge PQ between faces PQR and PQS, in degrees.
+fn dihedral<T: Float>(p: Point3<T>, q: Point3<T>, r: Point3<T>, s: Point3<T>) -> T {
+    let e = q - p;
+    let ee = e.dot(e);
+    let (pr, ps) = (r - p, s - p);
+    let u = pr - e * (pr.dot(e) / ee);
+    let v = ps - e * (ps.dot(e) / ee);
+    angle_between(u, v)
+}
+
+/// Dihedral angles of tetrahedron (A, B, C, D) along edges AB, AC, AD, BC, BD, CD, in degrees.
+pub fn tetrahedron_dihedral_angles<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> [T; 6] {
+    [
+        dihedral(a, b, c, d),
+        dihedral(a, c, b, d),
+        dihedral(a, d, b, c),
+        dihedral(b, c, a, d),
+        dihedral(b, d, a, c),
+        dihedral(c, d, a, b),
+    ]
+}
+
+/// Smallest dihedral angle of tetrahedron (A, B, C, D), in degrees.
+pub fn tetrahedron_min_dihedral_angle<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    tetrahedron_dihedral_angles(a, b, c, d)
+        .iter()
+        .fold(T::infinity(), |m, &x| m.min(x))
+}
+
+/// Largest dihedral angle of tetrahedron (A, B, C, D), in degrees.
+pub fn tetrahedron_max_dihedral_angle<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    tetrahedron_dihedral_angles(a, b, c, d)
+        .iter()
+        .fold(T::neg_infinity(), |m, &x| m.max(x))
+}
+
+/// Aspect ratio `l_max / (2√6 r)` with `r` the inradius (Verdict definition).
+pub fn tetrahedron_aspect_ratio<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    let lmax = tet_edges(a, b, c, d)
+        .iter()
+        .fold(T::zero(), |m, &x| m.max(x));
+    let (_, r) = tetrahedron_insphere(a, b, c, d);
+    lmax / (T::from(2.0).unwrap() * T::from(6.0).unwrap().sqrt() * r)
+}
+
+/// Normalised radius ratio `3 r / R` (inradius over circumradius).
+pub fn tetrahedron_radius_ratio<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    let (_, r) = tetrahedron_insphere(a, b, c, d);
+    T::from(3.0).unwrap() * r / tetrahedron_circumsphere(a, b, c, d).radius
+}
+
+/// Ratio of the longest to the shortest edge.
+pub fn tetrahedron_edge_ratio<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    let e = tet_edges(a, b, c, d);
+    let lmax = e.iter().fold(T::zero(), |m, &x| m.max(x));
+    let lmin = e.iter().fold(T::infinity(), |m, &x| m.min(x));
+    lmax / lmin
+}
+
+/// Equiangle skewness over the twelve face angles against 60°, the
+/// definition used by Fluent and Cubit.
+pub fn tetrahedron_equiangle_skewness<T: Float>(
+    a: Point3<T>,
+    b: Point3<T>,
+    c: Point3<T>,
+    d: Point3<T>,
+) -> T {
+    let faces = [[a, b, c], [a, b, d], [a, c, d], [b, c, d]];
+    let (min, max) = faces
+        .iter()
+        .flat_map(|&[p, q, r]| triangle_angles(p, q, r))
+        .fold((T::infinity(), T::neg_infinity()), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    equiangle_skewness(min, max, T::from(60.0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point3;
+
+    fn regular_tet() -> [Point3<f64>; 4] {
+        [
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+        ]
+    }
+
+    #[test]
+    fn equilateral_triangle_is_ideal() {
+        let a = Point3::new(0.0_f64, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.5, 3.0_f64.sqrt() / 2.0, 0.0);
+        assert!((triangle_min_angle(a, b, c) - 60.0).abs() < 1e-9);
+        assert!((triangle_aspect_ratio(a, b, c) - 1.0).abs() < 1e-12);
+        assert!((triangle_radius_ratio(a, b, c) - 1.0).abs() < 1e-12);
+        assert!((triangle_edge_ratio(a, b, c) - 1.0).abs() < 1e-12);
+        assert!(triangle_equiangle_skewness(a, b, c).abs() < 1e-9);
+    }
+
+    #[test]
+    fn right_triangle_angles() {
+        let a = Point3::new(0.0_f64, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+        let [ta, tb, tc] = triangle_angles(a, b, c);
+        assert!((ta - 90.0).abs() < 1e-9);
+        assert!((tb - 45.0).abs() < 1e-9 && (tc - 45.0).abs() < 1e-9);
+        // max angle 90 → skewness (90-60)/120 vs (60-45)/60
+        assert!((triangle_equiangle_skewness(a, b, c) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn regular_tet_is_ideal() {
+        let [a, b, c, d] = regular_tet();
+        let theta = (1.0_f64 / 3.0).acos().to_degrees();
+        for ang in tetrahedron_dihedral_angles(a, b, c, d) {
+            assert!((ang - theta).abs() < 1e-9);
+        }
+        assert!((tetrahedron_aspect_ratio(a, b, c, d) - 1.0).abs() < 1e-12);
+        assert!((tetrahedron_radius_ratio(a, b, c, d) - 1.0).abs() < 1e-12);
+        assert!((tetrahedron_edge_ratio(a, b, c, d) - 1.0).abs() < 1e-12);
+        assert!(tetrahedron_equiangle_skewness(a, b, c, d).abs() < 1e-9);
+    }
+
+    #[test]
+    fn corner_tet_dihedrals() {
+        let a = Point3::new(0.0_f64, 0.0, 0.0);
+        let b = Point3::new(1.0, 0.0, 0.0);
+        let c = Point3::new(0.0, 1.0, 0.0);
+        let d = Point3::new(0.0, 0.0, 1.0);
+        // the three edges at the corner are right angles
+        assert!((tetrahedron_max_dihedral_angle(a, b, c, d) - 90.0).abs() < 1e-9);
+        assert!(tetrahedron_radius_ratio(a, b, c, d) < 1.0);
+        // face angles span 45°..90°: (90-60)/120 = (60-45)/60
+        assert!((tetrahedron_equiangle_skewness(a, b, c, d) - 0.25).abs() < 1e-9);
+    }
+}
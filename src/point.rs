@@ -64,6 +64,54 @@ impl<T: Float> Add<Vec3<T>> for Point3<T> {
     }
 }
 
+/// Dimension-generic coordinate access, implemented by `Point2` and `Point3`.
+pub trait PointN: Copy {
+    /// Coordinate type
+    type Scalar: Float;
+
+    /// Number of coordinates
+    const DIM: usize;
+
+    /// Coordinate along `axis` (0 = x, 1 = y, 2 = z)
+    fn coord(&self, axis: usize) -> Self::Scalar;
+
+    /// Squared Euclidean distance to `other`
+    fn distance_squared(&self, other: &Self) -> Self::Scalar;
+}
+
+impl<T: Float> PointN for Point2<T> {
+    type Scalar = T;
+    const DIM: usize = 2;
+    fn coord(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => panic!("Point2 has no axis {}", axis),
+        }
+    }
+    fn distance_squared(&self, other: &Self) -> T {
+        let d = *self - *other;
+        d.dot(d)
+    }
+}
+
+impl<T: Float> PointN for Point3<T> {
+    type Scalar = T;
+    const DIM: usize = 3;
+    fn coord(&self, axis: usize) -> T {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            2 => self.z,
+            _ => panic!("Point3 has no axis {}", axis),
+        }
+    }
+    fn distance_squared(&self, other: &Self) -> T {
+        let d = *self - *other;
+        d.dot(d)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Point2, Point3, Vec3};
@@ -1,7 +1,7 @@
 //! Prelude: re-exports common types/traits for mesh-geometry.
 //! Common re-exports for ease of use.
 
-pub use crate::point::{Point2, Point3};
+pub use crate::point::{Point2, Point3, PointN};
 pub use crate::vec::{Vec2, Vec3};
 pub use crate::Float;
 
@@ -0,0 +1,320 @@
+//! Point location in unstructured meshes.
+//!
+//! A [`Locator`] finds the cell containing a point and the point's local
+//! coordinates in it, either by walking from a hint cell ([`Locator::walk`],
+//! cheap when consecutive queries are close, as in particle tracking) or
+//! through a [`Bvh`] over the cell boxes ([`Locator::locate`]).
+//!
+//! The walk is a visibility walk: from the current cell it crosses the face
+//! the point lies furthest beyond, until no face separates the point from
+//! the cell. Cells are treated as convex, and a point counts as inside when
+//! it is at most `tol` beyond every face.
+//!
+//! A point on a shared edge, face or node is inside several cells; both
+//! methods then return the lowest-numbered of them, so the answer does not
+//! depend on the hint or the tree layout.
+//!
+//! Supported cells are the 2D cells of a `Mesh2` and the volume cells of a
+//! `Mesh3`. Local coordinates are barycentric for triangles and tetrahedra
+//! and `invert_quad_mapping`'s `(ξ, η)` for quads.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::mesh::{Cell, Mesh2};
+//! use mesh_geometry::queries::locate::{LocalCoords, Locator};
+//!
+//! let nodes = vec![
+//!     Point2::new(0.0_f64, 0.0),
+//!     Point2::new(1.0, 0.0),
+//!     Point2::new(1.0, 1.0),
+//!     Point2::new(0.0, 1.0),
+//! ];
+//! let mesh = Mesh2::from_parts(nodes, vec![Cell::Triangle([0, 1, 2]), Cell::Triangle([0, 2, 3])]);
+//! let locator = Locator::new(&mesh, 1e-12);
+//!
+//! let hit = locator.locate(&Point2::new(0.25, 0.75)).unwrap();
+//! assert_eq!(hit.cell, 1);
+//! if let LocalCoords::Triangle(l) = hit.coords {
+//!     assert!((l[0] - 0.25).abs() < 1e-12);
+//! }
+//!
+//! // on the shared diagonal: cell 0 from either method and any hint
+//! let p = Point2::new(0.5, 0.5);
+//! assert_eq!(locator.walk(1, &p).unwrap().cell, 0);
+//! assert_eq!(locator.locate(&p).unwrap().cell, 0);
+//! ```
+
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Float, Point2, Point3, PointN};
+use crate::mesh::{CellPoints, Mesh, Topology};
+use crate::metrics::face_normal;
+use crate::utils::aabb::Aabb;
+use crate::utils::bvh::{Bvh, Split};
+use crate::utils::jacobian::invert_quad_mapping;
+
+/// Newton iterations allowed when inverting a quad mapping.
+const QUAD_ITERS: usize = 50;
+
+/// Coordinates of a point within the cell that contains it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LocalCoords<T> {
+    /// Barycentric weights of the three corners
+    Triangle([T; 3]),
+    /// Reference coordinates `(ξ, η)` in `[-1, 1]²`
+    Quad([T; 2]),
+    /// Barycentric weights of the four corners
+    Tetrahedron([T; 4]),
+    /// Cell located, but no local coordinates for this shape
+    None,
+}
+
+/// A located point: the containing cell and local coordinates in it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location<T> {
+    /// Containing cell
+    pub cell: usize,
+    /// Local coordinates within `cell`
+    pub coords: LocalCoords<T>,
+}
+
+/// Point types meshes can be searched in.
+pub trait LocateGeometry: PointN {
+    /// Embed in 3D for bounding boxes.
+    fn lift(&self) -> Point3<Self::Scalar>;
+
+    /// Signed distance of `p` beyond a face (edge in 2D), positive on the
+    /// side the face's outward orientation points to.
+    fn face_side(face: &[Self], p: &Self) -> Self::Scalar;
+
+    /// Local coordinates of `p` in a cell known to contain it.
+    fn local_coords(cell: &CellPoints<'_, Self>, p: &Self) -> LocalCoords<Self::Scalar>;
+}
+
+impl<T: Float> LocateGeometry for Point2<T> {
+    fn lift(&self) -> Point3<T> {
+        Point3::new(self.x, self.y, T::zero())
+    }
+
+    fn face_side(face: &[Self], p: &Self) -> T {
+        // edges run with their owner on the left
+        let e = face[1] - face[0];
+        -e.cross(*p - face[0]) / e.magnitude()
+    }
+
+    fn local_coords(cell: &CellPoints<'_, Self>, p: &Self) -> LocalCoords<T> {
+        match *cell {
+            CellPoints::Triangle([a, b, c]) => {
+                let area = (b - a).cross(c - a);
+                let la = (b - *p).cross(c - *p) / area;
+                let lb = (c - *p).cross(a - *p) / area;
+                LocalCoords::Triangle([la, lb, T::one() - la - lb])
+            }
+            CellPoints::Quad([a, b, c, d]) => {
+                // Newton converges to round-off, relative to the cell size
+                let size = (c - a).magnitude().max((d - b).magnitude());
+                let newton_tol = size * T::epsilon() * T::from(1024.0).unwrap();
+                invert_quad_mapping(T::zero(), T::zero(), *p, a, b, c, d, newton_tol, QUAD_ITERS)
+                    .map_or(LocalCoords::None, |(xi, eta)| LocalCoords::Quad([xi, eta]))
+            }
+            _ => LocalCoords::None,
+        }
+    }
+}
+
+impl<T: Float> LocateGeometry for Point3<T> {
+    fn lift(&self) -> Point3<T> {
+        *self
+    }
+
+    fn face_side(face: &[Self], p: &Self) -> T {
+        let n = face_normal(face);
+        n.dot(*p - face[0]) / n.magnitude()
+    }
+
+    fn local_coords(cell: &CellPoints<'_, Self>, p: &Self) -> LocalCoords<T> {
+        match *cell {
+            CellPoints::Tetrahedron([a, b, c, d]) => {
+                let vol = |a: Point3<T>, b: Point3<T>, c: Point3<T>, d: Point3<T>| (b - a).cross(c - a).dot(d - a);
+                let v = vol(a, b, c, d);
+                let la = vol(*p, b, c, d) / v;
+                let lb = vol(a, *p, c, d) / v;
+                let lc = vol(a, b, *p, d) / v;
+                LocalCoords::Tetrahedron([la, lb, lc, T::one() - la - lb - lc])
+            }
+            _ => LocalCoords::None,
+        }
+    }
+}
+
+/// Point-location index over one mesh.
+#[derive(Debug, Clone)]
+pub struct Locator<'a, P: LocateGeometry> {
+    mesh: &'a Mesh<P>,
+    topology: Topology,
+    bvh: Bvh<P::Scalar>,
+    tol: P::Scalar,
+}
+
+impl<'a, T: Float, P: LocateGeometry<Scalar = T>> Locator<'a, P> {
+    /// Build face adjacency and a cell BVH for `mesh`; points up to `tol`
+    /// outside a cell still count as inside it.
+    pub fn new(mesh: &'a Mesh<P>, tol: T) -> Self {
+        let boxes: Vec<Aabb<T>> = mesh
+            .cell_points()
+            .map(|c| {
+                let Aabb { min, max } = Aabb::from_points(&c.points().iter().map(|p| p.lift()).collect::<Vec<_>>());
+                Aabb {
+                    min: Point3::new(min.x - tol, min.y - tol, min.z - tol),
+                    max: Point3::new(max.x + tol, max.y + tol, max.z + tol),
+                }
+            })
+            .collect();
+        Locator { mesh, topology: mesh.topology(), bvh: Bvh::new(&boxes, Split::Sah), tol }
+    }
+
+    /// For each face of cell `c`: the cell across it and how far `p` lies beyond it.
+    fn sides(&self, c: usize, p: &P) -> impl Iterator<Item = (Option<usize>, T)> + '_ {
+        let p = *p;
+        self.topology.cell_faces[c].iter().map(move |&f| {
+            let pts = self.topology.face_points_of(f, &self.mesh.nodes);
+            let side = P::face_side(&pts, &p);
+            if self.topology.owner[f] == c {
+                (self.topology.neighbour[f], side)
+            } else {
+                (Some(self.topology.owner[f]), -side)
+            }
+        })
+    }
+
+    /// Whether `p` lies in cell `c`, within the tolerance.
+    pub fn contains(&self, c: usize, p: &P) -> bool {
+        self.sides(c, p).all(|(_, s)| s <= self.tol)
+    }
+
+    /// Lowest-numbered cell containing `p` among those reachable from the
+    /// containing cell `c` through faces `p` lies on.
+    fn resolve(&self, c: usize, p: &P) -> Location<T> {
+        let mut seen = BTreeSet::from([c]);
+        let mut stack = vec![c];
+        while let Some(cur) = stack.pop() {
+            for (nb, s) in self.sides(cur, p) {
+                let on_face = s.abs() <= self.tol;
+                if let Some(nb) = nb.filter(|&nb| on_face && !seen.contains(&nb) && self.contains(nb, p)) {
+                    seen.insert(nb);
+                    stack.push(nb);
+                }
+            }
+        }
+        let cell = *seen.first().unwrap();
+        Location { cell, coords: P::local_coords(&self.mesh.cell_points_of(cell), p) }
+    }
+
+    /// Walk from cell `hint` towards `p`. Returns `None` if the walk leaves
+    /// the mesh (the point is outside, or a concave boundary is in the way)
+    /// or does not settle within one step per cell.
+    pub fn walk(&self, hint: usize, p: &P) -> Option<Location<T>> {
+        let mut cur = hint;
+        for _ in 0..=self.mesh.num_cells() {
+            let (nb, s) = self
+                .sides(cur, p)
+                .fold((None, T::neg_infinity()), |best, (nb, s)| if s > best.1 { (nb, s) } else { best });
+            if s <= self.tol {
+                return Some(self.resolve(cur, p));
+            }
+            cur = nb?;
+        }
+        None
+    }
+
+    /// Find the cell containing `p` through the bounding-volume hierarchy.
+    pub fn locate(&self, p: &P) -> Option<Location<T>> {
+        let mut cands = self.bvh.query_point(p.lift());
+        cands.sort_unstable();
+        cands.into_iter().find(|&c| self.contains(c, p)).map(|c| self.resolve(c, p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{Cell, Mesh2, Mesh3};
+
+    fn quad_grid(n: usize) -> Mesh2<f64> {
+        let mut mesh = Mesh2::new();
+        for j in 0..=n {
+            for i in 0..=n {
+                // shear the grid so the quads are not axis aligned
+                mesh.add_node(Point2::new(i as f64 + 0.2 * j as f64, j as f64));
+            }
+        }
+        for j in 0..n {
+            for i in 0..n {
+                let a = j * (n + 1) + i;
+                mesh.add_cell(Cell::Quad([a, a + 1, a + n + 2, a + n + 1]));
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn walk_and_tree_agree_on_quads() {
+        let mesh = quad_grid(6);
+        let loc = Locator::new(&mesh, 1e-10);
+        let p = Point2::new(3.5 + 0.2 * 4.25, 4.25);
+        let hit = loc.walk(0, &p).unwrap();
+        assert_eq!(hit, loc.locate(&p).unwrap());
+        assert_eq!(hit.cell, 4 * 6 + 3);
+        if let LocalCoords::Quad([xi, eta]) = hit.coords {
+            assert!((xi - 0.0).abs() < 1e-9 && (eta + 0.5).abs() < 1e-9);
+        } else {
+            panic!("expected quad coordinates");
+        }
+        // a shared grid node resolves to the lowest of its four cells
+        let node = mesh.nodes[2 * 7 + 2];
+        assert_eq!(loc.walk(35, &node).unwrap().cell, 7);
+        assert_eq!(loc.locate(&node).unwrap().cell, 7);
+        assert!(loc.locate(&Point2::new(-1.0, 0.5)).is_none());
+        assert!(loc.walk(10, &Point2::new(-1.0, 0.5)).is_none());
+    }
+
+    #[test]
+    fn quad_coords_without_tolerance() {
+        // a trapezoid far from the origin: Newton needs several steps
+        let corners = [(0.0_f64, 0.0), (4.0, 0.0), (3.0, 2.0), (1.0, 2.0)];
+        let nodes = corners.iter().map(|&(x, y)| Point2::new(1e5 + x, 2e5 + y)).collect();
+        let mesh = Mesh2::from_parts(nodes, vec![Cell::Quad([0, 1, 2, 3])]);
+        let loc = Locator::new(&mesh, 0.0);
+        let hit = loc.locate(&Point2::new(1e5 + 2.5, 2e5 + 1.5)).unwrap();
+        let LocalCoords::Quad([xi, eta]) = hit.coords else { panic!("expected quad coordinates") };
+        // x = 2 + xi (3 - eta) / 2, y = 1 + eta
+        assert!((eta - 0.5).abs() < 1e-9 && (xi - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn tetrahedra_barycentric() {
+        let nodes = vec![
+            Point3::new(0.0_f64, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(1.0, 1.0, 1.0),
+        ];
+        let mesh = Mesh3::from_parts(nodes, vec![Cell::Tetrahedron([0, 1, 2, 3]), Cell::Tetrahedron([1, 2, 3, 4])]);
+        let loc = Locator::new(&mesh, 1e-12);
+        let p = Point3::new(0.5, 0.5, 0.5);
+        let hit = loc.walk(0, &p).unwrap();
+        assert_eq!(hit.cell, 1);
+        if let LocalCoords::Tetrahedron(l) = hit.coords {
+            assert!(l.iter().all(|&w| (w - 0.25).abs() < 1e-12));
+        } else {
+            panic!("expected barycentric coordinates");
+        }
+        let q = Point3::new(0.1, 0.2, 0.3);
+        assert_eq!(loc.locate(&q).unwrap().cell, 0);
+    }
+}
@@ -4,6 +4,8 @@ pub mod point_in_polygon;
 pub mod ray_triangle;
 /// Distance queries for points and cells.
 pub mod distance;
+/// Point location in unstructured meshes.
+pub mod locate;
 
 // Re-exports:
 pub use point_in_polygon::point_in_polygon;
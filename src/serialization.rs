@@ -0,0 +1,226 @@
+//! Serde support (feature `serde`).
+//!
+//! Every geometry type serialises in a compact array form by default:
+//! points and vectors as `[x, y]` or `[x, y, z]`, a [`Transform3`] as the
+//! three rows `[m0, m1, m2, t]` of its 3×4 affine matrix, an [`Aabb`] as
+//! `[min, max]`, a [`Ray`] as `[origin, dir]` and a [`Jacobian2x2`] as
+//! `[[m11, m12], [m21, m22]]`. The struct form, with named fields, is
+//! available per field through the `*Def` types of this module and serde's
+//! `with` attribute. Only `core` is needed, so the feature works without
+//! `std`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point3;
+//! use mesh_geometry::aabb::Aabb;
+//! use mesh_geometry::serialization::Point3Def;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Gauge {
+//!     #[serde(with = "Point3Def")]
+//!     position: Point3<f64>,
+//!     bounds: Aabb<f64>,
+//! }
+//!
+//! let gauge = Gauge {
+//!     position: Point3::new(1.0, 2.0, -3.5),
+//!     bounds: Aabb { min: Point3::new(0.0, 0.0, -4.0), max: Point3::new(2.0, 4.0, 0.0) },
+//! };
+//! let json = serde_json::to_string(&gauge).unwrap();
+//! assert_eq!(json, r#"{"position":{"x":1.0,"y":2.0,"z":-3.5},"bounds":[[0.0,0.0,-4.0],[2.0,4.0,0.0]]}"#);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::aabb::Aabb;
+use crate::jacobian::Jacobian2x2;
+use crate::queries::Ray;
+use crate::transforms::Transform3;
+use crate::{Float, Point2, Point3, Vec2, Vec3};
+
+/// Implement the compact form through an array or tuple representation.
+macro_rules! compact {
+    ($ty:ident, $repr:ty, |$v:ident| $to:expr, |$r:pat_param| $from:expr) => {
+        impl<T: Float + Serialize> Serialize for $ty<T> {
+            fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                let $v = self;
+                $to.serialize(s)
+            }
+        }
+
+        impl<'de, T: Float + Deserialize<'de>> Deserialize<'de> for $ty<T> {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                let $r = <$repr>::deserialize(d)?;
+                Ok($from)
+            }
+        }
+    };
+}
+
+compact!(Point2, [T; 2], |p| [p.x, p.y], |[x, y]| Point2::new(x, y));
+compact!(Point3, [T; 3], |p| [p.x, p.y, p.z], |[x, y, z]| Point3::new(x, y, z));
+compact!(Vec2, [T; 2], |v| [v.x, v.y], |[x, y]| Vec2::new(x, y));
+compact!(Vec3, [T; 3], |v| [v.x, v.y, v.z], |[x, y, z]| Vec3::new(x, y, z));
+compact!(
+    Transform3,
+    [[T; 4]; 3],
+    |tr| {
+        let (m, t) = (tr.m, tr.t);
+        [[m[0][0], m[0][1], m[0][2], t.x], [m[1][0], m[1][1], m[1][2], t.y], [m[2][0], m[2][1], m[2][2], t.z]]
+    },
+    |rows| Transform3::new(
+        [[rows[0][0], rows[0][1], rows[0][2]], [rows[1][0], rows[1][1], rows[1][2]], [rows[2][0], rows[2][1], rows[2][2]]],
+        Vec3::new(rows[0][3], rows[1][3], rows[2][3]),
+    )
+);
+compact!(Aabb, (Point3<T>, Point3<T>), |b| (b.min, b.max), |(min, max)| Aabb { min, max });
+compact!(Ray, (Point3<T>, Vec3<T>), |r| (r.origin, r.dir), |(origin, dir)| Ray { origin, dir });
+compact!(
+    Jacobian2x2,
+    [[T; 2]; 2],
+    |j| [[j.m11, j.m12], [j.m21, j.m22]],
+    |[[m11, m12], [m21, m22]]| Jacobian2x2 { m11, m12, m21, m22 }
+);
+
+/// Struct form of [`Point2`]: `{ "x": .., "y": .. }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Point2")]
+pub struct Point2Def<T: Float> {
+    /// X coordinate
+    pub x: T,
+    /// Y coordinate
+    pub y: T,
+}
+
+/// Struct form of [`Point3`]: `{ "x": .., "y": .., "z": .. }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Point3")]
+pub struct Point3Def<T: Float> {
+    /// X coordinate
+    pub x: T,
+    /// Y coordinate
+    pub y: T,
+    /// Z coordinate
+    pub z: T,
+}
+
+/// Struct form of [`Vec2`]: `{ "x": .., "y": .. }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Vec2")]
+pub struct Vec2Def<T: Float> {
+    /// X component
+    pub x: T,
+    /// Y component
+    pub y: T,
+}
+
+/// Struct form of [`Vec3`]: `{ "x": .., "y": .., "z": .. }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Vec3")]
+pub struct Vec3Def<T: Float> {
+    /// X component
+    pub x: T,
+    /// Y component
+    pub y: T,
+    /// Z component
+    pub z: T,
+}
+
+/// Struct form of [`Transform3`]: `{ "m": [[..]; 3], "t": { "x": .., .. } }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Transform3", bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct Transform3Def<T: Float> {
+    /// 3x3 matrix, row by row
+    pub m: [[T; 3]; 3],
+    /// Translation vector
+    #[serde(with = "Vec3Def")]
+    pub t: Vec3<T>,
+}
+
+/// Struct form of [`Aabb`]: `{ "min": { "x": .., .. }, "max": { .. } }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Aabb", bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct AabbDef<T: Float> {
+    /// Minimum corner
+    #[serde(with = "Point3Def")]
+    pub min: Point3<T>,
+    /// Maximum corner
+    #[serde(with = "Point3Def")]
+    pub max: Point3<T>,
+}
+
+/// Struct form of [`Ray`]: `{ "origin": { "x": .., .. }, "dir": { .. } }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Ray", bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct RayDef<T: Float> {
+    /// Ray origin point
+    #[serde(with = "Point3Def")]
+    pub origin: Point3<T>,
+    /// Ray direction vector
+    #[serde(with = "Vec3Def")]
+    pub dir: Vec3<T>,
+}
+
+/// Struct form of [`Jacobian2x2`]: `{ "m11": .., "m12": .., "m21": .., "m22": .. }`.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Jacobian2x2")]
+pub struct Jacobian2x2Def<T: Float> {
+    /// Entry (1,1)
+    pub m11: T,
+    /// Entry (1,2)
+    pub m12: T,
+    /// Entry (2,1)
+    pub m21: T,
+    /// Entry (2,2)
+    pub m22: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_round_trips_through_json_and_toml() {
+        let tr = Transform3::new([[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]], Vec3::new(5.0, 6.0, 7.0));
+        let json = serde_json::to_string(&tr).unwrap();
+        assert_eq!(json, "[[0.0,-1.0,0.0,5.0],[1.0,0.0,0.0,6.0],[0.0,0.0,2.0,7.0]]");
+        assert_eq!(serde_json::from_str::<Transform3<f64>>(&json).unwrap(), tr);
+
+        let j: Jacobian2x2<f32> = serde_json::from_str("[[2, 0.5], [0, 1]]").unwrap();
+        assert_eq!(j, Jacobian2x2 { m11: 2.0, m12: 0.5, m21: 0.0, m22: 1.0 });
+        assert!(serde_json::from_str::<Point3<f64>>("[1, 2]").is_err());
+
+        #[derive(Serialize, Deserialize)]
+        struct Probe {
+            ray: Ray<f64>,
+            offset: Vec2<f64>,
+        }
+        let probe: Probe = toml::from_str("ray = [[0, 0, 10], [0, 0, -1]]\noffset = [0.5, -0.5]\n").unwrap();
+        assert_eq!((probe.ray.origin, probe.ray.dir), (Point3::new(0.0, 0.0, 10.0), Vec3::new(0.0, 0.0, -1.0)));
+        let text = toml::to_string(&probe).unwrap();
+        assert_eq!(text, "ray = [[0.0, 0.0, 10.0], [0.0, 0.0, -1.0]]\noffset = [0.5, -0.5]\n");
+    }
+
+    #[test]
+    fn struct_form_through_with() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Config {
+            #[serde(with = "AabbDef")]
+            domain: Aabb<f64>,
+            #[serde(with = "Point2Def")]
+            origin: Point2<f64>,
+        }
+        let config = Config {
+            domain: Aabb { min: Point3::new(0.0, 0.0, -50.0), max: Point3::new(1e3, 2e3, 0.0) },
+            origin: Point2::new(0.5, 0.25),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"domain":{"min":{"x":0.0,"y":0.0,"z":-50.0},"max":{"x":1000.0,"y":2000.0,"z":0.0}},"origin":{"x":0.5,"y":0.25}}"#
+        );
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+    }
+}
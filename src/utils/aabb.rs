@@ -60,6 +60,37 @@ impl<T: Float> Aabb<T> {
         p.y >= self.min.y && p.y <= self.max.y &&
         p.z >= self.min.z && p.z <= self.max.z
     }
+
+    /// Smallest box containing both `self` and `other`
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        Aabb {
+            min: Point3::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y), self.min.z.min(other.min.z)),
+            max: Point3::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y), self.max.z.max(other.max.z)),
+        }
+    }
+
+    /// Centre of the box
+    pub fn center(&self) -> Point3<T> {
+        let half = T::from(0.5).unwrap();
+        Point3::new(
+            (self.min.x + self.max.x) * half,
+            (self.min.y + self.max.y) * half,
+            (self.min.z + self.max.z) * half,
+        )
+    }
+
+    /// Total area of the six faces
+    pub fn surface_area(&self) -> T {
+        let d = self.max - self.min;
+        (d.x * d.y + d.y * d.z + d.z * d.x) * T::from(2.0).unwrap()
+    }
+
+    /// Squared distance from `p` to the box (zero inside)
+    pub fn distance_squared(&self, p: Point3<T>) -> T {
+        let gap = |v: T, lo: T, hi: T| (lo - v).max(T::zero()).max(v - hi);
+        let (dx, dy, dz) = (gap(p.x, self.min.x, self.max.x), gap(p.y, self.min.y, self.max.y), gap(p.z, self.min.z, self.max.z));
+        dx * dx + dy * dy + dz * dz
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,394 @@
+//! Bounding volume hierarchy over per-cell axis-aligned boxes.
+//!
+//! [`Bvh::new`] builds a binary tree over a list of [`Aabb`]s, splitting
+//! either at the median centroid along the longest axis ([`Split::Median`],
+//! fast to build) or where the binned surface area heuristic is cheapest
+//! ([`Split::Sah`], faster to query). Leaves hold up to four items.
+//!
+//! Queries return item indices, i.e. cell indices when the tree was built
+//! with [`Bvh::from_mesh`]:
+//!
+//! - [`Bvh::query_point`]: items whose box contains a point;
+//! - [`Bvh::query_aabb`]: items whose box overlaps a box;
+//! - [`Bvh::nearest`]: item minimising a caller-supplied distance, pruned
+//!   by box distance;
+//! - [`Bvh::raycast`] / [`Bvh::raycast_mesh`]: first hit along a ray, the
+//!   latter testing cell (or cell face) triangles with
+//!   `ray_intersects_triangle`.
+//!
+//! For a [`Mesh2`](crate::mesh::Mesh2), build from `mesh.to_mesh3(0.0)`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::{Point3, Vec3};
+//! use mesh_geometry::mesh::{Cell, Mesh3};
+//! use mesh_geometry::queries::Ray;
+//! use mesh_geometry::utils::bvh::{Bvh, Split};
+//!
+//! // a 4×4 grid of triangulated unit squares at z = -2
+//! let mut mesh = Mesh3::new();
+//! for j in 0..5 {
+//!     for i in 0..5 {
+//!         mesh.add_node(Point3::new(i as f64, j as f64, -2.0));
+//!     }
+//! }
+//! for j in 0..4 {
+//!     for i in 0..4 {
+//!         let n = j * 5 + i;
+//!         mesh.add_cell(Cell::Triangle([n, n + 1, n + 6]));
+//!         mesh.add_cell(Cell::Triangle([n, n + 6, n + 5]));
+//!     }
+//! }
+//! let bvh = Bvh::from_mesh(&mesh, Split::Sah);
+//!
+//! let ray = Ray { origin: Point3::new(2.3, 1.6, 0.0), dir: Vec3::new(0.0, 0.0, -1.0) };
+//! let (cell, t) = bvh.raycast_mesh(&mesh, ray).unwrap();
+//! assert_eq!(cell, 2 * (4 + 2) + 1);
+//! assert!((t - 2.0).abs() < 1e-12);
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Float, Point3, PointN, Vec3};
+use crate::mesh::Mesh3;
+use crate::queries::{ray_intersects_triangle, Ray};
+use crate::utils::aabb::Aabb;
+
+/// Maximum number of items in a leaf.
+const LEAF_SIZE: usize = 4;
+
+/// Number of centroid bins evaluated by the surface area heuristic.
+const SAH_BINS: usize = 16;
+
+/// How interior nodes are split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Split {
+    /// Median centroid along the longest axis
+    Median,
+    /// Binned surface area heuristic
+    Sah,
+}
+
+/// One tree node: a leaf when `count > 0`, otherwise children at `start` and `start + 1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node<T: Float> {
+    bounds: Aabb<T>,
+    start: usize,
+    count: usize,
+}
+
+/// Bounding volume hierarchy over a list of boxes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<T: Float> {
+    nodes: Vec<Node<T>>,
+    /// Item indices, leaf ranges refer into this list
+    items: Vec<usize>,
+    boxes: Vec<Aabb<T>>,
+}
+
+/// Component of `v` along `axis`.
+fn component<T: Float>(v: Vec3<T>, axis: usize) -> T {
+    match axis {
+        0 => v.x,
+        1 => v.y,
+        _ => v.z,
+    }
+}
+
+/// Entry distance of `ray` into `bb`, if it hits at `t ≥ 0`.
+fn ray_box<T: Float>(ray: &Ray<T>, bb: &Aabb<T>) -> Option<T> {
+    let mut t0 = T::zero();
+    let mut t1 = T::infinity();
+    for axis in 0..3 {
+        let inv = component(ray.dir, axis).recip();
+        let a = (bb.min.coord(axis) - ray.origin.coord(axis)) * inv;
+        let b = (bb.max.coord(axis) - ray.origin.coord(axis)) * inv;
+        // NaN (origin on a slab of a parallel ray) is ignored by min/max
+        t0 = t0.max(a.min(b));
+        t1 = t1.min(a.max(b));
+    }
+    (t0 <= t1).then_some(t0)
+}
+
+impl<T: Float> Bvh<T> {
+    /// Build a hierarchy over `boxes`; item `i` is `boxes[i]`.
+    pub fn new(boxes: &[Aabb<T>], split: Split) -> Self {
+        let mut bvh = Bvh { nodes: Vec::new(), items: (0..boxes.len()).collect(), boxes: boxes.to_vec() };
+        if boxes.is_empty() {
+            return bvh;
+        }
+        let centers: Vec<Point3<T>> = boxes.iter().map(|b| b.center()).collect();
+        bvh.nodes.push(Node { bounds: Aabb::empty(), start: 0, count: 0 });
+        bvh.build(&centers, 0, 0, boxes.len(), split);
+        bvh
+    }
+
+    /// Build over the bounding boxes of every cell of `mesh`.
+    pub fn from_mesh(mesh: &Mesh3<T>, split: Split) -> Self {
+        let boxes: Vec<Aabb<T>> = mesh.cell_points().map(|c| Aabb::from_points(c.points())).collect();
+        Self::new(&boxes, split)
+    }
+
+    fn build(&mut self, centers: &[Point3<T>], node: usize, lo: usize, hi: usize, split: Split) {
+        let bounds = self.items[lo..hi].iter().fold(Aabb::empty(), |acc, &i| acc.union(&self.boxes[i]));
+        self.nodes[node] = Node { bounds, start: lo, count: hi - lo };
+        if hi - lo <= LEAF_SIZE {
+            return;
+        }
+        let cb = Aabb::from_points(&self.items[lo..hi].iter().map(|&i| centers[i]).collect::<Vec<_>>());
+        let ext = cb.max - cb.min;
+        let axis = if ext.x >= ext.y && ext.x >= ext.z { 0 } else if ext.y >= ext.z { 1 } else { 2 };
+        let (cmin, extent) = (cb.min.coord(axis), component(ext, axis));
+        if extent <= T::zero() {
+            // coincident centroids cannot be separated
+            return;
+        }
+        let median = |items: &mut [usize]| {
+            let m = items.len() / 2;
+            items.select_nth_unstable_by(m, |&a, &b| {
+                centers[a].coord(axis).partial_cmp(&centers[b].coord(axis)).unwrap_or(core::cmp::Ordering::Equal)
+            });
+            m
+        };
+        let mid = lo + match split {
+            Split::Median => median(&mut self.items[lo..hi]),
+            Split::Sah => {
+                let bin = |i: usize| {
+                    let f = (centers[i].coord(axis) - cmin) / extent * T::from(SAH_BINS).unwrap();
+                    f.to_usize().unwrap_or(0).min(SAH_BINS - 1)
+                };
+                match self.sah_bin(lo, hi, &bin) {
+                    Some(s) => {
+                        let items = &mut self.items[lo..hi];
+                        let mut m = 0;
+                        for k in 0..items.len() {
+                            if bin(items[k]) < s {
+                                items.swap(k, m);
+                                m += 1;
+                            }
+                        }
+                        m
+                    }
+                    None => median(&mut self.items[lo..hi]),
+                }
+            }
+        };
+        let left = self.nodes.len();
+        let empty = Node { bounds: Aabb::empty(), start: 0, count: 0 };
+        self.nodes.push(empty);
+        self.nodes.push(empty);
+        self.nodes[node] = Node { bounds, start: left, count: 0 };
+        self.build(centers, left, lo, mid, split);
+        self.build(centers, left + 1, mid, hi, split);
+    }
+
+    /// First bin of the right half of the cheapest SAH split, if any split is valid.
+    fn sah_bin(&self, lo: usize, hi: usize, bin: &impl Fn(usize) -> usize) -> Option<usize> {
+        let mut counts = [0usize; SAH_BINS];
+        let mut bounds = [Aabb::empty(); SAH_BINS];
+        for &i in &self.items[lo..hi] {
+            let b = bin(i);
+            counts[b] += 1;
+            bounds[b] = bounds[b].union(&self.boxes[i]);
+        }
+        let mut best: Option<(T, usize)> = None;
+        for s in 1..SAH_BINS {
+            let (nl, nr): (usize, usize) = (counts[..s].iter().sum(), counts[s..].iter().sum());
+            if nl == 0 || nr == 0 {
+                continue;
+            }
+            let merge = |r: &[Aabb<T>]| r.iter().fold(Aabb::empty(), |acc, b| acc.union(b));
+            let cost = T::from(nl).unwrap() * merge(&bounds[..s]).surface_area()
+                + T::from(nr).unwrap() * merge(&bounds[s..]).surface_area();
+            if best.is_none_or(|(c, _)| cost < c) {
+                best = Some((cost, s));
+            }
+        }
+        best.map(|(_, s)| s)
+    }
+
+    /// Number of items in the hierarchy
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// True when built from no boxes
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Visit every leaf item of the subtrees `enter` accepts.
+    fn visit(&self, mut enter: impl FnMut(&Aabb<T>) -> bool, mut f: impl FnMut(usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !enter(&node.bounds) {
+                continue;
+            }
+            if node.count > 0 {
+                for &i in &self.items[node.start..node.start + node.count] {
+                    if enter(&self.boxes[i]) {
+                        f(i);
+                    }
+                }
+            } else {
+                stack.push(node.start);
+                stack.push(node.start + 1);
+            }
+        }
+    }
+
+    /// Items whose box contains `p`.
+    pub fn query_point(&self, p: Point3<T>) -> Vec<usize> {
+        let mut out = Vec::new();
+        self.visit(|b| b.contains(p), |i| out.push(i));
+        out
+    }
+
+    /// Items whose box overlaps `bb`.
+    pub fn query_aabb(&self, bb: &Aabb<T>) -> Vec<usize> {
+        let mut out = Vec::new();
+        self.visit(|b| b.intersects(bb), |i| out.push(i));
+        out
+    }
+
+    /// Item minimising `dist(i)`, the caller's distance from `p` to item `i`,
+    /// with that distance. `dist` must never be less than the distance from
+    /// `p` to the item's box.
+    pub fn nearest(&self, p: Point3<T>, mut dist: impl FnMut(usize) -> T) -> Option<(usize, T)> {
+        let mut best: Option<(usize, T)> = None;
+        if self.nodes.is_empty() {
+            return best;
+        }
+        let mut stack = vec![(0, T::zero())];
+        while let Some((n, lower)) = stack.pop() {
+            if best.is_some_and(|(_, d)| lower > d) {
+                continue;
+            }
+            let node = &self.nodes[n];
+            if node.count > 0 {
+                for &i in &self.items[node.start..node.start + node.count] {
+                    let d = dist(i);
+                    if best.is_none_or(|(_, bd)| d < bd) {
+                        best = Some((i, d));
+                    }
+                }
+            } else {
+                let (a, b) = (node.start, node.start + 1);
+                let (da, db) =
+                    (self.nodes[a].bounds.distance_squared(p).sqrt(), self.nodes[b].bounds.distance_squared(p).sqrt());
+                // visit the closer child first
+                if da <= db {
+                    stack.push((b, db));
+                    stack.push((a, da));
+                } else {
+                    stack.push((a, da));
+                    stack.push((b, db));
+                }
+            }
+        }
+        best
+    }
+
+    /// First hit along `ray`: `hit(i)` returns the ray parameter at which
+    /// the ray meets item `i`, if it does.
+    pub fn raycast(&self, ray: Ray<T>, mut hit: impl FnMut(usize) -> Option<T>) -> Option<(usize, T)> {
+        let mut best: Option<(usize, T)> = None;
+        if self.nodes.is_empty() {
+            return best;
+        }
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            match ray_box(&ray, &node.bounds) {
+                Some(t) if best.is_none_or(|(_, bt)| t <= bt) => {}
+                _ => continue,
+            }
+            if node.count > 0 {
+                for &i in &self.items[node.start..node.start + node.count] {
+                    if let Some(t) = hit(i).filter(|&t| best.is_none_or(|(_, bt)| t < bt)) {
+                        best = Some((i, t));
+                    }
+                }
+            } else {
+                stack.push(node.start);
+                stack.push(node.start + 1);
+            }
+        }
+        best
+    }
+
+    /// First cell of `mesh` hit by `ray`, testing surface cells as triangle
+    /// fans and volume cells through the fans of their faces.
+    pub fn raycast_mesh(&self, mesh: &Mesh3<T>, ray: Ray<T>) -> Option<(usize, T)> {
+        self.raycast(ray, |c| {
+            let cell = &mesh.cells[c];
+            let faces = if cell.cell_type().dim() == 2 { vec![cell.nodes().to_vec()] } else { cell.faces() };
+            faces
+                .iter()
+                .flat_map(|f| {
+                    (1..f.len() - 1).filter_map(move |k| {
+                        let (a, b, cc) = (mesh.nodes[f[0]], mesh.nodes[f[k]], mesh.nodes[f[k + 1]]);
+                        ray_intersects_triangle(ray, a, b, cc).map(|(t, _, _)| t)
+                    })
+                })
+                .fold(None, |acc: Option<T>, t| Some(acc.map_or(t, |a| a.min(t))))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxes() -> Vec<Aabb<f64>> {
+        // a 10×10×10 lattice of unit boxes
+        let mut out = Vec::new();
+        for k in 0..10 {
+            for j in 0..10 {
+                for i in 0..10 {
+                    let min = Point3::new(i as f64, j as f64, k as f64);
+                    out.push(Aabb { min, max: Point3::new(min.x + 1.0, min.y + 1.0, min.z + 1.0) });
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn queries_match_brute_force() {
+        let bb = boxes();
+        for split in [Split::Median, Split::Sah] {
+            let bvh = Bvh::new(&bb, split);
+            let p = Point3::new(3.5, 4.25, 7.75);
+            assert_eq!(bvh.query_point(p), vec![7 * 100 + 4 * 10 + 3]);
+
+            let q = Aabb { min: Point3::new(2.5, 2.5, 2.5), max: Point3::new(4.5, 3.5, 2.7) };
+            let mut hits = bvh.query_aabb(&q);
+            hits.sort_unstable();
+            let mut brute: Vec<usize> = (0..bb.len()).filter(|&i| bb[i].intersects(&q)).collect();
+            brute.sort_unstable();
+            assert_eq!(hits, brute);
+
+            let far = Point3::new(-3.0, 0.5, 0.5);
+            let (i, d) = bvh.nearest(far, |i| bb[i].center().distance_squared(&far).sqrt()).unwrap();
+            assert_eq!(i, 0);
+            assert!((d - 3.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn ray_finds_first_box() {
+        let bb = boxes();
+        let bvh = Bvh::new(&bb, Split::Sah);
+        let ray = Ray { origin: Point3::new(20.0, 2.5, 5.5), dir: Vec3::new(-1.0, 0.0, 0.0) };
+        let (i, t) = bvh.raycast(ray, |i| ray_box(&ray, &bb[i])).unwrap();
+        assert_eq!(i, 5 * 100 + 2 * 10 + 9);
+        assert!((t - 10.0).abs() < 1e-12);
+    }
+}
@@ -30,11 +30,12 @@ impl<T: Float> Jacobian2x2<T> {
         let d_n2_dxi =  one - eta; let d_n2_deta = -one - xi; // node B
         let d_n3_dxi =  one + eta; let d_n3_deta =  one + xi; // node C
         let d_n4_dxi = -one - eta; let d_n4_deta =  one - xi; // node D
-        // physical grads
-        let m11 = d_n1_dxi * a.x + d_n2_dxi * b.x + d_n3_dxi * c.x + d_n4_dxi * d.x;
-        let m12 = d_n1_deta * a.x + d_n2_deta * b.x + d_n3_deta * c.x + d_n4_deta * d.x;
-        let m21 = d_n1_dxi * a.y + d_n2_dxi * b.y + d_n3_dxi * c.y + d_n4_dxi * d.y;
-        let m22 = d_n1_deta * a.y + d_n2_deta * b.y + d_n3_deta * c.y + d_n4_deta * d.y;
+        // physical grads (the shape functions carry a factor ¼)
+        let q = T::from(0.25).unwrap();
+        let m11 = (d_n1_dxi * a.x + d_n2_dxi * b.x + d_n3_dxi * c.x + d_n4_dxi * d.x) * q;
+        let m12 = (d_n1_deta * a.x + d_n2_deta * b.x + d_n3_deta * c.x + d_n4_deta * d.x) * q;
+        let m21 = (d_n1_dxi * a.y + d_n2_dxi * b.y + d_n3_dxi * c.y + d_n4_dxi * d.y) * q;
+        let m22 = (d_n1_deta * a.y + d_n2_deta * b.y + d_n3_deta * c.y + d_n4_deta * d.y) * q;
         Jacobian2x2 { m11, m12, m21, m22 }
     }
 
@@ -58,6 +59,7 @@ impl<T: Float> Jacobian2x2<T> {
 }
 
 /// Given physical point `p` and quad corners `a,b,c,d`, find (xi,eta) via Newton:
+#[allow(clippy::too_many_arguments)]
 pub fn invert_quad_mapping<T: Float>(
     mut xi: T,
     mut eta: T,
@@ -86,10 +88,7 @@ pub fn invert_quad_mapping<T: Float>(
         }
         // Jacobian & inverse
         let j = Jacobian2x2::for_quad(xi, eta, a, b, c, d);
-        let inv_j = match j.inverse() {
-            Some(inv) => inv,
-            None => return None,
-        };
+        let inv_j = j.inverse()?;
         // Newton update: [Δxi; Δeta] = invJ * [rx; ry]
         let dxi = inv_j.m11 * rx + inv_j.m12 * ry;
         let deta = inv_j.m21 * rx + inv_j.m22 * ry;
@@ -113,6 +112,8 @@ mod tests {
         let d = Point2::new(0.0,1.0);
         let j = Jacobian2x2::for_quad(0.0_f64, 0.0, a,b,c,d );
         assert!(!j.det().is_zero());
+        // reference square has area 4, so det J = area / 4
+        assert!((j.det() - 0.5).abs() < 1e-12);
     }
 
     #[test]
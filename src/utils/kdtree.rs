@@ -0,0 +1,183 @@
+//! Static k-d tree for nearest-neighbour and radius searches.
+//!
+//! [`KdTree`] stores an implicit balanced tree over a permutation of the
+//! input points: the median of each index range splits it along axis
+//! `depth % DIM`. Building is `O(n log n)`; queries return
+//! `(index, distance)` pairs sorted by distance, ties broken by index. Works
+//! for any [`PointN`] (`Point2`, `Point3`) and needs only `alloc`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::utils::kdtree::KdTree;
+//!
+//! let soundings = [Point2::new(0.0_f64, 0.0), Point2::new(2.0, 0.0), Point2::new(0.0, 3.0)];
+//! let tree = KdTree::new(&soundings);
+//! let near = tree.knn(&Point2::new(1.5, 0.5), 2);
+//! assert_eq!(near[0].0, 1);
+//! assert_eq!(near[1].0, 0);
+//! assert_eq!(tree.within_radius(&Point2::new(0.0, 2.0), 1.0).len(), 1);
+//! ```
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{Float, PointN};
+
+/// Balanced k-d tree over a fixed set of points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdTree<P> {
+    points: Vec<P>,
+    /// Point indices in tree order; the median of each range is its node
+    order: Vec<usize>,
+}
+
+fn by_distance<T: Float>(a: &(usize, T), b: &(usize, T)) -> Ordering {
+    a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0))
+}
+
+impl<T: Float, P: PointN<Scalar = T>> KdTree<P> {
+    /// Build a tree over `points`; results refer to indices into this slice.
+    pub fn new(points: &[P]) -> Self {
+        let mut tree = KdTree { points: points.to_vec(), order: (0..points.len()).collect() };
+        tree.build(0, points.len(), 0);
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize, depth: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let axis = depth % P::DIM;
+        let mid = (hi - lo) / 2;
+        let pts = &self.points;
+        self.order[lo..hi].select_nth_unstable_by(mid, |&a, &b| {
+            pts[a].coord(axis).partial_cmp(&pts[b].coord(axis)).unwrap_or(Ordering::Equal)
+        });
+        self.build(lo, lo + mid, depth + 1);
+        self.build(lo + mid + 1, hi, depth + 1);
+    }
+
+    /// Number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// True when built from no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Walk the subtrees that `visit` may still improve. `visit` receives a
+    /// point index and squared distance and returns the current squared
+    /// search bound.
+    fn search(&self, q: &P, lo: usize, hi: usize, depth: usize, bound: &mut T, visit: &mut impl FnMut(usize, T) -> T) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let i = self.order[mid];
+        *bound = visit(i, self.points[i].distance_squared(q));
+        let axis = depth % P::DIM;
+        let diff = q.coord(axis) - self.points[i].coord(axis);
+        let (near, far) = if diff < T::zero() { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.search(q, near.0, near.1, depth + 1, bound, visit);
+        if diff * diff <= *bound {
+            self.search(q, far.0, far.1, depth + 1, bound, visit);
+        }
+    }
+
+    /// The `k` points nearest to `q`, closest first.
+    pub fn knn(&self, q: &P, k: usize) -> Vec<(usize, T)> {
+        let mut best: Vec<(usize, T)> = Vec::with_capacity(k + 1);
+        if k == 0 {
+            return best;
+        }
+        let mut bound = T::infinity();
+        self.search(q, 0, self.order.len(), 0, &mut bound, &mut |i, d2| {
+            if best.len() < k || by_distance(&(i, d2), &best[k - 1]) == Ordering::Less {
+                let at = best.partition_point(|e| by_distance(e, &(i, d2)) == Ordering::Less);
+                best.insert(at, (i, d2));
+                best.truncate(k);
+            }
+            if best.len() == k { best[k - 1].1 } else { T::infinity() }
+        });
+        best.into_iter().map(|(i, d2)| (i, d2.sqrt())).collect()
+    }
+
+    /// The point nearest to `q`, if the tree is not empty.
+    pub fn nearest(&self, q: &P) -> Option<(usize, T)> {
+        self.knn(q, 1).pop()
+    }
+
+    /// All points within distance `r` of `q` (inclusive), closest first.
+    pub fn within_radius(&self, q: &P, r: T) -> Vec<(usize, T)> {
+        let r2 = r * r;
+        let mut out = Vec::new();
+        let mut bound = r2;
+        self.search(q, 0, self.order.len(), 0, &mut bound, &mut |i, d2| {
+            if d2 <= r2 {
+                out.push((i, d2));
+            }
+            r2
+        });
+        out.sort_by(by_distance);
+        out.into_iter().map(|(i, d2)| (i, d2.sqrt())).collect()
+    }
+
+    /// [`knn`](Self::knn) for every query point.
+    pub fn knn_batch(&self, queries: &[P], k: usize) -> Vec<Vec<(usize, T)>> {
+        queries.iter().map(|q| self.knn(q, k)).collect()
+    }
+
+    /// [`within_radius`](Self::within_radius) for every query point.
+    pub fn within_radius_batch(&self, queries: &[P], r: T) -> Vec<Vec<(usize, T)>> {
+        queries.iter().map(|q| self.within_radius(q, r)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point3;
+
+    /// Deterministic pseudo-random points in the unit cube.
+    fn cloud(n: usize) -> Vec<Point3<f64>> {
+        let mut s: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || {
+            s ^= s << 13;
+            s ^= s >> 7;
+            s ^= s << 17;
+            (s >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..n).map(|_| Point3::new(next(), next(), next())).collect()
+    }
+
+    #[test]
+    fn knn_and_radius_match_brute_force() {
+        let pts = cloud(500);
+        let tree = KdTree::new(&pts);
+        let queries = cloud(20);
+        let knn = tree.knn_batch(&queries, 7);
+        let radius = tree.within_radius_batch(&queries, 0.15);
+        for (qi, q) in queries.iter().enumerate() {
+            let mut brute: Vec<(usize, f64)> =
+                pts.iter().enumerate().map(|(i, p)| (i, p.distance_squared(q).sqrt())).collect();
+            brute.sort_by(by_distance);
+            let ids: Vec<usize> = knn[qi].iter().map(|e| e.0).collect();
+            let expect: Vec<usize> = brute.iter().take(7).map(|e| e.0).collect();
+            assert_eq!(ids, expect);
+            let inside: Vec<usize> = brute.iter().filter(|e| e.1 <= 0.15).map(|e| e.0).collect();
+            assert_eq!(radius[qi].iter().map(|e| e.0).collect::<Vec<_>>(), inside);
+        }
+    }
+
+    #[test]
+    fn empty_and_small_trees() {
+        let empty: KdTree<Point3<f64>> = KdTree::new(&[]);
+        assert!(empty.nearest(&Point3::new(0.0, 0.0, 0.0)).is_none());
+        let one = KdTree::new(&[Point3::new(1.0, 2.0, 2.0)]);
+        assert_eq!(one.knn(&Point3::new(0.0, 0.0, 0.0), 3), vec![(0, 3.0)]);
+    }
+}
@@ -3,3 +3,9 @@
 pub mod jacobian;
 /// Axis-aligned bounding box utilities.
 pub mod aabb;
+/// Tolerance-based vertex welding.
+pub mod weld;
+/// Bounding volume hierarchy over cell boxes.
+pub mod bvh;
+/// k-d tree for nearest-neighbour searches.
+pub mod kdtree;
@@ -0,0 +1,129 @@
+//! Tolerance-based vertex welding.
+//!
+//! [`weld_vertices`] merges points closer than `tol` using a uniform hash
+//! grid with cell size `tol`: each point is compared only against the kept
+//! points in its own and the neighbouring grid cells, so the cost is
+//! `O(n log n)` for well-spread input. Points are processed in order and the
+//! first point of each cluster is kept, so the result is deterministic;
+//! chains of points each within `tol` of the next may still split into
+//! several clusters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use mesh_geometry::Point2;
+//! use mesh_geometry::utils::weld::weld_vertices;
+//!
+//! let pts = [Point2::new(0.0_f64, 0.0), Point2::new(1.0, 0.0), Point2::new(1e-12, 0.0)];
+//! let weld = weld_vertices(&pts, 1e-9);
+//! assert_eq!(weld.remap, vec![0, 1, 0]);
+//! assert_eq!(weld.points.len(), 2);
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Float, PointN};
+
+/// Result of [`weld_vertices`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weld<P> {
+    /// For each input point, the index of its merged point
+    pub remap: Vec<usize>,
+    /// Merged coordinates (first point of each cluster)
+    pub points: Vec<P>,
+}
+
+/// Uniform hash grid over point indices, keyed on `floor(coord / cell)`
+/// along every axis.
+pub(crate) struct PointGrid<T> {
+    cell: T,
+    cells: BTreeMap<[i64; 3], Vec<usize>>,
+}
+
+impl<T: Float> PointGrid<T> {
+    /// Empty grid with cells of size `cell` (which must be positive).
+    pub(crate) fn new(cell: T) -> Self {
+        PointGrid { cell, cells: BTreeMap::new() }
+    }
+
+    fn key<P: PointN<Scalar = T>>(&self, p: &P) -> [i64; 3] {
+        let mut k = [0i64; 3];
+        for (axis, k) in k.iter_mut().enumerate().take(P::DIM) {
+            *k = (p.coord(axis) / self.cell).floor().to_i64().unwrap_or(0);
+        }
+        k
+    }
+
+    /// Store `index` in the grid cell of `p`.
+    pub(crate) fn insert<P: PointN<Scalar = T>>(&mut self, p: &P, index: usize) {
+        let k = self.key(p);
+        self.cells.entry(k).or_default().push(index);
+    }
+
+    /// Indices stored in the grid cell of `p` and its neighbours: every
+    /// index whose point lies within `cell` of `p`, plus some further away.
+    pub(crate) fn near<P: PointN<Scalar = T>>(&self, p: &P) -> impl Iterator<Item = usize> + '_ {
+        let k = self.key(p);
+        // offsets in x-major order; axes beyond the point's dimension stay at 0
+        (0..27i64)
+            .map(|i| [i / 9 - 1, i / 3 % 3 - 1, i % 3 - 1])
+            .filter(|d| (P::DIM..3).all(|axis| d[axis] == 0))
+            .filter_map(move |d| self.cells.get(&[k[0] + d[0], k[1] + d[1], k[2] + d[2]]))
+            .flatten()
+            .copied()
+    }
+}
+
+/// Merge points within distance `tol` of each other.
+///
+/// Panics if `tol` is not positive.
+pub fn weld_vertices<T: Float, P: PointN<Scalar = T>>(points: &[P], tol: T) -> Weld<P> {
+    assert!(tol > T::zero(), "weld_vertices requires a positive tolerance");
+    let tol2 = tol * tol;
+    let mut grid = PointGrid::new(tol);
+    let mut remap = Vec::with_capacity(points.len());
+    let mut merged: Vec<P> = Vec::new();
+    for p in points {
+        let found = grid.near(p).find(|&m| merged[m].distance_squared(p) <= tol2);
+        let m = found.unwrap_or_else(|| {
+            merged.push(*p);
+            grid.insert(p, merged.len() - 1);
+            merged.len() - 1
+        });
+        remap.push(m);
+    }
+    Weld { remap, points: merged }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point3;
+
+    #[test]
+    fn stitched_grids_share_interface_nodes() {
+        // two 3×3 node grids meeting at x = 1, offset by roundoff
+        let mut pts = Vec::new();
+        for (x0, eps) in [(0.0_f64, 0.0), (1.0, 1e-13)] {
+            for j in 0..3 {
+                for i in 0..3 {
+                    pts.push(Point3::new(x0 + 0.5 * i as f64 + eps, 0.5 * j as f64 - eps, 0.0));
+                }
+            }
+        }
+        let weld = weld_vertices(&pts, 1e-8);
+        assert_eq!(weld.points.len(), 15);
+        for j in 0..3 {
+            assert_eq!(weld.remap[9 + 3 * j], weld.remap[3 * j + 2]);
+        }
+    }
+
+    #[test]
+    fn neighbours_across_grid_cells_merge() {
+        // straddle a grid-cell boundary at x = 0
+        let pts = [Point3::new(-1e-4_f64, 0.0, 0.0), Point3::new(1e-4, 0.0, 0.0), Point3::new(5e-3, 0.0, 0.0)];
+        let weld = weld_vertices(&pts, 1e-3);
+        assert_eq!(weld.remap, vec![0, 0, 1]);
+    }
+}